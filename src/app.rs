@@ -1,13 +1,55 @@
 //! Main application structure and message routing
 //!
 //! This module implements the root App following the Elm architecture pattern.
-//! It manages navigation between different modes (Home, Practice, Learning, Settings)
+//! It manages navigation between different modes (Home, Practice, Learning, Conversation,
+//! Settings, Developer)
 //! and routes messages to the appropriate view handlers.
+//! It also owns the services and coordinates work that spans several views,
+//! such as generating a deck from the Home input, updating the learner
+//! report after a practice session and checking LLM output against the local
+//! dictionary.
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use iced::task;
 use iced::{Element, Task};
 
+use crate::constants;
+use crate::error::AppError;
+use crate::models::deck::{GradedRewrite, GradedText, LearningText, ReportSource, title_from_text};
+use crate::models::flashcard::{FlashCard, ReviewLog, SRSData, SchedulerKind};
+use crate::models::word::sentences;
+use crate::models::{CardType, JLPTLevel, Language, WordExplanation};
+use crate::services::card_generator::{self, GeneratedCard, GenerationProgress};
+use crate::services::custom_study::StudyPreset;
+use crate::services::database::DatabaseError;
+use crate::services::dictionary::Dictionary;
+use crate::services::distractors::{DistractorPool, WRONG_ANSWER_COUNT};
+use crate::services::fsrs::{self, FsrsParameters, Rating};
+use crate::services::learner_report::{ReportInputs, ReviewOutcome};
+use crate::services::llm::audit;
+use crate::services::llm::governor::{GovernorConfig, RequestGovernor};
+use crate::services::llm::mock::MockProvider;
+use crate::services::llm::{LLMError, Provider};
+use crate::services::load_balancer::{self, DueForecast, LoadBalancing};
+use crate::services::scheduler::{self, LearningSteps, LeechPolicy};
+use crate::services::study_day::{self, DailyCounts, DailyLimits, DayRollover, DayStats};
+use crate::services::study_queue::{self, QueueOptions, QueueOrder};
+use crate::services::tutor_tools::{self, TutorTools};
+use crate::services::verification;
+use crate::services::{DatabaseService, LLMService, TokenizerService};
 use crate::views::{
-    home::HomeView, learning::LearningView, practice::PracticeView, settings::SettingsView,
+    conversation::ConversationView,
+    custom_session::CustomSessionView,
+    developer::DeveloperView,
+    home::HomeView,
+    learning::Companion,
+    learning::LearningView,
+    practice::{PracticeAnswer, PracticeView},
+    settings::SettingsView,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,7 +57,9 @@ pub enum AppMode {
     Home,
     Practice,
     Learning,
+    Conversation,
     Settings,
+    Developer,
 }
 
 pub struct App {
@@ -23,7 +67,32 @@ pub struct App {
     home_view: HomeView,
     practice_view: PracticeView,
     learning_view: LearningView,
+    conversation_view: ConversationView,
     settings_view: SettingsView,
+    developer_view: DeveloperView,
+    database: Arc<DatabaseService>,
+    governor: Arc<RequestGovernor>,
+    dictionary: Option<Arc<Dictionary>>,
+    card_generation: Option<CardGeneration>,
+    fsrs_parameters: FsrsParameters,
+    learning_steps: LearningSteps,
+    daily_limits: DailyLimits,
+    day_rollover: DayRollover,
+    queue_options: QueueOptions,
+    self_rating: bool,
+    leech_policy: LeechPolicy,
+    load_balancing: LoadBalancing,
+    /// Reviews due per study day, built when a session starts and updated per answer
+    due_forecast: DueForecast,
+}
+
+/// A batch flashcard generation started from the Home view
+struct CardGeneration {
+    source_text: String,
+    progress: GenerationProgress,
+    cards: Vec<GeneratedCard>,
+    last_error: Option<LLMError>,
+    handle: task::Handle,
 }
 
 #[derive(Debug, Clone)]
@@ -37,25 +106,128 @@ pub enum Message {
     // Learning messages
     Learning(crate::views::learning::Message),
 
+    // Conversation messages
+    Conversation(crate::views::conversation::Message),
+
     // Settings messages
     Settings(crate::views::settings::Message),
+
+    // Developer log messages
+    Developer(crate::views::developer::Message),
+
+    // Batch card generation results
+    CardGenerated(Result<GeneratedCard, LLMError>),
+
+    // Replacement for a practice card flagged by verification
+    CardRegenerated(String, Result<CardType, LLMError>),
+
+    // Graded reader rewrite of the text with this id
+    TextRewritten(String, JLPTLevel, Result<GradedRewrite, LLMError>),
+
+    // Learner report regeneration result
+    LearnerReportGenerated(Result<String, LLMError>),
 }
 
-impl Default for App {
-    fn default() -> Self {
-        Self {
+impl App {
+    pub fn new() -> (Self, Task<Message>) {
+        let database = DatabaseService::new(PathBuf::from(constants::app::DATABASE_FILE))
+            .or_else(|e| {
+                eprintln!("{}; falling back to an in-memory database", e);
+                DatabaseService::new_in_memory()
+            })
+            .expect("Failed to initialize database");
+        let database = Arc::new(database);
+
+        let governor_config = load_governor_config(&database);
+        let governor = Arc::new(RequestGovernor::new(governor_config, Arc::clone(&database)));
+        let mut settings_view = SettingsView::default();
+        settings_view.set_governor_config(governor_config);
+        settings_view.set_demo_mode(
+            database
+                .load_settings(constants::settings::DEMO_MODE)
+                .ok()
+                .flatten()
+                .is_some_and(|value| value == "true"),
+        );
+        if let Ok(Some(tag)) = database.load_settings(constants::settings::EXPLANATION_LANGUAGE)
+            && let Some(language) = Language::from_tag(&tag)
+        {
+            settings_view.set_language(language);
+        }
+
+        // The dictionary is optional; without it only tokenizer readings are checked
+        let dictionary_path = Path::new(constants::app::DICTIONARY_FILE);
+        let dictionary = dictionary_path
+            .exists()
+            .then(|| Dictionary::load(dictionary_path))
+            .and_then(|loaded| {
+                loaded
+                    .inspect_err(|e| eprintln!("Failed to load dictionary: {}", e))
+                    .ok()
+            })
+            .map(Arc::new);
+
+        // FSRS decks use the default parameters until they are fitted
+        let fsrs_parameters = database
+            .load_settings(constants::settings::FSRS_PARAMETERS)
+            .ok()
+            .flatten()
+            .filter(|json| !json.is_empty())
+            .and_then(|json| {
+                serde_json::from_str::<FsrsParameters>(&json)
+                    .inspect_err(|e| eprintln!("Ignoring unreadable FSRS parameters: {}", e))
+                    .ok()
+            });
+        settings_view.set_fsrs_parameters(fsrs_parameters.is_some(), None);
+        let learning_steps = load_learning_steps(&database);
+        settings_view.set_learning_steps(&learning_steps);
+        let (daily_limits, day_rollover) = load_study_day(&database);
+        if let Err(e) = database.stamp_migrated_review_days(&day_rollover) {
+            eprintln!("Failed to date migrated reviews: {}", e);
+        }
+        settings_view.set_daily_limits(daily_limits);
+        settings_view.set_day_rollover(day_rollover);
+        let queue_options = load_queue_options(&database);
+        settings_view.set_queue_options(queue_options);
+        let self_rating = load_setting(&database, constants::settings::SELF_RATING)
+            .is_some_and(|value| value == "true");
+        settings_view.set_self_rating(self_rating);
+        let leech_policy = load_leech_policy(&database);
+        settings_view.set_leech_policy(leech_policy);
+        let load_balancing = load_load_balancing(&database);
+        settings_view.set_load_balancing(load_balancing);
+
+        let mut conversation_view = ConversationView::default();
+        if let Ok(Some(level)) = database.load_settings(constants::settings::CONVERSATION_LEVEL) {
+            conversation_view.set_level(JLPTLevel::from_str(&level));
+        }
+
+        let mut app = Self {
             mode: AppMode::Home,
             home_view: HomeView::default(),
             practice_view: PracticeView::default(),
             learning_view: LearningView::default(),
-            settings_view: SettingsView::default(),
-        }
-    }
-}
+            conversation_view,
+            settings_view,
+            developer_view: DeveloperView::default(),
+            database,
+            governor,
+            dictionary,
+            card_generation: None,
+            fsrs_parameters: fsrs_parameters.unwrap_or_default(),
+            learning_steps,
+            daily_limits,
+            day_rollover,
+            queue_options,
+            self_rating,
+            leech_policy,
+            load_balancing,
+            due_forecast: DueForecast::default(),
+        };
+        app.refresh_home_lists();
+        app.refresh_report_history();
 
-impl App {
-    pub fn new() -> (Self, Task<Message>) {
-        (Self::default(), Task::none())
+        (app, Task::none())
     }
 
     pub fn title(&self) -> String {
@@ -63,7 +235,9 @@ impl App {
             AppMode::Home => "nihon.rs - Home".to_string(),
             AppMode::Practice => "nihon.rs - Practice".to_string(),
             AppMode::Learning => "nihon.rs - Learning".to_string(),
+            AppMode::Conversation => "nihon.rs - Conversation".to_string(),
             AppMode::Settings => "nihon.rs - Settings".to_string(),
+            AppMode::Developer => "nihon.rs - Developer Log".to_string(),
         }
     }
 
@@ -76,7 +250,22 @@ impl App {
             Message::Home(msg) => self.handle_home_message(msg),
             Message::Practice(msg) => self.handle_practice_message(msg),
             Message::Learning(msg) => self.handle_learning_message(msg),
+            Message::Conversation(msg) => self.handle_conversation_message(msg),
             Message::Settings(msg) => self.handle_settings_message(msg),
+            Message::Developer(msg) => self.handle_developer_message(msg),
+            Message::CardGenerated(result) => self.handle_card_generated(result),
+            Message::CardRegenerated(card_id, result) => {
+                self.handle_card_regenerated(&card_id, result);
+                Task::none()
+            }
+            Message::TextRewritten(source_id, level, result) => {
+                self.handle_text_rewritten(&source_id, level, result);
+                Task::none()
+            }
+            Message::LearnerReportGenerated(result) => {
+                self.handle_learner_report_generated(result);
+                Task::none()
+            }
         }
     }
 
@@ -84,22 +273,1480 @@ impl App {
         self.mode = mode;
     }
 
+    /// Build an LLM service from the current settings
+    ///
+    /// Falls back to the `GEMINI_API_KEY` environment variable when no key is set.
+    /// In demo mode, requests are answered from the fixture files instead.
+    /// Every call is recorded in the audit log.
+    fn llm_service(&self) -> LLMService {
+        let api_key = Some(self.settings_view.api_key().trim().to_string())
+            .filter(|key| !key.is_empty())
+            .or_else(|| std::env::var("GEMINI_API_KEY").ok());
+
+        let mut llm = LLMService::new(
+            api_key,
+            self.settings_view.user_profile().to_string(),
+            Arc::clone(&self.governor),
+        );
+        llm.set_audit_log(Arc::clone(&self.database));
+        llm.set_language(self.settings_view.language());
+        match self.database.get_latest_report() {
+            Ok(Some(report)) => llm.set_learner_report(report.content),
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to load learner report: {}", e),
+        }
+        if self.settings_view.demo_mode() {
+            llm.set_provider(Provider::Mock(MockProvider::installed()));
+        }
+        llm
+    }
+
+    /// Tokenize and save the Home input, then study it in Learning mode
+    fn start_learning(&mut self) {
+        let original_text = self.home_view.input_text().trim().to_string();
+        let saved = TokenizerService::default()
+            .tokenize(&original_text)
+            .map_err(AppError::from)
+            .and_then(|segments| {
+                let text = LearningText::new(original_text, segments);
+                self.database.save_text(text.clone())?;
+                Ok(text)
+            });
+
+        match saved {
+            Ok(text) => {
+                self.home_view.set_status(None);
+                self.refresh_home_lists();
+                self.learning_view.load_text(text, Vec::new());
+                self.navigate_to(AppMode::Learning);
+            }
+            Err(e) => self.home_view.set_status(Some(e.to_string())),
+        }
+    }
+
+    /// Reopen a saved text together with its conversation
+    fn open_text(&mut self, id: &str) {
+        let loaded = self.database.get_text(id).and_then(|text| {
+            let turns = self.database.get_conversation(id)?;
+            Ok(text.map(|text| (text, turns)))
+        });
+
+        match loaded {
+            Ok(Some((text, turns))) => {
+                let history = turns
+                    .into_iter()
+                    .map(|turn| (turn.question, turn.answer))
+                    .collect();
+                self.learning_view.load_text(text, history);
+                self.learning_view.set_companion(self.load_companion(id));
+                self.navigate_to(AppMode::Learning);
+            }
+            Ok(None) => self
+                .home_view
+                .set_status(Some(format!("Saved text not found: {}", id))),
+            Err(e) => self
+                .home_view
+                .set_status(Some(AppError::from(e).to_string())),
+        }
+    }
+
+    /// Practice the due and new cards of the given decks
+    ///
+    /// The queue stays within what is left of today's limits.
+    fn study_decks(&mut self, deck_ids: &[String]) {
+        let now = Utc::now();
+        let queue = self.build_queue(deck_ids, now);
+
+        match queue {
+            Ok(cards) if cards.is_empty() => self.home_view.set_status(Some(
+                "Nothing left to study today. Come back tomorrow or raise the daily limits in Settings."
+                    .into(),
+            )),
+            Ok(cards) => self.start_practice(cards),
+            Err(e) => self
+                .home_view
+                .set_status(Some(AppError::from(e).to_string())),
+        }
+    }
+
+    /// Start a practice session with the given cards
+    fn start_practice(&mut self, mut cards: Vec<FlashCard>) {
+        self.add_local_distractors(&mut cards);
+        let mismatches: Vec<_> = cards
+            .iter()
+            .map(|card| (card.id.clone(), self.verify_card(&card.card_type, None)))
+            .collect();
+        self.due_forecast = match self.database.get_all_cards() {
+            Ok(all_cards) => DueForecast::new(&all_cards, &self.day_rollover),
+            Err(e) => {
+                eprintln!("Failed to load the review forecast: {}", e);
+                DueForecast::default()
+            }
+        };
+        self.practice_view.close_custom_session();
+        self.practice_view.set_cards(cards);
+        for (card_id, mismatches) in mismatches {
+            self.practice_view.set_mismatches(&card_id, mismatches);
+        }
+        self.navigate_to(AppMode::Practice);
+    }
+
+    /// Show the custom session builder in Practice mode
+    fn open_custom_session(&mut self) {
+        let presets = load_study_presets(&self.database);
+        let decks = self.home_view.decks().to_vec();
+        self.practice_view
+            .open_custom_session(CustomSessionView::new(decks, presets));
+        self.navigate_to(AppMode::Practice);
+    }
+
+    /// Start a session with the cards matching the custom session filter
+    fn start_custom_session(&mut self) {
+        let Some(filter) = self
+            .practice_view
+            .custom_session()
+            .and_then(|builder| builder.filter())
+        else {
+            return;
+        };
+        let now = Utc::now();
+        let selected = self.database.get_all_cards().and_then(|cards| {
+            let reviews = self.database.get_reviews()?;
+            Ok(filter.select(cards, &reviews, &self.day_rollover, now))
+        });
+
+        let status = match selected {
+            Ok(cards) if cards.is_empty() => "No cards match these filters.".to_string(),
+            Ok(cards) => {
+                self.schedule_unrated_answer();
+                self.start_practice(cards);
+                self.practice_view
+                    .set_affects_scheduling(filter.affects_scheduling);
+                return;
+            }
+            Err(e) => AppError::from(e).to_string(),
+        };
+        if let Some(builder) = self.practice_view.custom_session_mut() {
+            builder.set_status(Some(status));
+        }
+    }
+
+    /// Persist the custom session presets of the builder
+    fn save_study_presets(&self) {
+        let Some(builder) = self.practice_view.custom_session() else {
+            return;
+        };
+        match serde_json::to_string(builder.presets()) {
+            Ok(json) => self.save_setting(constants::settings::STUDY_PRESETS, &json),
+            Err(e) => eprintln!("Failed to save custom session presets: {}", e),
+        }
+    }
+
+    /// Gather the practice queue of the given decks from the cards due at `now`
+    fn build_queue(
+        &self,
+        deck_ids: &[String],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<FlashCard>, DatabaseError> {
+        let mut cards = Vec::new();
+        for deck_id in deck_ids {
+            cards.extend(self.database.get_deck_cards(deck_id)?);
+        }
+
+        let reviews = self.database.get_reviews()?;
+        let counts = DailyCounts::today(&reviews, &self.day_rollover, now);
+        let studied = study_day::studied_today(&reviews, &self.day_rollover, now);
+        let studied_cards: Vec<FlashCard> = self
+            .database
+            .get_all_cards()?
+            .into_iter()
+            .filter(|card| studied.contains(card.id.as_str()))
+            .collect();
+
+        Ok(study_queue::build(
+            cards,
+            &studied_cards,
+            &self.daily_limits,
+            &counts,
+            &self.queue_options,
+            now,
+        ))
+    }
+
+    /// Give cards with fewer than three wrong answers some from other cards and the dictionary
+    fn add_local_distractors(&self, cards: &mut [FlashCard]) {
+        if cards
+            .iter()
+            .all(|card| card.card_type.wrong_answers().len() >= WRONG_ANSWER_COUNT)
+        {
+            return;
+        }
+
+        let all_cards = match self.database.get_all_cards() {
+            Ok(all_cards) => all_cards,
+            Err(e) => {
+                eprintln!("Failed to load cards for wrong answers: {}", e);
+                return;
+            }
+        };
+        let pool = DistractorPool::new(
+            all_cards.iter().map(|card| &card.card_type),
+            self.dictionary.as_deref(),
+        );
+
+        for card in cards.iter_mut() {
+            let mut card_type = card.card_type.clone();
+            if !pool.top_up(&mut card_type) {
+                continue;
+            }
+            match self.database.update_card_content(&card.id, card_type) {
+                Ok(updated) => *card = updated,
+                Err(e) => eprintln!("Failed to save wrong answers: {}", e),
+            }
+        }
+    }
+
+    /// Compare a vocabulary card with the local dictionary and reading
+    fn verify_card(
+        &self,
+        card: &CardType,
+        local_reading: Option<&str>,
+    ) -> Vec<verification::Mismatch> {
+        match card {
+            CardType::Vocabulary(vocab) => {
+                verification::verify_card(vocab, local_reading, self.dictionary.as_deref())
+            }
+            CardType::Grammar(_) => Vec::new(),
+        }
+    }
+
+    /// Ask the LLM for a new version of the current practice card
+    fn regenerate_current_card(&mut self) -> Task<Message> {
+        let Some(card) = self.practice_view.current_card() else {
+            return Task::none();
+        };
+        let task = self.regenerate_card(card.id.clone(), card.card_type.clone());
+
+        self.practice_view.set_regenerating(true);
+        task
+    }
+
+    /// Ask the LLM for a new version of a leech listed on the Home screen
+    fn regenerate_leech(&mut self, card_id: &str) -> Task<Message> {
+        let Some(card) = self.home_view.leech(card_id) else {
+            return Task::none();
+        };
+        let task = self.regenerate_card(card_id.to_string(), card.card_type.clone());
+
+        self.home_view
+            .set_regenerating_leech(Some(card_id.to_string()));
+        task
+    }
+
+    /// Generate a new version of a vocabulary or grammar card, using its first example as context
+    fn regenerate_card(&self, card_id: String, card: CardType) -> Task<Message> {
+        let llm = self.llm_service();
+        // Grammar distractors the LLM repeats or leaves out come from the other cards
+        let other_cards: Vec<CardType> = match &card {
+            CardType::Grammar(_) => self
+                .database
+                .get_all_cards()
+                .unwrap_or_else(|e| {
+                    eprintln!("Failed to load cards for wrong answers: {}", e);
+                    Vec::new()
+                })
+                .into_iter()
+                .filter(|other| other.id != card_id)
+                .map(|other| other.card_type)
+                .collect(),
+            CardType::Vocabulary(_) => Vec::new(),
+        };
+        let dictionary = self.dictionary.clone();
+        Task::perform(
+            async move {
+                let pool = DistractorPool::new(&other_cards, dictionary.as_deref());
+                let front = card.front();
+                let context = card
+                    .example_sentences()
+                    .first()
+                    .map_or(front, |example| example.japanese.as_str());
+                match &card {
+                    CardType::Vocabulary(vocab) => llm
+                        .generate_vocabulary_card(front, &vocab.hiragana, front, context)
+                        .await
+                        .map(CardType::Vocabulary),
+                    CardType::Grammar(_) => llm
+                        .generate_grammar_card(front, context, &pool)
+                        .await
+                        .map(CardType::Grammar),
+                }
+            },
+            move |result| Message::CardRegenerated(card_id.clone(), result),
+        )
+    }
+
+    fn handle_card_regenerated(&mut self, card_id: &str, result: Result<CardType, LLMError>) {
+        self.practice_view.set_regenerating(false);
+        self.home_view.set_regenerating_leech(None);
+        let updated = result.map_err(AppError::from).and_then(|card| {
+            self.database
+                .update_card_content(card_id, card)
+                .map_err(AppError::from)
+        });
+
+        match updated {
+            Ok(card) => {
+                let card = self.clear_leech(card);
+                let mismatches = self.verify_card(&card.card_type, None);
+                self.practice_view.set_mismatches(card_id, mismatches);
+                self.practice_view.replace_card(card);
+                self.refresh_home_lists();
+            }
+            Err(e) => {
+                self.home_view
+                    .set_status(Some(format!("Failed to regenerate card: {}", e)));
+                eprintln!("Failed to regenerate card: {}", e);
+            }
+        }
+    }
+
+    /// Save the answer the user rewrote for a leech
+    fn save_leech_edit(&mut self) {
+        let Some((card_id, answer)) = self.home_view.leech_edit() else {
+            return;
+        };
+        let Some(card) = self.home_view.leech(card_id) else {
+            return;
+        };
+        let card_id = card_id.to_string();
+        let mut card_type = card.card_type.clone();
+        card_type.set_correct_answer(answer.trim().to_string());
+
+        match self.database.update_card_content(&card_id, card_type) {
+            Ok(card) => {
+                self.clear_leech(card);
+                self.home_view.finish_leech_edit();
+                self.refresh_home_lists();
+            }
+            Err(e) => self
+                .home_view
+                .set_status(Some(AppError::from(e).to_string())),
+        }
+    }
+
+    /// Untag a leech whose content was just fixed
+    fn clear_leech(&self, mut card: FlashCard) -> FlashCard {
+        if !card.srs_data.leech {
+            return card;
+        }
+        scheduler::clear_leech(&mut card.srs_data);
+        if let Err(e) = self
+            .database
+            .update_card_srs(&card.id, card.srs_data.clone())
+        {
+            eprintln!("Failed to clear leech: {}", e);
+        }
+        card
+    }
+
+    /// Overwrite the flagged fields of the current practice card with local data
+    fn accept_local_card_data(&mut self) {
+        let Some(card) = self.practice_view.current_card() else {
+            return;
+        };
+        let CardType::Vocabulary(vocab) = &card.card_type else {
+            return;
+        };
+        let card_id = card.id.clone();
+        let mut vocab = vocab.clone();
+        verification::accept_local_card(&mut vocab, self.practice_view.mismatches(&card_id));
+
+        match self
+            .database
+            .update_card_content(&card_id, CardType::Vocabulary(vocab))
+        {
+            Ok(card) => {
+                self.practice_view.set_mismatches(&card_id, Vec::new());
+                self.practice_view.replace_card(card);
+            }
+            Err(e) => eprintln!("Failed to update card: {}", e),
+        }
+    }
+
+    /// Request an explanation for the word the Learning view is waiting on
+    ///
+    /// The user's correction of the word is used first, then a cached
+    /// explanation, and only then the LLM. Regenerating skips both, and the
+    /// new explanation replaces the cache entry and the correction.
+    fn explain_selected_word(&self) -> Task<Message> {
+        use crate::views::learning::Message as LearningMessage;
+
+        let Some((index, segment)) = self.learning_view.pending_explanation() else {
+            return Task::none();
+        };
+        let segment = segment.clone();
+        let sentence = card_generator::sentence_containing(
+            self.learning_view.original_text(),
+            &segment.surface,
+        )
+        .to_string();
+        let language = self.settings_view.language();
+        let cache_key = explanation_cache_key(&segment.base_form, &sentence, language);
+        let feedback = self
+            .learning_view
+            .regeneration_feedback()
+            .map(str::to_string);
+
+        if feedback.is_none() {
+            // Corrections written for another language no longer apply
+            match self.database.get_edited_explanation(&segment.base_form) {
+                Ok(Some(explanation)) if explanation.language == language => {
+                    return Task::done(Message::Learning(LearningMessage::ExplanationReceived(
+                        index,
+                        explanation,
+                        Vec::new(),
+                    )));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to load corrected explanation: {}", e),
+            }
+            match self.database.get_cached_response(&cache_key) {
+                Ok(Some(cached)) => match serde_json::from_str::<WordExplanation>(&cached) {
+                    Ok(explanation) => {
+                        let mismatches = verification::verify_explanation(
+                            &segment,
+                            &explanation,
+                            self.dictionary.as_deref(),
+                        );
+                        return Task::done(Message::Learning(
+                            LearningMessage::ExplanationReceived(index, explanation, mismatches),
+                        ));
+                    }
+                    Err(e) => eprintln!("Ignoring unreadable cached explanation: {}", e),
+                },
+                Ok(None) => {}
+                Err(e) => eprintln!("Failed to load cached explanation: {}", e),
+            }
+        }
+
+        let llm = self.llm_service();
+        let dictionary = self.dictionary.clone();
+        let database = Arc::clone(&self.database);
+
+        Task::perform(
+            async move {
+                let explanation = match (&feedback, &segment.explanation) {
+                    (Some(feedback), Some(previous)) => {
+                        llm.regenerate_explanation(&segment, &sentence, previous, feedback)
+                            .await?
+                    }
+                    _ => {
+                        llm.explain_word(
+                            &segment.surface,
+                            &segment.reading,
+                            &segment.base_form,
+                            &sentence,
+                        )
+                        .await?
+                    }
+                };
+
+                if let Ok(json) = serde_json::to_string(&explanation)
+                    && let Err(e) = database.cache_llm_response(&cache_key, &json)
+                {
+                    eprintln!("Failed to cache explanation: {}", e);
+                }
+                if feedback.is_some()
+                    && let Err(e) = database.remove_edited_explanation(&segment.base_form)
+                {
+                    eprintln!("Failed to drop corrected explanation: {}", e);
+                }
+
+                let mismatches =
+                    verification::verify_explanation(&segment, &explanation, dictionary.as_deref());
+                Ok((explanation, mismatches))
+            },
+            move |result: Result<_, LLMError>| {
+                Message::Learning(match result {
+                    Ok((explanation, mismatches)) => {
+                        LearningMessage::ExplanationReceived(index, explanation, mismatches)
+                    }
+                    Err(e) => LearningMessage::ExplanationError(index, e.to_string()),
+                })
+            },
+        )
+    }
+
+    /// Request a translation for the sentence the Learning view is waiting on
+    fn translate_sentence(&self) -> Task<Message> {
+        use crate::views::learning::Message as LearningMessage;
+
+        let Some((start, segments)) = self.learning_view.pending_translation() else {
+            return Task::none();
+        };
+        let segments = segments.to_vec();
+        let llm = self.llm_service();
+
+        Task::perform(
+            async move { llm.translate_sentence(&segments).await },
+            move |result| {
+                Message::Learning(match result {
+                    Ok(translation) => LearningMessage::TranslationReceived(start, translation),
+                    Err(e) => LearningMessage::TranslationFailed(start, e.to_string()),
+                })
+            },
+        )
+    }
+
+    /// Send the pending question of the Learning view to the LLM
+    fn ask_question(&self) -> Task<Message> {
+        use crate::views::learning::Message as LearningMessage;
+
+        let Some(question) = self.learning_view.pending_question() else {
+            return Task::none();
+        };
+        let question = question.to_string();
+        let context = self.learning_view.original_text().to_string();
+        let history = self.learning_view.answered_questions().to_vec();
+        let llm = self.llm_service();
+        let tools = TutorTools::new(
+            Arc::clone(&self.database),
+            self.dictionary.clone(),
+            self.settings_view.language(),
+        );
+
+        Task::perform(
+            async move {
+                llm.answer_question(&question, &context, &history, Some(&tools))
+                    .await
+            },
+            |result| {
+                Message::Learning(match result {
+                    Ok(answer) => LearningMessage::QuestionAnswered(answer),
+                    Err(e) => LearningMessage::QuestionFailed(e.to_string()),
+                })
+            },
+        )
+    }
+
+    /// The source of a graded reader rewrite, or the latest rewrite of a source text
+    fn load_companion(&self, id: &str) -> Option<Companion> {
+        let companion = || -> Result<Option<Companion>, DatabaseError> {
+            if let Some(link) = self.database.get_graded_text(id)? {
+                let source = self.database.get_text(&link.source_id)?;
+                return Ok(source.map(|source| Companion {
+                    text_id: source.id,
+                    sentences: sentences(&source.tokenized_segments),
+                    level: link.level,
+                    is_rewrite: false,
+                    sentence_map: link.sentence_map,
+                }));
+            }
+
+            let Some(link) = self.database.get_graded_versions(id)?.into_iter().next() else {
+                return Ok(None);
+            };
+            let rewrite = self.database.get_text(&link.id)?;
+            Ok(rewrite.map(|rewrite| Companion {
+                text_id: rewrite.id,
+                sentences: sentences(&rewrite.tokenized_segments),
+                level: link.level,
+                is_rewrite: true,
+                sentence_map: link.sentence_map,
+            }))
+        };
+
+        companion()
+            .inspect_err(|e| eprintln!("Failed to load linked text: {}", e))
+            .ok()
+            .flatten()
+    }
+
+    /// Ask the LLM for a graded reader rewrite of the text in the Learning view
+    fn rewrite_text(&self) -> Task<Message> {
+        let (Some(source_id), Some((sentences, level))) = (
+            self.learning_view.text_id(),
+            self.learning_view.pending_rewrite(),
+        ) else {
+            return Task::none();
+        };
+        let source_id = source_id.to_string();
+        let llm = self.llm_service();
+
+        Task::perform(
+            async move { llm.rewrite_text(&sentences, level).await },
+            move |result| Message::TextRewritten(source_id, level, result),
+        )
+    }
+
+    /// Save a graded reader rewrite as a new text linked to its source
+    fn handle_text_rewritten(
+        &mut self,
+        source_id: &str,
+        level: JLPTLevel,
+        result: Result<GradedRewrite, LLMError>,
+    ) {
+        use crate::views::learning::Message as LearningMessage;
+
+        let saved = result.map_err(AppError::from).and_then(|rewrite| {
+            let source = self
+                .database
+                .get_text(source_id)?
+                .ok_or_else(|| AppError::Other(format!("Saved text not found: {}", source_id)))?;
+            let segments = TokenizerService::default().tokenize(&rewrite.text)?;
+
+            let mut text = LearningText::new(rewrite.text, segments);
+            text.title = format!("{} ({})", source.title, level.as_str());
+            self.database.save_text(text.clone())?;
+            self.database.save_graded_text(GradedText {
+                id: text.id.clone(),
+                source_id: source_id.to_string(),
+                level,
+                sentence_map: rewrite.sentence_map.clone(),
+                created_at: Utc::now(),
+            })?;
+
+            Ok(Companion {
+                text_id: text.id,
+                sentences: sentences(&text.tokenized_segments),
+                level,
+                is_rewrite: true,
+                sentence_map: rewrite.sentence_map,
+            })
+        });
+        if saved.is_ok() {
+            self.refresh_home_lists();
+        }
+
+        // The user may have opened another text meanwhile
+        if self.learning_view.text_id() == Some(source_id) {
+            let _ = self.learning_view.update(match saved {
+                Ok(companion) => LearningMessage::RewriteSaved(companion),
+                Err(e) => LearningMessage::RewriteFailed(e.to_string()),
+            });
+        }
+    }
+
+    /// Open the companion of the Learning text at the matching sentence
+    fn switch_to_companion(&mut self, sentence: Option<usize>) -> Task<Message> {
+        use crate::views::learning::Message as LearningMessage;
+
+        let Some((text_id, sentence)) = self.learning_view.companion_jump(sentence) else {
+            return Task::none();
+        };
+        self.open_text(&text_id);
+
+        match sentence.and_then(|sentence| self.learning_view.sentence_start(sentence)) {
+            Some(start) => self.handle_learning_message(LearningMessage::SelectWord(start)),
+            None => Task::none(),
+        }
+    }
+
+    /// Send the sentence written in Practice mode to the LLM for grading
+    fn grade_writing(&self) -> Task<Message> {
+        use crate::views::practice::Message as PracticeMessage;
+
+        let Some((card, sentence)) = self.practice_view.pending_writing() else {
+            return Task::none();
+        };
+        let card_id = card.id.clone();
+        let card = card.card_type.clone();
+        let sentence = sentence.to_string();
+        let llm = self.llm_service();
+
+        Task::perform(
+            async move { llm.grade_sentence(&card, &sentence).await },
+            move |result| {
+                Message::Practice(match result {
+                    Ok(grade) => PracticeMessage::WritingGraded(card_id, grade),
+                    Err(e) => PracticeMessage::WritingGradeFailed(e.to_string()),
+                })
+            },
+        )
+    }
+
+    /// Log a practice answer and, unless the session leaves scheduling alone, reschedule its card
+    ///
+    /// Cards left in learning steps are queued to come back in the session.
+    fn record_answer(&mut self, answer: PracticeAnswer) {
+        let Some(card) = self.practice_view.card(&answer.card_id) else {
+            return;
+        };
+        let mut card = card.clone();
+        let now = Utc::now();
+        let previous_interval = card.srs_data.interval;
+        let rescheduled = self.practice_view.affects_scheduling();
+        if rescheduled {
+            self.due_forecast.remove(&card.srs_data, &self.day_rollover);
+            card.srs_data = self.next_srs(&card, answer.quality, now);
+            self.fuzz_interval(&mut card, now);
+            self.due_forecast.add(&card.srs_data, &self.day_rollover);
+        }
+
+        let review = ReviewLog {
+            id: format!("review-{:020}-{}", now.timestamp_micros(), card.id),
+            card_id: card.id.clone(),
+            day: self.day_rollover.day_of(now).to_string(),
+            quality: answer.quality,
+            answer: Some(answer.answer),
+            response_ms: u64::try_from(answer.response_time.as_millis()).ok(),
+            previous_interval,
+            interval: card.srs_data.interval,
+            ease_factor: card.srs_data.ease_factor,
+            scheduler: self.deck_scheduler(&card.deck_id),
+            rescheduled,
+            reviewed_at: now,
+        };
+        if let Err(e) = self.database.record_review(review) {
+            eprintln!("Failed to record review: {}", e);
+        }
+        if !rescheduled {
+            self.refresh_home_lists();
+            return;
+        }
+
+        match self
+            .database
+            .update_card_srs(&card.id, card.srs_data.clone())
+        {
+            Ok(()) => {
+                self.practice_view.requeue_if_learning(&card);
+                self.practice_view.replace_card(card);
+                self.refresh_home_lists();
+            }
+            Err(e) => eprintln!("Failed to save review: {}", e),
+        }
+    }
+
+    /// Scheduling state of a card after an answer graded `quality` at `now`
+    ///
+    /// Cards that lapsed too often are tagged as leeches, and suspended if the
+    /// leech policy says so.
+    fn next_srs(&self, card: &FlashCard, quality: u8, now: DateTime<Utc>) -> SRSData {
+        let mut next = scheduler::answer(
+            &card.srs_data,
+            quality,
+            now,
+            self.deck_scheduler(&card.deck_id),
+            &self.fsrs_parameters,
+            &self.learning_steps,
+        );
+        scheduler::tag_leech(&mut next, &self.leech_policy);
+        next
+    }
+
+    /// Scheduler of a deck, falling back to the default when it cannot be loaded
+    fn deck_scheduler(&self, deck_id: &str) -> SchedulerKind {
+        self.database
+            .get_deck_scheduler(deck_id)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to load deck scheduler: {}", e);
+                Default::default()
+            })
+    }
+
+    /// Fuzz the day interval of a card just answered, toward days with fewer reviews due
+    ///
+    /// The card itself must already be left out of the session's forecast.
+    fn fuzz_interval(&self, card: &mut FlashCard, now: DateTime<Utc>) {
+        load_balancer::fuzz_interval(
+            &mut card.srs_data,
+            now,
+            &self.load_balancing,
+            &self.due_forecast,
+            &self.day_rollover,
+            &mut rand::rng(),
+        );
+    }
+
+    /// Delay until the next review that each self-rating of a card leads to
+    ///
+    /// Day intervals are shown as the range fuzzing may move them within.
+    fn rating_previews(&self, card_id: &str) -> Vec<(Rating, String)> {
+        let Some(card) = self.practice_view.card(card_id) else {
+            return Vec::new();
+        };
+        let now = Utc::now();
+        Rating::ALL
+            .into_iter()
+            .map(|rating| {
+                let next = self.next_srs(card, rating.quality(), now);
+                if !self.load_balancing.fuzz || next.learning.is_some() {
+                    return (rating, scheduler::format_delay(next.next_review - now));
+                }
+                let (shortest, longest) = load_balancer::fuzz_range(next.interval);
+                let preview = scheduler::format_delay_range(
+                    Duration::days(i64::from(shortest)),
+                    Duration::days(i64::from(longest)),
+                );
+                (rating, preview)
+            })
+            .collect()
+    }
+
+    /// Schedule a revealed answer the user moved on from without rating
+    fn schedule_unrated_answer(&mut self) {
+        if let Some(answer) = self.practice_view.take_pending_rating() {
+            self.record_answer(answer);
+        }
+    }
+
+    /// Fit the FSRS parameters to the stored reviews and save them
+    fn optimize_fsrs(&mut self) {
+        let reviews = match self.database.get_reviews() {
+            Ok(reviews) => reviews,
+            Err(e) => {
+                self.settings_view.set_fsrs_parameters(
+                    self.fsrs_parameters != FsrsParameters::default(),
+                    Some(AppError::from(e).to_string()),
+                );
+                return;
+            }
+        };
+
+        let Some(fitted) = fsrs::optimize(&reviews, &self.fsrs_parameters) else {
+            self.settings_view.set_fsrs_parameters(
+                self.fsrs_parameters != FsrsParameters::default(),
+                Some(format!(
+                    "Not enough reviews yet: fitting needs at least {} repeat reviews on separate days.",
+                    constants::srs::FSRS_MIN_REVIEWS
+                )),
+            );
+            return;
+        };
+
+        let saved = serde_json::to_string(&fitted.parameters)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                self.database
+                    .save_settings(constants::settings::FSRS_PARAMETERS, &json)
+                    .map_err(|e| AppError::from(e).to_string())
+            });
+        let status = match saved {
+            Ok(()) => {
+                self.fsrs_parameters = fitted.parameters;
+                format!(
+                    "Fitted to {} reviews; prediction loss {:.3} → {:.3}.",
+                    fitted.reviews, fitted.loss_before, fitted.loss_after
+                )
+            }
+            Err(e) => format!("Failed to save FSRS parameters: {}", e),
+        };
+        self.settings_view.set_fsrs_parameters(
+            self.fsrs_parameters != FsrsParameters::default(),
+            Some(status),
+        );
+    }
+
+    /// Send the pending message of the Conversation view to the LLM
+    fn send_conversation_message(&self) -> Task<Message> {
+        use crate::views::conversation::Message as ConversationMessage;
+
+        let Some(message) = self.conversation_view.pending_message() else {
+            return Task::none();
+        };
+        let message = message.to_string();
+        let history = self.conversation_view.answered_turns().to_vec();
+        let level = self.conversation_view.level();
+        let llm = self.llm_service();
+
+        Task::perform(
+            async move { llm.conversation_reply(&message, &history, level).await },
+            |result| {
+                Message::Conversation(match result {
+                    Ok(reply) => ConversationMessage::ReplyReceived(reply),
+                    Err(e) => ConversationMessage::ReplyFailed(e.to_string()),
+                })
+            },
+        )
+    }
+
+    /// Save a conversation correction as a grammar card in the tutor deck
+    fn make_mistake_card(&mut self, turn: usize, correction: usize) {
+        let Some(card) =
+            self.conversation_view
+                .mistake_card(turn, correction, self.settings_view.language())
+        else {
+            return;
+        };
+
+        match tutor_tools::add_tutor_card(&self.database, card) {
+            Ok(_) => {
+                self.conversation_view.mark_card_added(turn, correction);
+                self.refresh_home_lists();
+            }
+            Err(e) => self
+                .conversation_view
+                .set_status(Some(AppError::from(e).to_string())),
+        }
+    }
+
+    /// Persist the pending question with its answer for the current text
+    fn save_conversation_turn(&self, answer: &str) {
+        let (Some(text_id), Some(question)) = (
+            self.learning_view.text_id(),
+            self.learning_view.pending_question(),
+        ) else {
+            return;
+        };
+
+        if let Err(e) = self
+            .database
+            .save_conversation_turn(text_id, question, answer)
+        {
+            eprintln!("Failed to save conversation: {}", e);
+        }
+    }
+
+    /// Apply and persist new learning and relearning steps
+    fn apply_learning_steps(&mut self, steps: LearningSteps) {
+        if steps == self.learning_steps {
+            return;
+        }
+        self.learning_steps = steps;
+
+        for (key, value) in [
+            (
+                constants::settings::LEARNING_STEPS,
+                self.settings_view.learning_steps_text(),
+            ),
+            (
+                constants::settings::RELEARNING_STEPS,
+                self.settings_view.relearning_steps_text(),
+            ),
+        ] {
+            self.save_setting(key, value.trim());
+        }
+    }
+
+    /// Apply and persist new daily limits
+    fn apply_daily_limits(&mut self, limits: DailyLimits) {
+        if limits == self.daily_limits {
+            return;
+        }
+        self.daily_limits = limits;
+        self.save_setting(
+            constants::settings::DAILY_REVIEW_LIMIT,
+            &limits.reviews.to_string(),
+        );
+        self.save_setting(
+            constants::settings::NEW_CARDS_PER_DAY,
+            &limits.new_cards.to_string(),
+        );
+    }
+
+    /// Apply and persist a new start of the study day
+    fn apply_day_rollover(&mut self, rollover: DayRollover) {
+        if rollover == self.day_rollover {
+            return;
+        }
+        self.day_rollover = rollover;
+        self.save_setting(
+            constants::settings::DAY_STARTS_AT,
+            &rollover.starts_at.to_string(),
+        );
+        self.save_setting(
+            constants::settings::UTC_OFFSET,
+            &study_day::format_utc_offset(rollover.utc_offset),
+        );
+        if let Err(e) = self.database.restamp_review_days(&rollover) {
+            eprintln!("Failed to move reviews to their new study day: {}", e);
+        }
+    }
+
+    /// Apply and persist new practice queue options
+    fn apply_queue_options(&mut self, options: QueueOptions) {
+        if options == self.queue_options {
+            return;
+        }
+        self.queue_options = options;
+        self.save_setting(constants::settings::QUEUE_ORDER, options.order.tag());
+        self.save_setting(
+            constants::settings::REVIEWS_PER_NEW_CARD,
+            &options.reviews_per_new_card.to_string(),
+        );
+        self.save_setting(
+            constants::settings::BURY_SIBLINGS,
+            &options.bury_siblings.to_string(),
+        );
+    }
+
+    /// Apply and persist a new leech policy
+    fn apply_leech_policy(&mut self, policy: LeechPolicy) {
+        if policy == self.leech_policy {
+            return;
+        }
+        self.leech_policy = policy;
+        self.save_setting(
+            constants::settings::LEECH_THRESHOLD,
+            &policy.threshold.to_string(),
+        );
+        self.save_setting(
+            constants::settings::SUSPEND_LEECHES,
+            &policy.suspend.to_string(),
+        );
+    }
+
+    /// Apply and persist new interval fuzz options
+    fn apply_load_balancing(&mut self, options: LoadBalancing) {
+        if options == self.load_balancing {
+            return;
+        }
+        self.load_balancing = options;
+        self.save_setting(
+            constants::settings::FUZZ_INTERVALS,
+            &options.fuzz.to_string(),
+        );
+        self.save_setting(
+            constants::settings::BALANCE_LOAD,
+            &options.balance.to_string(),
+        );
+    }
+
+    /// Save a setting, logging failures
+    fn save_setting(&self, key: &str, value: &str) {
+        if let Err(e) = self.database.save_settings(key, value) {
+            eprintln!("Failed to save setting {}: {}", key, e);
+        }
+    }
+
+    /// Apply and persist new LLM request limits
+    fn apply_governor_config(&mut self, config: GovernorConfig) {
+        if config == self.governor.config() {
+            return;
+        }
+        self.governor.set_config(config);
+
+        let saved = self
+            .database
+            .save_settings(
+                constants::settings::REQUESTS_PER_MINUTE,
+                &config.requests_per_minute.to_string(),
+            )
+            .and_then(|_| {
+                self.database.save_settings(
+                    constants::settings::DAILY_TOKEN_BUDGET,
+                    &config.daily_token_budget.to_string(),
+                )
+            });
+        if let Err(e) = saved {
+            eprintln!("Failed to save LLM limits: {}", e);
+        }
+    }
+
+    /// Ask the LLM for an updated learner report from the given session outcomes
+    ///
+    /// Does nothing without an API key or when there is nothing to report on,
+    /// unless the user asked for it from Settings.
+    fn regenerate_learner_report(
+        &mut self,
+        outcomes: Vec<ReviewOutcome>,
+        requested: bool,
+    ) -> Task<Message> {
+        let llm = self.llm_service();
+        let prepared = if llm.is_configured() {
+            ReportInputs::gather(&self.database, outcomes)
+                .and_then(|inputs| {
+                    let previous = self.database.get_latest_report()?;
+                    Ok((inputs, previous.map(|report| report.content)))
+                })
+                .map_err(|e| AppError::from(e).to_string())
+                .and_then(|(inputs, previous)| {
+                    if inputs.is_empty() {
+                        Err("Not enough study history yet. Practice some cards or ask questions first."
+                            .to_string())
+                    } else {
+                        Ok((inputs, previous))
+                    }
+                })
+        } else {
+            Err(AppError::from(LLMError::NotConfigured).to_string())
+        };
+
+        let (inputs, previous) = match prepared {
+            Ok(prepared) => prepared,
+            Err(reason) => {
+                if requested {
+                    self.settings_view.set_report_status(Some(reason));
+                }
+                return Task::none();
+            }
+        };
+
+        self.settings_view.set_generating_report(true);
+        self.settings_view.set_report_status(None);
+        Task::perform(
+            async move {
+                llm.generate_learner_report(&inputs.summary(), previous.as_deref())
+                    .await
+            },
+            Message::LearnerReportGenerated,
+        )
+    }
+
+    fn handle_learner_report_generated(&mut self, result: Result<String, LLMError>) {
+        self.settings_view.set_generating_report(false);
+
+        let saved = result.map_err(AppError::from).and_then(|content| {
+            self.database
+                .save_learner_report(&content, ReportSource::Generated)
+                .map_err(AppError::from)
+        });
+        match saved {
+            Ok(_) => {
+                self.refresh_report_history();
+                self.settings_view
+                    .set_report_status(Some("Learner report updated.".to_string()));
+            }
+            Err(e) => {
+                eprintln!("Failed to update learner report: {}", e);
+                self.settings_view.set_report_status(Some(e.to_string()));
+            }
+        }
+    }
+
+    /// Store the report text from Settings as a new revision
+    fn save_learner_report(&mut self) {
+        let content = self.settings_view.learner_report();
+        let status = match self
+            .database
+            .save_learner_report(content.trim(), ReportSource::Edited)
+        {
+            Ok(_) => {
+                self.refresh_report_history();
+                "Learner report saved.".to_string()
+            }
+            Err(e) => AppError::from(e).to_string(),
+        };
+        self.settings_view.set_report_status(Some(status));
+    }
+
+    /// Reload the learner report revisions shown in Settings
+    fn refresh_report_history(&mut self) {
+        match self.database.get_report_history() {
+            Ok(history) => self.settings_view.set_report_history(history),
+            Err(e) => eprintln!("Failed to load learner report history: {}", e),
+        }
+    }
+
+    /// Reload the audit log shown in the developer view
+    fn refresh_llm_calls(&mut self) {
+        match self.database.get_llm_calls() {
+            Ok(calls) => self.developer_view.set_calls(calls),
+            Err(e) => eprintln!("Failed to load LLM calls: {}", e),
+        }
+        self.developer_view
+            .set_unrecorded_calls(audit::unrecorded_calls());
+    }
+
+    /// Send the selected logged call again with the current provider
+    fn rerun_llm_call(&self) -> Task<Message> {
+        let Some(call) = self.developer_view.pending_rerun().cloned() else {
+            return Task::none();
+        };
+
+        let llm = self.llm_service();
+        Task::perform(async move { llm.rerun(&call).await }, |result| {
+            Message::Developer(crate::views::developer::Message::RerunFinished(
+                result.err().map(|e| e.to_string()),
+            ))
+        })
+    }
+
+    /// Reload the deck and text lists shown on the Home view
+    fn refresh_home_lists(&mut self) {
+        match self.database.load_decks() {
+            Ok(decks) => self.home_view.set_decks(decks),
+            Err(e) => eprintln!("Failed to load decks: {}", e),
+        }
+        match self.database.load_texts() {
+            Ok(texts) => self.home_view.set_saved_texts(texts),
+            Err(e) => eprintln!("Failed to load texts: {}", e),
+        }
+        match self
+            .database
+            .get_reviews_on(self.day_rollover.day_of(Utc::now()))
+        {
+            Ok(reviews) => self.home_view.set_day_stats(DayStats::new(&reviews)),
+            Err(e) => eprintln!("Failed to load today's reviews: {}", e),
+        }
+        match self.load_leeches() {
+            Ok((leeches, mistakes)) => self.home_view.set_leeches(leeches, mistakes),
+            Err(e) => eprintln!("Failed to load leeches: {}", e),
+        }
+    }
+
+    /// Leeches, with the wrong answer each one is usually mistaken for
+    fn load_leeches(&self) -> Result<(Vec<FlashCard>, HashMap<String, String>), AppError> {
+        let leeches: Vec<FlashCard> = self
+            .database
+            .get_all_cards()?
+            .into_iter()
+            .filter(|card| card.srs_data.leech)
+            .collect();
+        let mut mistakes = HashMap::new();
+        for card in &leeches {
+            let reviews = self.database.get_card_reviews(&card.id)?;
+            if let Some(mistake) = scheduler::usual_mistake(&reviews) {
+                mistakes.insert(card.id.clone(), mistake.to_string());
+            }
+        }
+        Ok((leeches, mistakes))
+    }
+
+    /// Tokenize the Home input and start generating cards for its new words
+    ///
+    /// Without an LLM, cards are built from the local dictionary instead.
+    fn start_card_generation(&mut self) -> Task<Message> {
+        let llm = self.llm_service();
+        if !llm.is_configured() && self.dictionary.is_none() {
+            self.home_view
+                .set_status(Some(AppError::from(LLMError::NotConfigured).to_string()));
+            return Task::none();
+        }
+
+        let text = self.home_view.input_text().trim().to_string();
+        let words = TokenizerService::default()
+            .tokenize(&text)
+            .map_err(AppError::from)
+            .and_then(|segments| {
+                let cards = self.database.get_all_cards()?;
+                let known = card_generator::known_words(&cards);
+                Ok(card_generator::select_words(segments, &known))
+            });
+
+        let words = match words {
+            Ok(words) if words.is_empty() => {
+                self.home_view
+                    .set_status(Some("No new words found in this text.".to_string()));
+                return Task::none();
+            }
+            Ok(words) => words,
+            Err(e) => {
+                self.home_view.set_status(Some(e.to_string()));
+                return Task::none();
+            }
+        };
+
+        if let Some(dictionary) = self.dictionary.clone().filter(|_| !llm.is_configured()) {
+            let cards: Vec<GeneratedCard> = words
+                .iter()
+                .filter_map(|word| card_generator::dictionary_card(word, &dictionary))
+                .map(|card| GeneratedCard {
+                    card,
+                    mismatches: Vec::new(),
+                })
+                .collect();
+            let failed = words.len() - cards.len();
+            self.save_generated_deck(&text, cards, failed, None);
+            return Task::none();
+        }
+
+        let progress = GenerationProgress {
+            total: words.len(),
+            ..GenerationProgress::default()
+        };
+        self.home_view.set_status(None);
+        self.home_view.set_generation_progress(Some(progress));
+
+        let (task, handle) = Task::run(
+            card_generator::generate_cards(llm, words, &text, self.dictionary.clone()),
+            Message::CardGenerated,
+        )
+        .abortable();
+
+        self.card_generation = Some(CardGeneration {
+            source_text: text,
+            progress,
+            cards: Vec::new(),
+            last_error: None,
+            handle,
+        });
+
+        task
+    }
+
+    fn cancel_card_generation(&mut self) {
+        if let Some(generation) = self.card_generation.take() {
+            generation.handle.abort();
+            self.home_view.set_generation_progress(None);
+            self.home_view
+                .set_status(Some("Card generation cancelled.".to_string()));
+        }
+    }
+
+    fn handle_card_generated(&mut self, result: Result<GeneratedCard, LLMError>) -> Task<Message> {
+        // Results that arrive after a cancellation are dropped
+        let Some(generation) = self.card_generation.as_mut() else {
+            return Task::none();
+        };
+
+        match result {
+            Ok(card) => {
+                generation.progress.completed += 1;
+                generation.cards.push(card);
+            }
+            Err(e) => {
+                eprintln!("Card generation failed: {}", e);
+                generation.progress.failed += 1;
+                generation.last_error = Some(e);
+            }
+        }
+        self.home_view
+            .set_generation_progress(Some(generation.progress));
+
+        if generation.progress.is_done() {
+            self.finish_card_generation();
+        }
+        Task::none()
+    }
+
+    /// Save the generated cards once every word has been processed
+    fn finish_card_generation(&mut self) {
+        let Some(generation) = self.card_generation.take() else {
+            return;
+        };
+        self.home_view.set_generation_progress(None);
+        self.save_generated_deck(
+            &generation.source_text,
+            generation.cards,
+            generation.progress.failed,
+            generation.last_error,
+        );
+    }
+
+    /// Save generated cards into a new deck and open it in Practice mode
+    fn save_generated_deck(
+        &mut self,
+        source_text: &str,
+        cards: Vec<GeneratedCard>,
+        failed: usize,
+        last_error: Option<LLMError>,
+    ) {
+        if cards.is_empty() {
+            let reason = last_error.map(|e| e.to_string()).unwrap_or_default();
+            self.home_view
+                .set_status(Some(format!("Could not generate any cards. {}", reason)));
+            return;
+        }
+
+        let deck_id = format!("deck-{}", Utc::now().timestamp_millis());
+        let description = format!(
+            "Generated from: {}",
+            source_text.chars().take(100).collect::<String>()
+        );
+        let saved = self
+            .database
+            .create_deck(deck_id.clone(), title_from_text(source_text), description)
+            .and_then(|_| {
+                cards.iter().enumerate().try_for_each(|(i, generated)| {
+                    self.database.save_card(
+                        format!("{}-{}", deck_id, i),
+                        deck_id.clone(),
+                        CardType::Vocabulary(generated.card.clone()),
+                    )
+                })
+            });
+
+        if let Err(e) = saved {
+            self.home_view
+                .set_status(Some(AppError::from(e).to_string()));
+            return;
+        }
+
+        let flagged = cards
+            .iter()
+            .filter(|generated| !generated.mismatches.is_empty())
+            .count();
+        let mut status = Vec::new();
+        if failed > 0 {
+            status.push(format!(
+                "{} word(s) could not be turned into cards.",
+                failed
+            ));
+        }
+        if flagged > 0 {
+            status.push(format!(
+                "{} card(s) disagree with local data; check them in Practice.",
+                flagged
+            ));
+        }
+        self.home_view
+            .set_status((!status.is_empty()).then(|| status.join(" ")));
+        self.refresh_home_lists();
+
+        self.study_decks(std::slice::from_ref(&deck_id));
+        // Generation also checked the tokenizer readings, which are not stored
+        for (i, generated) in cards.into_iter().enumerate() {
+            self.practice_view
+                .set_mismatches(&format!("{}-{}", deck_id, i), generated.mismatches);
+        }
+    }
+
     fn handle_home_message(&mut self, msg: crate::views::home::Message) -> Task<Message> {
         use crate::views::home::Message as HomeMessage;
 
         match msg {
             HomeMessage::NavigateToSettings => {
+                self.settings_view
+                    .set_llm_usage(self.governor.usage_today());
                 self.navigate_to(AppMode::Settings);
                 Task::none()
             }
-            HomeMessage::SubmitForPractice => {
-                // TODO: Process text and navigate to practice
-                self.navigate_to(AppMode::Practice);
+            HomeMessage::NavigateToConversation => {
+                self.navigate_to(AppMode::Conversation);
+                Task::none()
+            }
+            HomeMessage::SubmitForPractice => self.start_card_generation(),
+            HomeMessage::CancelGeneration => {
+                self.cancel_card_generation();
                 Task::none()
             }
             HomeMessage::SubmitForLearning => {
-                // TODO: Process text and navigate to learning
-                self.navigate_to(AppMode::Learning);
+                self.start_learning();
+                Task::none()
+            }
+            HomeMessage::SelectText(id) => {
+                self.open_text(&id);
+                Task::none()
+            }
+            HomeMessage::SelectDeck(id) => {
+                self.study_decks(&[id]);
+                Task::none()
+            }
+            HomeMessage::OpenCustomSession => {
+                self.open_custom_session();
+                Task::none()
+            }
+            HomeMessage::StudyAllDue => {
+                let deck_ids = self.home_view.deck_ids();
+                self.study_decks(&deck_ids);
+                Task::none()
+            }
+            HomeMessage::SaveLeech => {
+                self.save_leech_edit();
+                Task::none()
+            }
+            HomeMessage::RegenerateLeech(id) => self.regenerate_leech(&id),
+            HomeMessage::ResetLeech(id) => {
+                match self
+                    .database
+                    .update_card_srs(&id, SRSData::new_card(Utc::now()))
+                {
+                    Ok(()) => self.refresh_home_lists(),
+                    Err(e) => self
+                        .home_view
+                        .set_status(Some(AppError::from(e).to_string())),
+                }
+                Task::none()
+            }
+            HomeMessage::SetDeckScheduler(id, scheduler) => {
+                match self.database.set_deck_scheduler(&id, scheduler) {
+                    Ok(()) => self.refresh_home_lists(),
+                    Err(e) => self
+                        .home_view
+                        .set_status(Some(AppError::from(e).to_string())),
+                }
                 Task::none()
             }
             _ => self.home_view.update(msg).map(Message::Home),
@@ -107,13 +1754,77 @@ impl App {
     }
 
     fn handle_practice_message(&mut self, msg: crate::views::practice::Message) -> Task<Message> {
+        use crate::views::custom_session::Message as CustomSessionMessage;
         use crate::views::practice::Message as PracticeMessage;
 
         match msg {
             PracticeMessage::BackToHome => {
+                self.schedule_unrated_answer();
                 self.navigate_to(AppMode::Home);
+                let outcomes = self.practice_view.take_session_outcomes();
+                if outcomes.is_empty() {
+                    return Task::none();
+                }
+                self.regenerate_learner_report(outcomes, false)
+            }
+            PracticeMessage::RegenerateCard => self.regenerate_current_card(),
+            PracticeMessage::SubmitWriting => {
+                let task = self.practice_view.update(msg).map(Message::Practice);
+                Task::batch([task, self.grade_writing()])
+            }
+            PracticeMessage::SelectAnswer(selected) => {
+                if let Some(answer) = self.practice_view.answer_review(selected) {
+                    if self.self_rating && self.practice_view.affects_scheduling() {
+                        let previews = self.rating_previews(&answer.card_id);
+                        self.practice_view.await_rating(answer, previews);
+                    } else {
+                        self.record_answer(answer);
+                    }
+                }
+                self.practice_view.update(msg).map(Message::Practice)
+            }
+            PracticeMessage::RateAnswer(rating) => {
+                if let Some(answer) = self.practice_view.take_pending_rating() {
+                    self.record_answer(PracticeAnswer {
+                        quality: rating.quality(),
+                        ..answer
+                    });
+                }
                 Task::none()
             }
+            PracticeMessage::NextCard | PracticeMessage::PreviousCard => {
+                self.schedule_unrated_answer();
+                self.practice_view.update(msg).map(Message::Practice)
+            }
+            PracticeMessage::WritingGraded(ref card_id, ref grade) => {
+                if let Some(answer) = self.practice_view.writing_answer(card_id, grade.quality()) {
+                    self.record_answer(answer);
+                }
+                self.practice_view.update(msg).map(Message::Practice)
+            }
+            PracticeMessage::AcceptLocalCardData => {
+                self.accept_local_card_data();
+                Task::none()
+            }
+            PracticeMessage::OpenCustomSession => {
+                self.open_custom_session();
+                Task::none()
+            }
+            PracticeMessage::CustomSession(CustomSessionMessage::Start) => {
+                self.start_custom_session();
+                Task::none()
+            }
+            PracticeMessage::CustomSession(CustomSessionMessage::Cancel) => {
+                self.practice_view.close_custom_session();
+                Task::none()
+            }
+            PracticeMessage::CustomSession(
+                CustomSessionMessage::SavePreset | CustomSessionMessage::DeletePreset(_),
+            ) => {
+                let task = self.practice_view.update(msg).map(Message::Practice);
+                self.save_study_presets();
+                task
+            }
             _ => self.practice_view.update(msg).map(Message::Practice),
         }
     }
@@ -126,10 +1837,84 @@ impl App {
                 self.navigate_to(AppMode::Home);
                 Task::none()
             }
+            LearningMessage::SelectWord(_)
+            | LearningMessage::RequestExplanation
+            | LearningMessage::RegenerateExplanation => {
+                let task = self.learning_view.update(msg).map(Message::Learning);
+                Task::batch([task, self.explain_selected_word()])
+            }
+            LearningMessage::RequestTranslation => {
+                let task = self.learning_view.update(msg).map(Message::Learning);
+                Task::batch([task, self.translate_sentence()])
+            }
+            LearningMessage::AskQuestion => {
+                let task = self.learning_view.update(msg).map(Message::Learning);
+                Task::batch([task, self.ask_question()])
+            }
+            LearningMessage::RequestRewrite => {
+                let task = self.learning_view.update(msg).map(Message::Learning);
+                Task::batch([task, self.rewrite_text()])
+            }
+            LearningMessage::SaveExplanation => {
+                let task = self.learning_view.update(msg).map(Message::Learning);
+                if let Some((base_form, explanation)) = self.learning_view.edited_explanation()
+                    && let Err(e) = self
+                        .database
+                        .save_edited_explanation(base_form, explanation.clone())
+                {
+                    eprintln!("Failed to save corrected explanation: {}", e);
+                }
+                task
+            }
+            LearningMessage::JumpToCompanion(sentence) => self.switch_to_companion(sentence),
+            LearningMessage::QuestionAnswered(ref answer) => {
+                self.save_conversation_turn(answer);
+                self.learning_view.update(msg).map(Message::Learning)
+            }
             _ => self.learning_view.update(msg).map(Message::Learning),
         }
     }
 
+    fn handle_conversation_message(
+        &mut self,
+        msg: crate::views::conversation::Message,
+    ) -> Task<Message> {
+        use crate::views::conversation::Message as ConversationMessage;
+
+        match msg {
+            ConversationMessage::BackToHome => {
+                self.navigate_to(AppMode::Home);
+                Task::none()
+            }
+            ConversationMessage::Send => {
+                let task = self
+                    .conversation_view
+                    .update(msg)
+                    .map(Message::Conversation);
+                Task::batch([task, self.send_conversation_message()])
+            }
+            ConversationMessage::LevelSelected(level) => {
+                if let Err(e) = self
+                    .database
+                    .save_settings(constants::settings::CONVERSATION_LEVEL, level.as_str())
+                {
+                    eprintln!("Failed to save conversation level: {}", e);
+                }
+                self.conversation_view
+                    .update(msg)
+                    .map(Message::Conversation)
+            }
+            ConversationMessage::MakeCard(turn, correction) => {
+                self.make_mistake_card(turn, correction);
+                Task::none()
+            }
+            _ => self
+                .conversation_view
+                .update(msg)
+                .map(Message::Conversation),
+        }
+    }
+
     fn handle_settings_message(&mut self, msg: crate::views::settings::Message) -> Task<Message> {
         use crate::views::settings::Message as SettingsMessage;
 
@@ -143,10 +1928,130 @@ impl App {
                 self.navigate_to(AppMode::Home);
                 task
             }
+            SettingsMessage::RequestsPerMinuteChanged(_)
+            | SettingsMessage::DailyTokenBudgetChanged(_) => {
+                if let Some(config) = self.settings_view.governor_config() {
+                    self.apply_governor_config(config);
+                }
+                task
+            }
+            SettingsMessage::LearningStepsChanged(_)
+            | SettingsMessage::RelearningStepsChanged(_) => {
+                if let Some(steps) = self.settings_view.learning_steps() {
+                    self.apply_learning_steps(steps);
+                }
+                task
+            }
+            SettingsMessage::DailyReviewLimitChanged(_)
+            | SettingsMessage::NewCardsPerDayChanged(_) => {
+                if let Some(limits) = self.settings_view.daily_limits() {
+                    self.apply_daily_limits(limits);
+                }
+                task
+            }
+            SettingsMessage::DayStartsAtChanged(_) | SettingsMessage::UtcOffsetChanged(_) => {
+                if let Some(rollover) = self.settings_view.day_rollover() {
+                    self.apply_day_rollover(rollover);
+                }
+                task
+            }
+            SettingsMessage::QueueOrderSelected(_)
+            | SettingsMessage::ReviewsPerNewCardChanged(_)
+            | SettingsMessage::BurySiblingsToggled(_) => {
+                if let Some(options) = self.settings_view.queue_options() {
+                    self.apply_queue_options(options);
+                }
+                task
+            }
+            SettingsMessage::SelfRatingToggled(enabled) => {
+                self.self_rating = enabled;
+                self.save_setting(constants::settings::SELF_RATING, &enabled.to_string());
+                task
+            }
+            SettingsMessage::FuzzIntervalsToggled(_) | SettingsMessage::BalanceLoadToggled(_) => {
+                self.apply_load_balancing(self.settings_view.load_balancing());
+                task
+            }
+            SettingsMessage::LeechThresholdChanged(_)
+            | SettingsMessage::SuspendLeechesToggled(_) => {
+                if let Some(policy) = self.settings_view.leech_policy() {
+                    self.apply_leech_policy(policy);
+                }
+                task
+            }
+            SettingsMessage::LanguageSelected(language) => {
+                if let Err(e) = self
+                    .database
+                    .save_settings(constants::settings::EXPLANATION_LANGUAGE, language.tag())
+                {
+                    eprintln!("Failed to save explanation language: {}", e);
+                }
+                task
+            }
+            SettingsMessage::DemoModeToggled(enabled) => {
+                if let Err(e) = self
+                    .database
+                    .save_settings(constants::settings::DEMO_MODE, &enabled.to_string())
+                {
+                    eprintln!("Failed to save demo mode: {}", e);
+                }
+                task
+            }
+            SettingsMessage::OptimizeFsrs => {
+                self.optimize_fsrs();
+                task
+            }
+            SettingsMessage::ResetFsrs => {
+                self.fsrs_parameters = FsrsParameters::default();
+                if let Err(e) = self
+                    .database
+                    .save_settings(constants::settings::FSRS_PARAMETERS, "")
+                {
+                    eprintln!("Failed to reset FSRS parameters: {}", e);
+                }
+                self.settings_view
+                    .set_fsrs_parameters(false, Some("Reset to the default parameters.".into()));
+                task
+            }
+            SettingsMessage::SaveLearnerReport => {
+                self.save_learner_report();
+                task
+            }
+            SettingsMessage::RegenerateLearnerReport => {
+                Task::batch([task, self.regenerate_learner_report(Vec::new(), true)])
+            }
+            SettingsMessage::OpenDeveloperLog => {
+                self.refresh_llm_calls();
+                self.navigate_to(AppMode::Developer);
+                task
+            }
             _ => task,
         }
     }
 
+    fn handle_developer_message(&mut self, msg: crate::views::developer::Message) -> Task<Message> {
+        use crate::views::developer::Message as DeveloperMessage;
+
+        match msg {
+            DeveloperMessage::BackToSettings => {
+                self.navigate_to(AppMode::Settings);
+                Task::none()
+            }
+            DeveloperMessage::Rerun => {
+                let task = self.developer_view.update(msg).map(Message::Developer);
+                Task::batch([task, self.rerun_llm_call()])
+            }
+            DeveloperMessage::RerunFinished(_) => {
+                let task = self.developer_view.update(msg).map(Message::Developer);
+                // The re-run was logged as a new call
+                self.refresh_llm_calls();
+                self.developer_view.select_newest();
+                task
+            }
+            _ => self.developer_view.update(msg).map(Message::Developer),
+        }
+    }
+
     pub fn view(&self) -> Element<'_, Message> {
         use iced::widget::container;
         use iced::{Fill, Length};
@@ -154,7 +2059,9 @@ impl App {
             AppMode::Home => self.home_view.view().map(Message::Home),
             AppMode::Practice => self.practice_view.view().map(Message::Practice),
             AppMode::Learning => self.learning_view.view().map(Message::Learning),
+            AppMode::Conversation => self.conversation_view.view().map(Message::Conversation),
             AppMode::Settings => self.settings_view.view().map(Message::Settings),
+            AppMode::Developer => self.developer_view.view().map(Message::Developer),
         };
         container(content)
             .width(Length::Fill)
@@ -163,3 +2070,128 @@ impl App {
             .into()
     }
 }
+
+/// Cache key of a word's explanation in a sentence, in the explanation language
+fn explanation_cache_key(base_form: &str, sentence: &str, language: Language) -> String {
+    format!("explain:{}:{}:{}", language.tag(), base_form, sentence)
+}
+
+/// Load a stored setting, logging failures
+fn load_setting(database: &DatabaseService, key: &str) -> Option<String> {
+    database.load_settings(key).unwrap_or_else(|e| {
+        eprintln!("Failed to load setting {}: {}", key, e);
+        None
+    })
+}
+
+/// Load the stored learning and relearning steps, falling back to defaults for missing values
+fn load_learning_steps(database: &DatabaseService) -> LearningSteps {
+    let defaults = LearningSteps::default();
+    let load = |key: &str| load_setting(database, key);
+
+    LearningSteps {
+        learning: load(constants::settings::LEARNING_STEPS)
+            .and_then(|value| scheduler::parse_steps(&value))
+            .unwrap_or(defaults.learning),
+        relearning: load(constants::settings::RELEARNING_STEPS)
+            .and_then(|value| scheduler::parse_steps(&value))
+            .unwrap_or(defaults.relearning),
+    }
+}
+
+/// Load the stored daily limits and start of the study day, falling back to defaults
+fn load_study_day(database: &DatabaseService) -> (DailyLimits, DayRollover) {
+    let (limits, rollover) = (DailyLimits::default(), DayRollover::default());
+    let load = |key: &str| load_setting(database, key);
+
+    (
+        DailyLimits {
+            reviews: load(constants::settings::DAILY_REVIEW_LIMIT)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(limits.reviews),
+            new_cards: load(constants::settings::NEW_CARDS_PER_DAY)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(limits.new_cards),
+        },
+        DayRollover {
+            starts_at: load(constants::settings::DAY_STARTS_AT)
+                .and_then(|value| value.parse().ok())
+                .filter(|hour| *hour < 24)
+                .unwrap_or(rollover.starts_at),
+            utc_offset: load(constants::settings::UTC_OFFSET)
+                .and_then(|value| study_day::parse_utc_offset(&value))
+                .unwrap_or(rollover.utc_offset),
+        },
+    )
+}
+
+/// Load the stored practice queue options, falling back to defaults for missing values
+fn load_queue_options(database: &DatabaseService) -> QueueOptions {
+    let defaults = QueueOptions::default();
+    let load = |key: &str| load_setting(database, key);
+
+    QueueOptions {
+        order: load(constants::settings::QUEUE_ORDER)
+            .and_then(|value| QueueOrder::from_tag(&value))
+            .unwrap_or(defaults.order),
+        reviews_per_new_card: load(constants::settings::REVIEWS_PER_NEW_CARD)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.reviews_per_new_card),
+        bury_siblings: load(constants::settings::BURY_SIBLINGS)
+            .map_or(defaults.bury_siblings, |value| value == "true"),
+    }
+}
+
+/// Load the stored leech policy, falling back to defaults for missing values
+fn load_leech_policy(database: &DatabaseService) -> LeechPolicy {
+    let defaults = LeechPolicy::default();
+    let load = |key: &str| load_setting(database, key);
+
+    LeechPolicy {
+        threshold: load(constants::settings::LEECH_THRESHOLD)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.threshold),
+        suspend: load(constants::settings::SUSPEND_LEECHES)
+            .map_or(defaults.suspend, |value| value == "true"),
+    }
+}
+
+/// Load the stored interval fuzz options, falling back to defaults for missing values
+fn load_load_balancing(database: &DatabaseService) -> LoadBalancing {
+    let defaults = LoadBalancing::default();
+    let load = |key: &str| load_setting(database, key);
+
+    LoadBalancing {
+        fuzz: load(constants::settings::FUZZ_INTERVALS)
+            .map_or(defaults.fuzz, |value| value == "true"),
+        balance: load(constants::settings::BALANCE_LOAD)
+            .map_or(defaults.balance, |value| value == "true"),
+    }
+}
+
+/// Load the saved custom session presets
+fn load_study_presets(database: &DatabaseService) -> Vec<StudyPreset> {
+    load_setting(database, constants::settings::STUDY_PRESETS)
+        .filter(|json| !json.is_empty())
+        .and_then(|json| {
+            serde_json::from_str(&json)
+                .inspect_err(|e| eprintln!("Ignoring unreadable custom session presets: {}", e))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Load the stored LLM limits, falling back to defaults for missing values
+fn load_governor_config(database: &DatabaseService) -> GovernorConfig {
+    let defaults = GovernorConfig::default();
+    let load = |key: &str| load_setting(database, key);
+
+    GovernorConfig {
+        requests_per_minute: load(constants::settings::REQUESTS_PER_MINUTE)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.requests_per_minute),
+        daily_token_budget: load(constants::settings::DAILY_TOKEN_BUDGET)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.daily_token_budget),
+    }
+}
@@ -20,7 +20,7 @@ pub fn example_sentences<'a, Message: 'a>(
                 container(
                     column![
                         text(&example.japanese).size(14),
-                        text(&example.english).size(12),
+                        text(&example.translation).size(12),
                     ]
                     .spacing(5),
                 )
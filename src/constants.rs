@@ -29,6 +29,122 @@ pub mod srs {
 
     /// Default new cards per day
     pub const DEFAULT_NEW_CARDS_PER_DAY: usize = 10;
+
+    /// Default hour at which the next study day starts, so late sessions count toward the evening
+    pub const DEFAULT_DAY_STARTS_AT: u32 = 4;
+
+    /// Default number of reviews practiced between two new cards
+    pub const DEFAULT_REVIEWS_PER_NEW_CARD: usize = 4;
+
+    /// Highest SM-2 quality grade (perfect recall)
+    pub const MAX_QUALITY: u8 = 5;
+
+    /// Lowest SM-2 quality grade that counts as recalled
+    pub const PASSING_QUALITY: u8 = 3;
+
+    /// Correct multiple-choice answers given faster than this count as perfect recall
+    pub const FAST_ANSWER_SECONDS: u64 = 8;
+
+    /// Recall probability at which FSRS schedules the next review
+    pub const DESIRED_RETENTION: f32 = 0.9;
+
+    /// Inter-day reviews needed before FSRS parameters are fitted
+    pub const FSRS_MIN_REVIEWS: usize = 100;
+
+    /// Delays before a new card is shown again, until it gets day intervals
+    pub const DEFAULT_LEARNING_STEPS: &str = "1m 10m";
+
+    /// Delays before a forgotten card is shown again, until it returns to day intervals
+    pub const DEFAULT_RELEARNING_STEPS: &str = "10m";
+
+    /// Default number of lapses after which a card is tagged as a leech
+    pub const DEFAULT_LEECH_THRESHOLD: u32 = 8;
+
+    /// Cards in learning due within this many minutes can be shown early when nothing else is left
+    pub const LEARN_AHEAD_MINUTES: i64 = 20;
+}
+
+/// LLM Constants
+pub mod llm {
+    /// Gemini model used for explanations and card generation
+    pub const DEFAULT_MODEL: &str = "gemini-2.5-pro";
+
+    /// Maximum number of LLM requests in flight during batch card generation
+    pub const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+    /// Default requests-per-minute limit
+    pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 10;
+
+    /// Default daily token budget (0 disables the budget)
+    pub const DEFAULT_DAILY_TOKEN_BUDGET: u64 = 500_000;
+
+    /// Retries for rate-limited or failed requests before giving up
+    pub const MAX_RETRIES: u32 = 4;
+
+    /// First retry delay; doubled on each further attempt
+    pub const INITIAL_BACKOFF_MS: u64 = 1_000;
+
+    /// Upper bound for a single retry delay
+    pub const MAX_BACKOFF_MS: u64 = 30_000;
+
+    /// Estimated tokens of earlier Q&A turns sent along with a new question
+    pub const HISTORY_TOKEN_BUDGET: usize = 4_000;
+
+    /// Rounds of tool calls the tutor may make before it has to answer
+    pub const MAX_TOOL_ROUNDS: usize = 4;
+
+    /// Cards and dictionary entries returned by a single tutor tool call
+    pub const MAX_TOOL_RESULTS: usize = 10;
+
+    /// Learner report revisions kept in the database
+    pub const MAX_REPORT_REVISIONS: usize = 20;
+
+    /// Recent questions and missed cards listed in the learner report input
+    pub const REPORT_SAMPLE_SIZE: usize = 10;
+
+    /// Days of the review log summarized in the learner report
+    pub const REPORT_HISTORY_DAYS: i64 = 14;
+
+    /// LLM calls kept in the audit log
+    pub const AUDIT_LOG_LIMIT: usize = 500;
+
+    /// Fixture directory answering requests in demo mode, looked up next to
+    /// the executable, then relative to the working directory
+    pub const FIXTURE_DIR: &str = "fixtures/llm";
+
+    /// Environment variable overriding the demo mode fixture directory
+    pub const FIXTURE_DIR_VAR: &str = "NIHONRS_FIXTURES";
+
+    /// Fixtures in the source tree, used by the unit tests
+    #[cfg(test)]
+    pub const TEST_FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/llm");
+}
+
+/// Keys for values stored with `DatabaseService::save_settings`
+pub mod settings {
+    pub const REQUESTS_PER_MINUTE: &str = "llm.requests_per_minute";
+    pub const DAILY_TOKEN_BUDGET: &str = "llm.daily_token_budget";
+    pub const DEMO_MODE: &str = "llm.demo_mode";
+    pub const CONVERSATION_LEVEL: &str = "conversation.level";
+    pub const EXPLANATION_LANGUAGE: &str = "explanation.language";
+    pub const FSRS_PARAMETERS: &str = "srs.fsrs_parameters";
+    pub const LEARNING_STEPS: &str = "srs.learning_steps";
+    pub const RELEARNING_STEPS: &str = "srs.relearning_steps";
+    pub const DAILY_REVIEW_LIMIT: &str = "srs.daily_review_limit";
+    pub const NEW_CARDS_PER_DAY: &str = "srs.new_cards_per_day";
+    pub const DAY_STARTS_AT: &str = "srs.day_starts_at";
+    pub const UTC_OFFSET: &str = "srs.utc_offset";
+    pub const QUEUE_ORDER: &str = "srs.queue_order";
+    pub const REVIEWS_PER_NEW_CARD: &str = "srs.reviews_per_new_card";
+    pub const BURY_SIBLINGS: &str = "srs.bury_siblings";
+    pub const SELF_RATING: &str = "srs.self_rating";
+    pub const LEECH_THRESHOLD: &str = "srs.leech_threshold";
+    pub const SUSPEND_LEECHES: &str = "srs.suspend_leeches";
+    pub const FUZZ_INTERVALS: &str = "srs.fuzz_intervals";
+    pub const BALANCE_LOAD: &str = "srs.balance_load";
+    pub const STUDY_PRESETS: &str = "srs.study_presets";
+    /// Set while migrated reviews still carry their UTC date instead of their study day
+    pub const RESTAMP_REVIEW_DAYS: &str = "srs.restamp_review_days";
 }
 
 /// Application metadata
@@ -40,4 +156,15 @@ pub mod app {
     /// Version (from Cargo.toml)
     #[allow(dead_code)]
     pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+    /// Database file name (relative to the working directory)
+    pub const DATABASE_FILE: &str = "nihonrs.db";
+
+    /// Deck receiving cards added by the tutor
+    pub const TUTOR_DECK_ID: &str = "deck-tutor";
+    pub const TUTOR_DECK_NAME: &str = "Tutor Cards";
+
+    /// Optional dictionary file (relative to the working directory) used to
+    /// check LLM readings and meanings
+    pub const DICTIONARY_FILE: &str = "dictionary.json";
 }
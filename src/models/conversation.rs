@@ -0,0 +1,167 @@
+//! Conversation and sentence writing practice models
+
+use crate::constants::srs;
+use serde::Deserialize;
+use std::ops::Range;
+
+/// A mistake in the learner's Japanese with its fix
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Correction {
+    /// Text exactly as the learner wrote it
+    pub original: String,
+    pub corrected: String,
+    pub explanation: String,
+}
+
+/// The tutor's answer to one learner message
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConversationReply {
+    /// In-character reply in Japanese
+    pub reply: String,
+    #[serde(default)]
+    pub corrections: Vec<Correction>,
+}
+
+/// The LLM's grade of a sentence written to practice a card
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WritingGrade {
+    /// Overall grade on the SM-2 scale, 0 (unusable) to 5 (correct and natural)
+    pub score: u8,
+    /// Whether the card's word or pattern was used correctly
+    pub target_used: bool,
+    #[serde(default)]
+    pub corrections: Vec<Correction>,
+    /// Short comment on correctness and naturalness
+    pub feedback: String,
+}
+
+impl WritingGrade {
+    /// Quality grade for scheduling; a sentence that misses the target cannot pass
+    pub fn quality(&self) -> u8 {
+        if self.target_used {
+            self.score.min(srs::MAX_QUALITY)
+        } else {
+            self.score.min(srs::PASSING_QUALITY - 1)
+        }
+    }
+}
+
+/// A learner message with the tutor's reply
+#[derive(Debug, Clone)]
+pub struct ConversationTurn {
+    pub message: String,
+    /// Empty while the reply is pending
+    pub reply: ConversationReply,
+}
+
+impl ConversationTurn {
+    pub fn new(message: String) -> Self {
+        Self {
+            message,
+            reply: ConversationReply {
+                reply: String::new(),
+                corrections: Vec::new(),
+            },
+        }
+    }
+
+    /// Byte ranges of the corrections in the message, by correction index
+    ///
+    /// Corrections whose text is not found, or only overlapping an earlier
+    /// one, have no range.
+    pub fn correction_spans(&self) -> Vec<Option<Range<usize>>> {
+        let mut taken: Vec<Range<usize>> = Vec::new();
+        self.reply
+            .corrections
+            .iter()
+            .map(|correction| {
+                if correction.original.is_empty() {
+                    return None;
+                }
+                let span = self
+                    .message
+                    .match_indices(correction.original.as_str())
+                    .map(|(start, found)| start..start + found.len())
+                    .find(|span| {
+                        !taken
+                            .iter()
+                            .any(|other| other.start < span.end && span.start < other.end)
+                    })?;
+                taken.push(span.clone());
+                Some(span)
+            })
+            .collect()
+    }
+
+    /// Split the message into consecutive pieces, tagging corrected ones with their index
+    pub fn pieces(&self) -> Vec<(&str, Option<usize>)> {
+        let mut spans: Vec<(Range<usize>, usize)> = self
+            .correction_spans()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, span)| span.map(|span| (span, index)))
+            .collect();
+        spans.sort_by_key(|(span, _)| span.start);
+
+        let mut pieces = Vec::new();
+        let mut position = 0;
+        for (span, index) in spans {
+            if span.start > position {
+                pieces.push((&self.message[position..span.start], None));
+            }
+            pieces.push((&self.message[span.clone()], Some(index)));
+            position = span.end;
+        }
+        if position < self.message.len() {
+            pieces.push((&self.message[position..], None));
+        }
+        pieces
+    }
+
+    /// The message with every located correction applied
+    pub fn corrected_message(&self) -> String {
+        self.pieces()
+            .into_iter()
+            .map(|(piece, correction)| match correction {
+                Some(index) => self.reply.corrections[index].corrected.as_str(),
+                None => piece,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn correction(original: &str, corrected: &str) -> Correction {
+        Correction {
+            original: original.to_string(),
+            corrected: corrected.to_string(),
+            explanation: String::new(),
+        }
+    }
+
+    #[test]
+    fn corrections_are_located_in_the_message() {
+        let mut turn = ConversationTurn::new("私は学生ですです。猫が好きです。".to_string());
+        turn.reply.corrections = vec![
+            correction("好きです", "大好きです"),
+            correction("ですです", "です"),
+            correction("犬", "猫"),
+        ];
+
+        assert_eq!(
+            turn.pieces(),
+            [
+                ("私は学生", None),
+                ("ですです", Some(1)),
+                ("。猫が", None),
+                ("好きです", Some(0)),
+                ("。", None),
+            ]
+        );
+        assert_eq!(turn.correction_spans()[2], None);
+        assert_eq!(turn.corrected_message(), "私は学生です。猫が大好きです。");
+    }
+}
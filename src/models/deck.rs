@@ -5,7 +5,8 @@ use native_db::{native_db, ToKey};
 use native_model::{native_model, Model};
 use serde::{Deserialize, Serialize};
 
-use super::word::WordSegment;
+use super::flashcard::SchedulerKind;
+use super::word::{JLPTLevel, WordExplanation, WordSegment};
 
 /// Information about a flashcard deck (UI model)
 #[derive(Debug, Clone)]
@@ -15,17 +16,20 @@ pub struct DeckInfo {
     pub total_cards: usize,
     pub due_cards: usize,
     pub new_cards: usize,
+    pub scheduler: SchedulerKind,
 }
 
 /// A flashcard deck - database model
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[native_model(id = 2, version = 1)]
+#[native_model(id = 2, version = 2, from = super::legacy::DeckV1)]
 #[native_db]
 pub struct Deck {
     #[primary_key]
     pub id: String,
     pub name: String,
     pub description: String,
+    /// Algorithm that schedules this deck's reviews
+    pub scheduler: SchedulerKind,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -53,6 +57,78 @@ pub struct LearningText {
     pub updated_at: DateTime<Utc>,
 }
 
+impl LearningText {
+    /// Create a new text with an id and title derived from its content
+    pub fn new(original_text: String, tokenized_segments: Vec<WordSegment>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: format!("text-{}", now.timestamp_millis()),
+            title: title_from_text(&original_text),
+            original_text,
+            tokenized_segments,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Links a graded reader rewrite to the text it was written from - database model
+///
+/// The rewrite itself is stored as an ordinary `LearningText`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 9, version = 1)]
+#[native_db]
+pub struct GradedText {
+    /// Id of the rewritten text
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub source_id: String,
+    pub level: JLPTLevel,
+    /// For each sentence of the rewrite, the source sentence it retells
+    pub sentence_map: Vec<Option<usize>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A text rewritten by the LLM at a JLPT level (not yet saved)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradedRewrite {
+    pub text: String,
+    /// For each sentence of `text`, the source sentence it retells
+    pub sentence_map: Vec<Option<usize>>,
+}
+
+/// One question and answer about a learning text - database model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 7, version = 1)]
+#[native_db]
+pub struct ConversationTurn {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub text_id: String,
+    pub question: String,
+    pub answer: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Build a short title from the first non-empty line of a text
+pub fn title_from_text(text: &str) -> String {
+    const MAX_CHARS: usize = 20;
+
+    let first_line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("Untitled");
+
+    let mut title: String = first_line.chars().take(MAX_CHARS).collect();
+    if first_line.chars().count() > MAX_CHARS {
+        title.push('…');
+    }
+    title
+}
+
 /// Cached LLM response - database model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[native_model(id = 4, version = 1)]
@@ -64,6 +140,20 @@ pub struct CachedResponse {
     pub created_at: DateTime<Utc>,
 }
 
+/// Explanation corrected by the user - database model
+///
+/// Authoritative for its word: shown instead of cached or newly generated
+/// explanations until the user regenerates it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 11, version = 2, from = super::legacy::EditedExplanationV1)]
+#[native_db]
+pub struct EditedExplanation {
+    #[primary_key]
+    pub base_form: String,
+    pub explanation: WordExplanation,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// User settings - database model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[native_model(id = 5, version = 1)]
@@ -74,3 +164,73 @@ pub struct UserSetting {
     pub value: String,
     pub updated_at: DateTime<Utc>,
 }
+
+/// LLM token usage for a single day - database model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 6, version = 1)]
+#[native_db]
+pub struct LLMUsage {
+    /// Local date in `YYYY-MM-DD` format
+    #[primary_key]
+    pub date: String,
+    pub requests: u32,
+    pub total_tokens: u64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Origin of a learner report revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportSource {
+    /// Written by the LLM from review history
+    Generated,
+    /// Edited by the user in Settings
+    Edited,
+}
+
+/// One revision of the AI-written learner report - database model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 8, version = 1)]
+#[native_db]
+pub struct LearnerReport {
+    #[primary_key]
+    pub id: String,
+    pub content: String,
+    pub source: ReportSource,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A message of a logged LLM call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedMessage {
+    /// `user` or `model`
+    pub role: String,
+    /// Message text, with function calls and results written out
+    pub text: String,
+}
+
+/// One LLM provider call with its prompt and outcome - database model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 10, version = 1)]
+#[native_db]
+pub struct LLMCallLog {
+    #[primary_key]
+    pub id: String,
+    /// `gemini` or `fixtures`
+    pub provider: String,
+    pub model: String,
+    /// Request kind, e.g. `explain` or `card`
+    pub kind: String,
+    /// Word or text the request was about, used to pick fixtures
+    pub word: Option<String>,
+    pub system: Option<String>,
+    pub messages: Vec<LoggedMessage>,
+    pub json_output: bool,
+    /// Raw reply, with requested function calls written out
+    pub response: Option<String>,
+    pub latency_ms: u64,
+    pub total_tokens: u64,
+    /// `LLMError` variant of a failed call
+    pub error_kind: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,6 +1,7 @@
 //! Flashcard data models for SRS practice
 
-use super::word::{ExampleSentence, JLPTLevel};
+use super::legacy;
+use super::word::{ExampleSentence, JLPTLevel, Language};
 use chrono::{DateTime, Utc};
 use native_db::{native_db, ToKey};
 use native_model::{native_model, Model};
@@ -23,6 +24,8 @@ pub struct VocabularyCard {
     pub wrong_answers: Vec<String>,
     pub example_sentences: Vec<ExampleSentence>,
     pub jlpt_level: JLPTLevel,
+    /// Language of the meaning and translations
+    pub language: Language,
 }
 
 /// Grammar flashcard
@@ -34,11 +37,13 @@ pub struct GrammarCard {
     pub wrong_answers: Vec<String>,
     pub example_sentences: Vec<ExampleSentence>,
     pub jlpt_level: JLPTLevel,
+    /// Language of the meaning and translations
+    pub language: Language,
 }
 
 /// A flashcard with SRS metadata - the database model
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[native_model(id = 1, version = 1)]
+#[native_model(id = 1, version = 5, from = legacy::FlashCardV4)]
 #[native_db]
 pub struct FlashCard {
     #[primary_key]
@@ -56,17 +61,118 @@ pub struct FlashCard {
 pub struct SRSData {
     /// Ease factor (difficulty multiplier)
     pub ease_factor: f32,
-    /// Current interval in days
+    /// Current interval in days, used once the card leaves its learning steps
     pub interval: u32,
     /// Number of consecutive correct answers
     pub repetitions: u32,
-    /// Next review date
+    /// Next review time, minutes away during learning steps
     pub next_review: DateTime<Utc>,
     /// Is this card new (never reviewed)?
     pub is_new: bool,
+    /// FSRS memory state, kept up to date whichever scheduler the deck uses
+    pub fsrs: Option<FsrsState>,
+    /// Learning or relearning step the card is on, if any
+    pub learning: Option<LearningStep>,
+    /// Times the card was forgotten after graduating from its learning steps
+    pub lapses: u32,
+    /// Forgotten so often that it is tagged as a leech
+    pub leech: bool,
+    /// Left out of practice until it is reset
+    pub suspended: bool,
+}
+
+impl SRSData {
+    /// Scheduling state of a card that was never reviewed
+    pub fn new_card(now: DateTime<Utc>) -> Self {
+        Self {
+            ease_factor: 2.5,
+            interval: 0,
+            repetitions: 0,
+            next_review: now,
+            is_new: true,
+            fsrs: None,
+            learning: None,
+            lapses: 0,
+            leech: false,
+            suspended: false,
+        }
+    }
+}
+
+/// Position of a card in its learning or relearning steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LearningStep {
+    /// Index into the learning or relearning steps
+    pub index: usize,
+    /// Relearning after a lapse, rather than learning a new card
+    pub relearning: bool,
+}
+
+/// Memory state of a card as modelled by FSRS
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FsrsState {
+    /// Days until the recall probability falls to 90%
+    pub stability: f32,
+    /// How hard the card is to remember, from 1 to 10
+    pub difficulty: f32,
+    pub last_review: DateTime<Utc>,
+}
+
+/// Algorithm that picks review intervals, chosen per deck
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchedulerKind {
+    #[default]
+    Sm2,
+    Fsrs,
+}
+
+impl SchedulerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchedulerKind::Sm2 => "SM-2",
+            SchedulerKind::Fsrs => "FSRS",
+        }
+    }
+}
+
+/// One answered review of a card - database model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 12, version = 2, from = legacy::ReviewLogV1)]
+#[native_db]
+pub struct ReviewLog {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub card_id: String,
+    /// Study day of the review, as YYYY-MM-DD
+    #[secondary_key]
+    pub day: String,
+    /// SM-2 quality grade, 0-5
+    pub quality: u8,
+    /// Chosen multiple-choice answer or written sentence
+    pub answer: Option<String>,
+    /// Time from showing the card to answering it
+    pub response_ms: Option<u64>,
+    /// Interval in days before and after the review
+    pub previous_interval: u32,
+    pub interval: u32,
+    /// Ease factor after the review
+    pub ease_factor: f32,
+    pub scheduler: SchedulerKind,
+    /// Whether the review rescheduled the card; custom sessions may not
+    pub rescheduled: bool,
+    pub reviewed_at: DateTime<Utc>,
 }
 
 impl CardType {
+    /// Word or grammar pattern on the front of the card
+    pub fn front(&self) -> &str {
+        match self {
+            CardType::Vocabulary(card) => &card.kanji,
+            CardType::Grammar(card) => &card.pattern,
+        }
+    }
+
     /// Get the correct answer for this card
     pub fn correct_answer(&self) -> &str {
         match self {
@@ -75,6 +181,14 @@ impl CardType {
         }
     }
 
+    /// Replace the correct answer of this card
+    pub fn set_correct_answer(&mut self, answer: String) {
+        match self {
+            CardType::Vocabulary(card) => card.meaning = answer,
+            CardType::Grammar(card) => card.explanation = answer,
+        }
+    }
+
     /// Get the wrong answers for this card
     pub fn wrong_answers(&self) -> &[String] {
         match self {
@@ -98,4 +212,12 @@ impl CardType {
             CardType::Grammar(card) => card.jlpt_level,
         }
     }
+
+    /// Get the language of this card's answers and translations
+    pub fn language(&self) -> Language {
+        match self {
+            CardType::Vocabulary(card) => card.language,
+            CardType::Grammar(card) => card.language,
+        }
+    }
 }
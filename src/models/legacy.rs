@@ -0,0 +1,507 @@
+//! Earlier versions of database models
+//!
+//! Each type mirrors how a model was stored before its current version, so
+//! existing databases can be migrated when they are opened (see
+//! `DatabaseService::new`). Conversions back to an old version drop the
+//! fields it did not have.
+
+use chrono::{DateTime, Utc};
+use native_db::{ToKey, native_db};
+use native_model::{Model, native_model};
+use serde::{Deserialize, Serialize};
+
+use super::deck::{Deck, EditedExplanation};
+use super::flashcard::{
+    CardType, FlashCard, FsrsState, GrammarCard, LearningStep, ReviewLog, SRSData, SchedulerKind,
+    VocabularyCard,
+};
+use super::word::{ExampleSentence, JLPTLevel, Language, WordExplanation};
+
+/// Example sentence before translations were tagged with their language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleSentenceV1 {
+    pub japanese: String,
+    pub english: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyCardV1 {
+    pub kanji: String,
+    pub hiragana: String,
+    pub romaji: String,
+    pub meaning: String,
+    pub wrong_answers: Vec<String>,
+    pub example_sentences: Vec<ExampleSentenceV1>,
+    pub jlpt_level: JLPTLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrammarCardV1 {
+    pub pattern: String,
+    pub pattern_reading: String,
+    pub explanation: String,
+    pub wrong_answers: Vec<String>,
+    pub example_sentences: Vec<ExampleSentenceV1>,
+    pub jlpt_level: JLPTLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CardTypeV1 {
+    Vocabulary(VocabularyCardV1),
+    Grammar(GrammarCardV1),
+}
+
+/// Flashcard before cards recorded their language; all were English
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 1, version = 1)]
+#[native_db]
+pub struct FlashCardV1 {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub deck_id: String,
+    pub card_type: CardTypeV1,
+    pub srs_data: SRSDataV1,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// SM-2 data before cards tracked their FSRS memory state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SRSDataV1 {
+    pub ease_factor: f32,
+    pub interval: u32,
+    pub repetitions: u32,
+    pub next_review: DateTime<Utc>,
+    pub is_new: bool,
+}
+
+/// Flashcard before cards tracked their FSRS memory state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 1, version = 2, from = FlashCardV1)]
+#[native_db]
+pub struct FlashCardV2 {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub deck_id: String,
+    pub card_type: CardType,
+    pub srs_data: SRSDataV1,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// SRS data before cards had learning steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SRSDataV2 {
+    pub ease_factor: f32,
+    pub interval: u32,
+    pub repetitions: u32,
+    pub next_review: DateTime<Utc>,
+    pub is_new: bool,
+    pub fsrs: Option<FsrsState>,
+}
+
+/// Flashcard before cards had learning steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 1, version = 3, from = FlashCardV2)]
+#[native_db]
+pub struct FlashCardV3 {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub deck_id: String,
+    pub card_type: CardType,
+    pub srs_data: SRSDataV2,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// SRS data before cards counted their lapses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SRSDataV3 {
+    pub ease_factor: f32,
+    pub interval: u32,
+    pub repetitions: u32,
+    pub next_review: DateTime<Utc>,
+    pub is_new: bool,
+    pub fsrs: Option<FsrsState>,
+    pub learning: Option<LearningStep>,
+}
+
+/// Flashcard before cards counted their lapses and could be suspended as leeches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 1, version = 4, from = FlashCardV3)]
+#[native_db]
+pub struct FlashCardV4 {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub deck_id: String,
+    pub card_type: CardType,
+    pub srs_data: SRSDataV3,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Review before the log recorded answers, intervals and study days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 12, version = 1)]
+#[native_db]
+pub struct ReviewLogV1 {
+    #[primary_key]
+    pub id: String,
+    #[secondary_key]
+    pub card_id: String,
+    pub quality: u8,
+    pub reviewed_at: DateTime<Utc>,
+}
+
+/// Deck before decks chose their scheduler; all used SM-2
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 2, version = 1)]
+#[native_db]
+pub struct DeckV1 {
+    #[primary_key]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordExplanationV1 {
+    pub meaning: String,
+    pub reading: Option<String>,
+    pub grammar_notes: Option<String>,
+    pub examples: Vec<ExampleSentenceV1>,
+    pub jlpt_level: String,
+    pub user_edited: bool,
+}
+
+/// Corrected explanation before explanations recorded their language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[native_model(id = 11, version = 1)]
+#[native_db]
+pub struct EditedExplanationV1 {
+    #[primary_key]
+    pub base_form: String,
+    pub explanation: WordExplanationV1,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn examples_from_v1(examples: Vec<ExampleSentenceV1>) -> Vec<ExampleSentence> {
+    examples
+        .into_iter()
+        .map(|example| ExampleSentence {
+            japanese: example.japanese,
+            translation: example.english,
+            language: Language::English,
+        })
+        .collect()
+}
+
+fn examples_to_v1(examples: Vec<ExampleSentence>) -> Vec<ExampleSentenceV1> {
+    examples
+        .into_iter()
+        .map(|example| ExampleSentenceV1 {
+            japanese: example.japanese,
+            english: example.translation,
+        })
+        .collect()
+}
+
+impl From<FlashCardV1> for FlashCardV2 {
+    fn from(card: FlashCardV1) -> Self {
+        let card_type = match card.card_type {
+            CardTypeV1::Vocabulary(vocab) => CardType::Vocabulary(VocabularyCard {
+                kanji: vocab.kanji,
+                hiragana: vocab.hiragana,
+                romaji: vocab.romaji,
+                meaning: vocab.meaning,
+                wrong_answers: vocab.wrong_answers,
+                example_sentences: examples_from_v1(vocab.example_sentences),
+                jlpt_level: vocab.jlpt_level,
+                language: Language::English,
+            }),
+            CardTypeV1::Grammar(grammar) => CardType::Grammar(GrammarCard {
+                pattern: grammar.pattern,
+                pattern_reading: grammar.pattern_reading,
+                explanation: grammar.explanation,
+                wrong_answers: grammar.wrong_answers,
+                example_sentences: examples_from_v1(grammar.example_sentences),
+                jlpt_level: grammar.jlpt_level,
+                language: Language::English,
+            }),
+        };
+
+        FlashCardV2 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type,
+            srs_data: card.srs_data,
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCardV2> for FlashCardV1 {
+    fn from(card: FlashCardV2) -> Self {
+        let card_type = match card.card_type {
+            CardType::Vocabulary(vocab) => CardTypeV1::Vocabulary(VocabularyCardV1 {
+                kanji: vocab.kanji,
+                hiragana: vocab.hiragana,
+                romaji: vocab.romaji,
+                meaning: vocab.meaning,
+                wrong_answers: vocab.wrong_answers,
+                example_sentences: examples_to_v1(vocab.example_sentences),
+                jlpt_level: vocab.jlpt_level,
+            }),
+            CardType::Grammar(grammar) => CardTypeV1::Grammar(GrammarCardV1 {
+                pattern: grammar.pattern,
+                pattern_reading: grammar.pattern_reading,
+                explanation: grammar.explanation,
+                wrong_answers: grammar.wrong_answers,
+                example_sentences: examples_to_v1(grammar.example_sentences),
+                jlpt_level: grammar.jlpt_level,
+            }),
+        };
+
+        FlashCardV1 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type,
+            srs_data: card.srs_data,
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCardV2> for FlashCardV3 {
+    fn from(card: FlashCardV2) -> Self {
+        let srs = card.srs_data;
+        FlashCardV3 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type: card.card_type,
+            srs_data: SRSDataV2 {
+                ease_factor: srs.ease_factor,
+                interval: srs.interval,
+                repetitions: srs.repetitions,
+                next_review: srs.next_review,
+                is_new: srs.is_new,
+                fsrs: None,
+            },
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCardV3> for FlashCardV2 {
+    fn from(card: FlashCardV3) -> Self {
+        let srs = card.srs_data;
+        FlashCardV2 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type: card.card_type,
+            srs_data: SRSDataV1 {
+                ease_factor: srs.ease_factor,
+                interval: srs.interval,
+                repetitions: srs.repetitions,
+                next_review: srs.next_review,
+                is_new: srs.is_new,
+            },
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCardV3> for FlashCardV4 {
+    fn from(card: FlashCardV3) -> Self {
+        let srs = card.srs_data;
+        FlashCardV4 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type: card.card_type,
+            srs_data: SRSDataV3 {
+                ease_factor: srs.ease_factor,
+                interval: srs.interval,
+                repetitions: srs.repetitions,
+                next_review: srs.next_review,
+                is_new: srs.is_new,
+                fsrs: srs.fsrs,
+                learning: None,
+            },
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCardV4> for FlashCardV3 {
+    fn from(card: FlashCardV4) -> Self {
+        let srs = card.srs_data;
+        FlashCardV3 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type: card.card_type,
+            srs_data: SRSDataV2 {
+                ease_factor: srs.ease_factor,
+                interval: srs.interval,
+                repetitions: srs.repetitions,
+                next_review: srs.next_review,
+                is_new: srs.is_new,
+                fsrs: srs.fsrs,
+            },
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCardV4> for FlashCard {
+    fn from(card: FlashCardV4) -> Self {
+        let srs = card.srs_data;
+        FlashCard {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type: card.card_type,
+            srs_data: SRSData {
+                ease_factor: srs.ease_factor,
+                interval: srs.interval,
+                repetitions: srs.repetitions,
+                next_review: srs.next_review,
+                is_new: srs.is_new,
+                fsrs: srs.fsrs,
+                learning: srs.learning,
+                lapses: 0,
+                leech: false,
+                suspended: false,
+            },
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<FlashCard> for FlashCardV4 {
+    fn from(card: FlashCard) -> Self {
+        let srs = card.srs_data;
+        FlashCardV4 {
+            id: card.id,
+            deck_id: card.deck_id,
+            card_type: card.card_type,
+            srs_data: SRSDataV3 {
+                ease_factor: srs.ease_factor,
+                interval: srs.interval,
+                repetitions: srs.repetitions,
+                next_review: srs.next_review,
+                is_new: srs.is_new,
+                fsrs: srs.fsrs,
+                learning: srs.learning,
+            },
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+}
+
+impl From<DeckV1> for Deck {
+    fn from(deck: DeckV1) -> Self {
+        Deck {
+            id: deck.id,
+            name: deck.name,
+            description: deck.description,
+            scheduler: SchedulerKind::Sm2,
+            created_at: deck.created_at,
+            updated_at: deck.updated_at,
+        }
+    }
+}
+
+impl From<Deck> for DeckV1 {
+    fn from(deck: Deck) -> Self {
+        DeckV1 {
+            id: deck.id,
+            name: deck.name,
+            description: deck.description,
+            created_at: deck.created_at,
+            updated_at: deck.updated_at,
+        }
+    }
+}
+
+/// Old reviews only know their grade; the day is the UTC date of the review
+impl From<ReviewLogV1> for ReviewLog {
+    fn from(review: ReviewLogV1) -> Self {
+        ReviewLog {
+            id: review.id,
+            card_id: review.card_id,
+            // UTC date until `DatabaseService::stamp_migrated_review_days` sets the study day
+            day: review.reviewed_at.date_naive().to_string(),
+            quality: review.quality,
+            answer: None,
+            response_ms: None,
+            previous_interval: 0,
+            interval: 0,
+            // Ease of a new card; the ease at the time was not recorded
+            ease_factor: 2.5,
+            scheduler: SchedulerKind::Sm2,
+            rescheduled: true,
+            reviewed_at: review.reviewed_at,
+        }
+    }
+}
+
+impl From<ReviewLog> for ReviewLogV1 {
+    fn from(review: ReviewLog) -> Self {
+        ReviewLogV1 {
+            id: review.id,
+            card_id: review.card_id,
+            quality: review.quality,
+            reviewed_at: review.reviewed_at,
+        }
+    }
+}
+
+impl From<EditedExplanationV1> for EditedExplanation {
+    fn from(edited: EditedExplanationV1) -> Self {
+        let explanation = edited.explanation;
+        EditedExplanation {
+            base_form: edited.base_form,
+            explanation: WordExplanation {
+                meaning: explanation.meaning,
+                reading: explanation.reading,
+                grammar_notes: explanation.grammar_notes,
+                examples: examples_from_v1(explanation.examples),
+                jlpt_level: explanation.jlpt_level,
+                user_edited: explanation.user_edited,
+                language: Language::English,
+            },
+            updated_at: edited.updated_at,
+        }
+    }
+}
+
+impl From<EditedExplanation> for EditedExplanationV1 {
+    fn from(edited: EditedExplanation) -> Self {
+        let explanation = edited.explanation;
+        EditedExplanationV1 {
+            base_form: edited.base_form,
+            explanation: WordExplanationV1 {
+                meaning: explanation.meaning,
+                reading: explanation.reading,
+                grammar_notes: explanation.grammar_notes,
+                examples: examples_to_v1(explanation.examples),
+                jlpt_level: explanation.jlpt_level,
+                user_edited: explanation.user_edited,
+            },
+            updated_at: edited.updated_at,
+        }
+    }
+}
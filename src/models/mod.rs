@@ -1,13 +1,19 @@
 //! Domain models and data structures
 //!
 //! This module contains all domain models used throughout the application,
-//! including flashcards, word segments, JLPT levels, and example sentences.
+//! including flashcards, word segments, JLPT levels, example sentences, and
+//! conversation and writing practice.
 
 pub mod flashcard;
 pub mod word;
 pub mod deck;
+pub mod conversation;
+pub mod legacy;
 
 // Re-export commonly used types
 pub use flashcard::{CardType, GrammarCard, VocabularyCard};
-pub use word::{ExampleSentence, JLPTLevel, WordExplanation, WordSegment};
+pub use word::{
+    ExampleSentence, JLPTLevel, Language, PhraseAlignment, SentenceTranslation, WordExplanation, WordSegment,
+};
 pub use deck::{DeckInfo, TextInfo};
+pub use conversation::{ConversationReply, ConversationTurn, WritingGrade};
@@ -2,12 +2,79 @@
 
 use iced::Color;
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 
-/// Example sentence with Japanese and English
+/// Example sentence with its translation for the learner
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExampleSentence {
     pub japanese: String,
-    pub english: String,
+    /// Translation in `language`; called `english` in older data
+    #[serde(alias = "english")]
+    pub translation: String,
+    #[serde(default)]
+    pub language: Language,
+}
+
+/// Language meanings, explanations and translations are written in
+///
+/// Serialized as its language tag, e.g. `es`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "id")]
+    Indonesian,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::English,
+        Language::Spanish,
+        Language::Indonesian,
+        Language::Portuguese,
+        Language::French,
+        Language::German,
+    ];
+
+    /// Language tag, e.g. `en`
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::Indonesian => "id",
+            Language::Portuguese => "pt",
+            Language::French => "fr",
+            Language::German => "de",
+        }
+    }
+
+    /// English name of the language, as used in prompts
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Spanish",
+            Language::Indonesian => "Indonesian",
+            Language::Portuguese => "Portuguese",
+            Language::French => "French",
+            Language::German => "German",
+        }
+    }
+
+    /// Parse a language tag, e.g. from settings
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.tag().eq_ignore_ascii_case(tag.trim()))
+    }
 }
 
 /// JLPT difficulty levels
@@ -22,6 +89,15 @@ pub enum JLPTLevel {
 }
 
 impl JLPTLevel {
+    /// Known levels, easiest first
+    pub const ALL: [JLPTLevel; 5] = [
+        JLPTLevel::N5,
+        JLPTLevel::N4,
+        JLPTLevel::N3,
+        JLPTLevel::N2,
+        JLPTLevel::N1,
+    ];
+
     pub fn as_str(&self) -> &str {
         match self {
             JLPTLevel::N5 => "N5",
@@ -67,13 +143,108 @@ pub struct WordSegment {
     pub is_selected: bool,
 }
 
+impl WordSegment {
+    /// Check if this segment ends a sentence
+    pub fn ends_sentence(&self) -> bool {
+        self.surface.ends_with(['。', '！', '？', '!', '?', '\n'])
+    }
+}
+
+/// Segment ranges of the sentences in a tokenized text
+///
+/// Runs of whitespace between sentences, such as blank lines, are skipped.
+pub fn sentence_ranges(segments: &[WordSegment]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for (index, segment) in segments.iter().enumerate() {
+        if segment.ends_sentence() || index + 1 == segments.len() {
+            let range = start..index + 1;
+            if segments[range.clone()]
+                .iter()
+                .any(|segment| !segment.surface.trim().is_empty())
+            {
+                ranges.push(range);
+            }
+            start = index + 1;
+        }
+    }
+    ranges
+}
+
+/// Text of each sentence of a tokenized text, as split by `sentence_ranges`
+pub fn sentences(segments: &[WordSegment]) -> Vec<String> {
+    sentence_ranges(segments)
+        .into_iter()
+        .map(|range| {
+            segments[range]
+                .iter()
+                .map(|segment| segment.surface.as_str())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
 /// LLM-generated explanation for a word
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WordExplanation {
     pub meaning: String,
+    /// Hiragana reading as given by the LLM
+    #[serde(default)]
+    pub reading: Option<String>,
     pub grammar_notes: Option<String>,
     pub examples: Vec<ExampleSentence>,
     pub jlpt_level: String,
+    /// Corrected by the user; takes precedence over generated explanations
+    #[serde(default)]
+    pub user_edited: bool,
+    /// Language of the meaning, notes and translations
+    #[serde(default)]
+    pub language: Language,
+}
+
+/// LLM translation of a sentence with phrase-level alignments
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentenceTranslation {
+    pub translation: String,
+    /// Non-overlapping links, ordered by position in `translation`
+    pub alignments: Vec<PhraseAlignment>,
+}
+
+/// Links a run of word segments to the phrase translating it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhraseAlignment {
+    /// Segment indices within the translated sentence
+    pub segments: Range<usize>,
+    /// Byte range of the phrase in the translation
+    pub phrase: Range<usize>,
+}
+
+impl SentenceTranslation {
+    /// Index of the alignment covering a segment of the sentence
+    pub fn alignment_for_segment(&self, segment: usize) -> Option<usize> {
+        self.alignments
+            .iter()
+            .position(|alignment| alignment.segments.contains(&segment))
+    }
+
+    /// Split the translation into consecutive pieces, each tagged with its alignment
+    pub fn pieces(&self) -> Vec<(&str, Option<usize>)> {
+        let mut pieces = Vec::new();
+        let mut position = 0;
+        for (index, alignment) in self.alignments.iter().enumerate() {
+            if alignment.phrase.start > position {
+                pieces.push((&self.translation[position..alignment.phrase.start], None));
+            }
+            pieces.push((&self.translation[alignment.phrase.clone()], Some(index)));
+            position = alignment.phrase.end;
+        }
+        if position < self.translation.len() {
+            pieces.push((&self.translation[position..], None));
+        }
+        pieces
+    }
 }
 
 /// Represents a single furigana span
@@ -0,0 +1,271 @@
+//! Batch flashcard generation from a whole text
+//!
+//! The pipeline tokenizes the text, dedupes base forms, drops particles,
+//! punctuation and words that already have a card, then asks the LLM for a
+//! `VocabularyCard` per remaining word with a bounded number of requests in flight.
+//! Each card is checked against the tokenizer reading and the local dictionary.
+
+use crate::constants;
+use crate::models::flashcard::FlashCard;
+use crate::models::{CardType, JLPTLevel, Language, VocabularyCard, WordSegment};
+use crate::services::dictionary::Dictionary;
+use crate::services::llm::{LLMError, LLMService};
+use crate::services::verification::{self, Mismatch};
+use iced::futures::{Stream, StreamExt, stream};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Particles and auxiliaries that never become vocabulary cards
+const PARTICLES: &[&str] = &[
+    "は", "が", "を", "に", "へ", "で", "と", "も", "の", "や", "か", "から", "まで", "より", "ね",
+    "よ", "な", "て", "だ", "です", "ます",
+];
+
+/// Progress of a running batch generation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GenerationProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+}
+
+impl GenerationProgress {
+    /// Number of words processed so far, successfully or not
+    pub fn processed(&self) -> usize {
+        self.completed + self.failed
+    }
+
+    /// Check if every word has been processed
+    pub fn is_done(&self) -> bool {
+        self.processed() >= self.total
+    }
+}
+
+/// A generated card with its differences from local data
+#[derive(Debug, Clone)]
+pub struct GeneratedCard {
+    pub card: VocabularyCard,
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// Collect the words that already have a card in any deck
+pub fn known_words(cards: &[FlashCard]) -> HashSet<String> {
+    cards
+        .iter()
+        .map(|card| match &card.card_type {
+            CardType::Vocabulary(vocab) => vocab.kanji.clone(),
+            CardType::Grammar(grammar) => grammar.pattern.clone(),
+        })
+        .collect()
+}
+
+/// Select the words of a tokenized text that should become new cards
+///
+/// Keeps the first occurrence of each base form and skips particles,
+/// punctuation and words listed in `known`.
+pub fn select_words(segments: Vec<WordSegment>, known: &HashSet<String>) -> Vec<WordSegment> {
+    let mut seen = HashSet::new();
+    segments
+        .into_iter()
+        .filter(|segment| is_content_word(segment) && !known.contains(&segment.base_form))
+        .filter(|segment| seen.insert(segment.base_form.clone()))
+        .collect()
+}
+
+/// Generate a card for each word, yielding results in completion order
+pub fn generate_cards(
+    llm: LLMService,
+    words: Vec<WordSegment>,
+    text: &str,
+    dictionary: Option<Arc<Dictionary>>,
+) -> impl Stream<Item = Result<GeneratedCard, LLMError>> + use<> {
+    let llm = Arc::new(llm);
+    let jobs: Vec<(WordSegment, String)> = words
+        .into_iter()
+        .map(|word| {
+            let sentence = sentence_containing(text, &word.surface).to_string();
+            (word, sentence)
+        })
+        .collect();
+
+    stream::iter(jobs)
+        .map(move |(word, sentence)| {
+            let llm = Arc::clone(&llm);
+            let dictionary = dictionary.clone();
+            async move {
+                let card = llm
+                    .generate_vocabulary_card(
+                        &word.surface,
+                        &word.reading,
+                        &word.base_form,
+                        &sentence,
+                    )
+                    .await?;
+                // The tokenizer reading is for the surface, which may be inflected
+                let local_reading =
+                    (word.surface == word.base_form).then_some(word.reading.as_str());
+                let mismatches =
+                    verification::verify_card(&card, local_reading, dictionary.as_deref());
+                Ok(GeneratedCard { card, mismatches })
+            }
+        })
+        .buffer_unordered(constants::llm::MAX_CONCURRENT_REQUESTS)
+}
+
+/// Build a card for a word from the local dictionary, for use without an LLM
+///
+/// The card has no wrong answers or examples; wrong answers are added from
+/// other cards and the dictionary when the deck is opened.
+pub fn dictionary_card(word: &WordSegment, dictionary: &Dictionary) -> Option<VocabularyCard> {
+    let entry = dictionary.lookup(&word.base_form)?;
+    if entry.glosses.is_empty() {
+        return None;
+    }
+
+    Some(VocabularyCard {
+        kanji: entry.word.clone(),
+        hiragana: entry
+            .readings
+            .first()
+            .cloned()
+            .unwrap_or_else(|| word.reading.clone()),
+        romaji: String::new(),
+        meaning: entry
+            .glosses
+            .iter()
+            .take(2)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("; "),
+        wrong_answers: Vec::new(),
+        example_sentences: Vec::new(),
+        jlpt_level: entry.jlpt.unwrap_or(JLPTLevel::Unknown),
+        // Dictionary glosses are English
+        language: Language::English,
+    })
+}
+
+/// Find the sentence of `text` that contains `surface`, falling back to the whole text
+pub fn sentence_containing<'a>(text: &'a str, surface: &str) -> &'a str {
+    text.split_inclusive(['。', '！', '？', '\n'])
+        .find(|sentence| sentence.contains(surface))
+        .map(str::trim)
+        .unwrap_or(text)
+}
+
+fn is_content_word(segment: &WordSegment) -> bool {
+    !PARTICLES.contains(&segment.base_form.as_str())
+        && segment.base_form.chars().any(is_japanese_char)
+}
+
+fn is_japanese_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3041}'..='\u{3096}' // Hiragana
+            | '\u{30A1}'..='\u{30FA}' // Katakana
+            | '\u{3400}'..='\u{4DBF}' // CJK extension A
+            | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+            | '々'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::llm::mock_service;
+    use iced::futures::executor::block_on;
+
+    fn segment(surface: &str, reading: &str, base_form: &str) -> WordSegment {
+        WordSegment {
+            surface: surface.to_string(),
+            reading: reading.to_string(),
+            base_form: base_form.to_string(),
+            explanation: None,
+            is_selected: false,
+        }
+    }
+
+    #[test]
+    fn select_words_skips_particles_punctuation_duplicates_and_known_words() {
+        let segments = vec![
+            segment("今日", "きょう", "今日"),
+            segment("は", "は", "は"),
+            segment("寿司", "すし", "寿司"),
+            segment("を", "を", "を"),
+            segment("食べた", "たべた", "食べる"),
+            segment("。", "。", "。"),
+            segment("食べます", "たべます", "食べる"),
+        ];
+        let known = HashSet::from(["寿司".to_string()]);
+
+        let words: Vec<String> = select_words(segments, &known)
+            .into_iter()
+            .map(|word| word.surface)
+            .collect();
+
+        assert_eq!(words, ["今日", "食べた"]);
+    }
+
+    #[test]
+    fn generate_cards_answers_from_fixtures_without_network() {
+        let text = "今日は日本語を勉強します。寿司を食べたい。";
+        let words = vec![
+            segment("食べ", "たべ", "食べる"),
+            segment("勉強", "べんきょう", "勉強"),
+        ];
+
+        let results: Vec<_> = block_on(generate_cards(mock_service(), words, text, None).collect());
+        let mut cards: Vec<VocabularyCard> = results
+            .into_iter()
+            .map(|result| result.unwrap().card)
+            .collect();
+        cards.sort_by(|a, b| a.kanji.cmp(&b.kanji));
+
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].kanji, "勉強");
+        assert_eq!(cards[0].meaning, "study");
+        assert_eq!(cards[1].kanji, "食べる");
+        assert_eq!(cards[1].hiragana, "たべる");
+        assert_eq!(cards[1].meaning, "to eat");
+        assert_eq!(cards[1].wrong_answers, ["to drink", "to cook", "to buy"]);
+    }
+
+    #[test]
+    fn generate_cards_uses_default_fixture_for_unknown_words() {
+        let words = vec![segment("猫", "ねこ", "猫")];
+
+        let results: Vec<_> =
+            block_on(generate_cards(mock_service(), words, "猫がいる。", None).collect());
+
+        let card = results.into_iter().next().unwrap().unwrap().card;
+        assert_eq!(card.kanji, "猫");
+        assert_eq!(card.meaning, "猫 (demo meaning)");
+        assert_eq!(card.wrong_answers.len(), 3);
+    }
+
+    #[test]
+    fn generated_cards_are_checked_against_the_tokenizer_reading() {
+        let words = vec![segment("今日", "あした", "今日")];
+
+        let results: Vec<_> =
+            block_on(generate_cards(mock_service(), words, "今日は晴れ。", None).collect());
+
+        let generated = results.into_iter().next().unwrap().unwrap();
+        assert_eq!(generated.card.hiragana, "きょう");
+        assert_eq!(
+            generated.mismatches,
+            [Mismatch::Reading {
+                llm: "きょう".to_string(),
+                local: "あした".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn sentence_containing_picks_the_matching_sentence() {
+        let text = "今日は晴れ。寿司を食べたい！明日は雨？";
+
+        assert_eq!(sentence_containing(text, "寿司"), "寿司を食べたい！");
+        assert_eq!(sentence_containing(text, "猫"), text);
+    }
+}
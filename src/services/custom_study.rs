@@ -0,0 +1,276 @@
+//! Custom study sessions
+//!
+//! A custom session practices the cards matching a `StudyFilter` instead of
+//! the day's due queue: only N3 grammar, say, or only the cards failed today.
+//! Sessions can leave scheduling untouched, so cramming does not disturb the
+//! intervals, and can be saved as named presets.
+
+use crate::constants::srs::PASSING_QUALITY;
+use crate::models::JLPTLevel;
+use crate::models::flashcard::{CardType, FlashCard, ReviewLog};
+use crate::services::study_day::DayRollover;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Kind of card, by `CardType` variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardKind {
+    Vocabulary,
+    Grammar,
+}
+
+impl CardKind {
+    pub const ALL: [CardKind; 2] = [CardKind::Vocabulary, CardKind::Grammar];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CardKind::Vocabulary => "Vocabulary",
+            CardKind::Grammar => "Grammar",
+        }
+    }
+
+    pub fn of(card: &CardType) -> Self {
+        match card {
+            CardType::Vocabulary(_) => CardKind::Vocabulary,
+            CardType::Grammar(_) => CardKind::Grammar,
+        }
+    }
+}
+
+/// Tag a card carries from its scheduling state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CardTag {
+    New,
+    Learning,
+    Leech,
+    Suspended,
+}
+
+impl CardTag {
+    pub const ALL: [CardTag; 4] = [
+        CardTag::New,
+        CardTag::Learning,
+        CardTag::Leech,
+        CardTag::Suspended,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CardTag::New => "New",
+            CardTag::Learning => "Learning",
+            CardTag::Leech => "Leech",
+            CardTag::Suspended => "Suspended",
+        }
+    }
+
+    /// Check if a card carries this tag
+    pub fn applies_to(&self, card: &FlashCard) -> bool {
+        let srs = &card.srs_data;
+        match self {
+            CardTag::New => srs.is_new,
+            CardTag::Learning => srs.learning.is_some(),
+            CardTag::Leech => srs.leech,
+            CardTag::Suspended => srs.suspended,
+        }
+    }
+}
+
+/// Which cards a custom session practices
+///
+/// Empty lists and unset day counts match every card. Day counts are study
+/// days, so 1 means today.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StudyFilter {
+    pub deck_ids: Vec<String>,
+    pub kinds: Vec<CardKind>,
+    pub levels: Vec<JLPTLevel>,
+    /// Cards must carry every tag; suspended cards are left out unless tagged `Suspended` here
+    pub tags: Vec<CardTag>,
+    pub failed_within_days: Option<u32>,
+    pub added_within_days: Option<u32>,
+    pub overdue_only: bool,
+    /// Reschedule the cards answered in the session, as in a normal review
+    pub affects_scheduling: bool,
+}
+
+impl StudyFilter {
+    /// Cards matching the filter, most overdue first
+    pub fn select(
+        &self,
+        cards: Vec<FlashCard>,
+        history: &[ReviewLog],
+        rollover: &DayRollover,
+        now: DateTime<Utc>,
+    ) -> Vec<FlashCard> {
+        // Start of the study day `days` study days back, counting today
+        let since =
+            |days: u32| rollover.day_start(now) - Duration::days(i64::from(days.saturating_sub(1)));
+        let failed: Option<HashSet<&str>> = self.failed_within_days.map(|days| {
+            history
+                .iter()
+                .filter(|review| review.reviewed_at >= since(days))
+                .filter(|review| review.quality < PASSING_QUALITY)
+                .map(|review| review.card_id.as_str())
+                .collect()
+        });
+        let added_since = self.added_within_days.map(since);
+
+        let mut selected: Vec<FlashCard> = cards
+            .into_iter()
+            .filter(|card| self.deck_ids.is_empty() || self.deck_ids.contains(&card.deck_id))
+            .filter(|card| {
+                self.kinds.is_empty() || self.kinds.contains(&CardKind::of(&card.card_type))
+            })
+            .filter(|card| {
+                self.levels.is_empty() || self.levels.contains(&card.card_type.jlpt_level())
+            })
+            .filter(|card| self.tags.iter().all(|tag| tag.applies_to(card)))
+            .filter(|card| !card.srs_data.suspended || self.tags.contains(&CardTag::Suspended))
+            .filter(|card| {
+                failed
+                    .as_ref()
+                    .is_none_or(|failed| failed.contains(card.id.as_str()))
+            })
+            .filter(|card| added_since.is_none_or(|since| card.created_at >= since))
+            .filter(|card| {
+                !self.overdue_only || (!card.srs_data.is_new && card.srs_data.next_review <= now)
+            })
+            .collect();
+        selected.sort_by_key(|card| (card.srs_data.next_review, card.created_at));
+        selected
+    }
+}
+
+/// A custom session saved under a name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StudyPreset {
+    pub name: String,
+    pub filter: StudyFilter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Language;
+    use crate::models::flashcard::{GrammarCard, SRSData, SchedulerKind};
+    use chrono::FixedOffset;
+
+    fn card(id: &str, level: JLPTLevel, created_days_ago: i64, now: DateTime<Utc>) -> FlashCard {
+        FlashCard {
+            id: id.to_string(),
+            deck_id: "deck".to_string(),
+            card_type: CardType::Grammar(GrammarCard {
+                pattern: id.to_string(),
+                pattern_reading: String::new(),
+                explanation: String::new(),
+                wrong_answers: Vec::new(),
+                example_sentences: Vec::new(),
+                jlpt_level: level,
+                language: Language::English,
+            }),
+            srs_data: SRSData {
+                is_new: false,
+                next_review: now + Duration::days(1),
+                ..SRSData::new_card(now)
+            },
+            created_at: now - Duration::days(created_days_ago),
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn filters_narrow_down_the_session() {
+        let rollover = DayRollover {
+            starts_at: 4,
+            utc_offset: Some(FixedOffset::east_opt(0).unwrap()),
+        };
+        let now = DateTime::parse_from_rfc3339("2026-03-11T12:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        let mut overdue = card("overdue", JLPTLevel::N3, 30, now);
+        overdue.srs_data.next_review = now - Duration::days(2);
+        let mut suspended = card("suspended", JLPTLevel::N3, 30, now);
+        suspended.srs_data.suspended = true;
+        let cards = vec![
+            card("failed-today", JLPTLevel::N3, 30, now),
+            card("failed-yesterday", JLPTLevel::N3, 30, now),
+            card("added-today", JLPTLevel::N5, 0, now),
+            overdue,
+            suspended,
+        ];
+        let failure = |card: &str, hours_ago: i64| ReviewLog {
+            id: card.to_string(),
+            card_id: card.to_string(),
+            day: rollover
+                .day_of(now - Duration::hours(hours_ago))
+                .to_string(),
+            quality: 1,
+            answer: Some("wrong".to_string()),
+            response_ms: Some(3000),
+            previous_interval: 1,
+            interval: 1,
+            ease_factor: 2.3,
+            scheduler: SchedulerKind::Sm2,
+            rescheduled: true,
+            reviewed_at: now - Duration::hours(hours_ago),
+        };
+        let history = [failure("failed-today", 2), failure("failed-yesterday", 20)];
+
+        let cases = [
+            (
+                StudyFilter::default(),
+                vec!["overdue", "failed-today", "failed-yesterday", "added-today"],
+            ),
+            (
+                StudyFilter {
+                    levels: vec![JLPTLevel::N3],
+                    kinds: vec![CardKind::Grammar],
+                    ..Default::default()
+                },
+                vec!["overdue", "failed-today", "failed-yesterday"],
+            ),
+            (
+                StudyFilter {
+                    failed_within_days: Some(1),
+                    ..Default::default()
+                },
+                vec!["failed-today"],
+            ),
+            (
+                StudyFilter {
+                    failed_within_days: Some(2),
+                    ..Default::default()
+                },
+                vec!["failed-today", "failed-yesterday"],
+            ),
+            (
+                StudyFilter {
+                    added_within_days: Some(1),
+                    ..Default::default()
+                },
+                vec!["added-today"],
+            ),
+            (
+                StudyFilter {
+                    overdue_only: true,
+                    ..Default::default()
+                },
+                vec!["overdue"],
+            ),
+            (
+                StudyFilter {
+                    tags: vec![CardTag::Suspended],
+                    ..Default::default()
+                },
+                vec!["suspended"],
+            ),
+        ];
+        for (filter, expected) in cases {
+            let selected = filter.select(cards.clone(), &history, &rollover, now);
+            let ids: Vec<&str> = selected.iter().map(|card| card.id.as_str()).collect();
+            assert_eq!(ids, expected, "{:?}", filter);
+        }
+    }
+}
@@ -1,16 +1,27 @@
 //! Database service for persistent storage
 //!
 //! This service uses `native_db` for local storage of:
-//! - Flashcards and SRS progress
-//! - Saved texts and cached LLM responses
+//! - Flashcards, SRS progress and the log of reviews
+//! - Saved texts, their graded reader rewrites and cached LLM responses
+//! - Explanations corrected by the user
 //! - User settings and preferences
+//! - An audit log of LLM calls
 
+use crate::constants;
 use crate::models::{
-    deck::{CachedResponse, Deck, LearningText, UserSetting},
-    flashcard::{CardType, FlashCard, SRSData},
-    DeckInfo, TextInfo,
+    deck::{
+        CachedResponse, ConversationTurn, Deck, EditedExplanation, GradedText, LLMCallLog,
+        LLMUsage, LearnerReport, LearningText, ReportSource, UserSetting,
+    },
+    flashcard::{CardType, FlashCard, ReviewLog, ReviewLogKey, SRSData, SchedulerKind},
+    legacy::{
+        DeckV1, EditedExplanationV1, FlashCardV1, FlashCardV2, FlashCardV3, FlashCardV4,
+        ReviewLogV1,
+    },
+    DeckInfo, TextInfo, WordExplanation,
 };
-use chrono::Utc;
+use crate::services::study_day::DayRollover;
+use chrono::{NaiveDate, Utc};
 use native_db::{Builder, Database, Models};
 use once_cell::sync::Lazy;
 use std::path::PathBuf;
@@ -18,11 +29,28 @@ use std::path::PathBuf;
 // Define all database models
 static MODELS: Lazy<Models> = Lazy::new(|| {
     let mut models = Models::new();
+    // Earlier versions first, so stored data can be migrated. native_db only
+    // treats models with a higher version than the last defined one as
+    // current, so versioned models come before the version 1 models.
+    models.define::<FlashCardV1>().unwrap();
+    models.define::<FlashCardV2>().unwrap();
+    models.define::<FlashCardV3>().unwrap();
+    models.define::<FlashCardV4>().unwrap();
+    models.define::<EditedExplanationV1>().unwrap();
+    models.define::<DeckV1>().unwrap();
+    models.define::<ReviewLogV1>().unwrap();
     models.define::<FlashCard>().unwrap();
+    models.define::<EditedExplanation>().unwrap();
     models.define::<Deck>().unwrap();
+    models.define::<ReviewLog>().unwrap();
     models.define::<LearningText>().unwrap();
     models.define::<CachedResponse>().unwrap();
     models.define::<UserSetting>().unwrap();
+    models.define::<LLMUsage>().unwrap();
+    models.define::<ConversationTurn>().unwrap();
+    models.define::<LearnerReport>().unwrap();
+    models.define::<GradedText>().unwrap();
+    models.define::<LLMCallLog>().unwrap();
     models
 });
 
@@ -33,12 +61,16 @@ pub struct DatabaseService {
 
 impl DatabaseService {
     /// Initialize the database service with a file path
+    ///
+    /// Data stored by earlier versions of the models is migrated first.
     pub fn new(db_path: PathBuf) -> Result<Self, DatabaseError> {
         let db = Builder::new()
             .create(&MODELS, db_path)
             .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
 
-        Ok(Self { db })
+        let service = Self { db };
+        service.migrate()?;
+        Ok(service)
     }
 
     /// Initialize in-memory database (for testing)
@@ -51,6 +83,41 @@ impl DatabaseService {
         Ok(Self { db })
     }
 
+    /// Upgrade models stored in an earlier version to the current one
+    fn migrate(&self) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+
+        rw.migrate::<FlashCard>()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        rw.migrate::<EditedExplanation>()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        rw.migrate::<Deck>()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+
+        // Migrated reviews only know their UTC date; flag them for their study day
+        let legacy_reviews = rw
+            .len()
+            .primary::<ReviewLogV1>()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        rw.migrate::<ReviewLog>()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        if legacy_reviews > 0 {
+            rw.upsert(UserSetting {
+                key: constants::settings::RESTAMP_REVIEW_DAYS.to_string(),
+                value: "true".to_string(),
+                updated_at: Utc::now(),
+            })
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        }
+        rw.commit()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+
+        Ok(())
+    }
+
     /// Save a flashcard to the database
     pub fn save_card(
         &self,
@@ -68,13 +135,7 @@ impl DatabaseService {
             id,
             deck_id,
             card_type,
-            srs_data: SRSData {
-                ease_factor: 2.5,
-                interval: 0,
-                repetitions: 0,
-                next_review: now,
-                is_new: true,
-            },
+            srs_data: SRSData::new_card(now),
             created_at: now,
             updated_at: now,
         };
@@ -112,6 +173,35 @@ impl DatabaseService {
         Ok(())
     }
 
+    /// Replace the content of a card, keeping its SRS data
+    pub fn update_card_content(
+        &self,
+        id: &str,
+        card_type: CardType,
+    ) -> Result<FlashCard, DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let old_card: FlashCard = rw
+            .get()
+            .primary(id.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .ok_or_else(|| DatabaseError::QueryError(format!("Card not found: {}", id)))?;
+
+        let mut new_card = old_card.clone();
+        new_card.card_type = card_type;
+        new_card.updated_at = Utc::now();
+
+        rw.update(old_card, new_card.clone())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(new_card)
+    }
+
     /// Get all cards in a deck
     pub fn get_deck_cards(&self, deck_id: &str) -> Result<Vec<FlashCard>, DatabaseError> {
         let r = self
@@ -126,6 +216,28 @@ impl DatabaseService {
             .start_with(deck_id.to_string())
             .map_err(|e| DatabaseError::QueryError(e.to_string()))?
             .collect();
+        let mut cards = cards.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        // `start_with` is a prefix match, so drop cards of decks whose id extends this one
+        cards.retain(|card| card.deck_id == deck_id);
+
+        Ok(cards)
+    }
+
+    /// Get all cards across every deck
+    pub fn get_all_cards(&self) -> Result<Vec<FlashCard>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let cards: Result<Vec<FlashCard>, _> = r
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
         let cards = cards.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
 
         Ok(cards)
@@ -143,6 +255,7 @@ impl DatabaseService {
             id,
             name,
             description,
+            scheduler: SchedulerKind::default(),
             created_at: now,
             updated_at: now,
         };
@@ -155,6 +268,46 @@ impl DatabaseService {
         Ok(())
     }
 
+    /// Choose the scheduler of a deck
+    pub fn set_deck_scheduler(&self, id: &str, scheduler: SchedulerKind) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let old_deck: Deck = rw
+            .get()
+            .primary(id.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .ok_or_else(|| DatabaseError::QueryError(format!("Deck not found: {}", id)))?;
+
+        let mut new_deck = old_deck.clone();
+        new_deck.scheduler = scheduler;
+        new_deck.updated_at = Utc::now();
+
+        rw.update(old_deck, new_deck)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the scheduler of a deck, SM-2 if the deck does not exist
+    pub fn get_deck_scheduler(&self, id: &str) -> Result<SchedulerKind, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let deck: Option<Deck> = r
+            .get()
+            .primary(id.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(deck.map(|deck| deck.scheduler).unwrap_or_default())
+    }
+
     /// Load all decks with statistics
     pub fn load_decks(&self) -> Result<Vec<DeckInfo>, DatabaseError> {
         let r = self
@@ -178,7 +331,7 @@ impl DatabaseService {
             let now = Utc::now();
             let due_cards = cards
                 .iter()
-                .filter(|c| c.srs_data.next_review <= now)
+                .filter(|c| c.srs_data.next_review <= now && !c.srs_data.suspended)
                 .count();
             let new_cards = cards.iter().filter(|c| c.srs_data.is_new).count();
 
@@ -188,6 +341,7 @@ impl DatabaseService {
                 total_cards,
                 due_cards,
                 new_cards,
+                scheduler: deck.scheduler,
             });
         }
 
@@ -257,6 +411,369 @@ impl DatabaseService {
         Ok(text)
     }
 
+    /// Save the link from a graded reader rewrite to its source text
+    pub fn save_graded_text(&self, link: GradedText) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        rw.insert(link)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the source link of a text, if it is a graded reader rewrite
+    pub fn get_graded_text(&self, id: &str) -> Result<Option<GradedText>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        r.get()
+            .primary(id.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// Get the graded reader rewrites of a text, newest first
+    pub fn get_graded_versions(&self, source_id: &str) -> Result<Vec<GradedText>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let links: Result<Vec<GradedText>, _> = r
+            .scan()
+            .secondary(crate::models::deck::GradedTextKey::source_id)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .start_with(source_id.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut links = links.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        // `start_with` is a prefix match, so drop rewrites of texts whose id extends this one
+        links.retain(|link| link.source_id == source_id);
+        links.sort_by_key(|link| std::cmp::Reverse(link.created_at));
+
+        Ok(links)
+    }
+
+    /// Append a question and its answer to the conversation about a text
+    pub fn save_conversation_turn(
+        &self,
+        text_id: &str,
+        question: &str,
+        answer: &str,
+    ) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let now = Utc::now();
+        let turn = ConversationTurn {
+            id: format!("{}-{}", text_id, now.timestamp_micros()),
+            text_id: text_id.to_string(),
+            question: question.to_string(),
+            answer: answer.to_string(),
+            created_at: now,
+        };
+
+        rw.insert(turn)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the conversation about a text, oldest turn first
+    pub fn get_conversation(&self, text_id: &str) -> Result<Vec<ConversationTurn>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let turns: Result<Vec<ConversationTurn>, _> = r
+            .scan()
+            .secondary(crate::models::deck::ConversationTurnKey::text_id)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .start_with(text_id.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut turns = turns.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        // `start_with` is a prefix match, so drop turns of texts whose id extends this one
+        turns.retain(|turn| turn.text_id == text_id);
+        turns.sort_by_key(|turn| turn.created_at);
+
+        Ok(turns)
+    }
+
+    /// Get the most recent questions across all texts, newest first
+    pub fn get_recent_questions(&self, limit: usize) -> Result<Vec<ConversationTurn>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let turns: Result<Vec<ConversationTurn>, _> = r
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut turns = turns.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        turns.sort_by_key(|turn| std::cmp::Reverse(turn.created_at));
+        turns.truncate(limit);
+
+        Ok(turns)
+    }
+
+    /// Store a new learner report revision, dropping the oldest beyond `MAX_REPORT_REVISIONS`
+    pub fn save_learner_report(
+        &self,
+        content: &str,
+        source: ReportSource,
+    ) -> Result<LearnerReport, DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let now = Utc::now();
+        let report = LearnerReport {
+            id: format!("report-{}", now.timestamp_micros()),
+            content: content.to_string(),
+            source,
+            created_at: now,
+        };
+        rw.insert(report.clone())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let existing: Result<Vec<LearnerReport>, _> = rw
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut existing = existing.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        existing.sort_by_key(|revision| std::cmp::Reverse(revision.created_at));
+        for old in existing.into_iter().skip(constants::llm::MAX_REPORT_REVISIONS) {
+            rw.remove(old)
+                .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        }
+
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(report)
+    }
+
+    /// Get all learner report revisions, newest first
+    pub fn get_report_history(&self) -> Result<Vec<LearnerReport>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let reports: Result<Vec<LearnerReport>, _> = r
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut reports = reports.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        reports.sort_by_key(|report| std::cmp::Reverse(report.created_at));
+
+        Ok(reports)
+    }
+
+    /// Get the current learner report, if one has been written
+    pub fn get_latest_report(&self) -> Result<Option<LearnerReport>, DatabaseError> {
+        Ok(self.get_report_history()?.into_iter().next())
+    }
+
+    /// Store an LLM call, dropping the oldest beyond `AUDIT_LOG_LIMIT`
+    pub fn record_llm_call(&self, call: LLMCallLog) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        rw.insert(call)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let existing: Result<Vec<LLMCallLog>, _> = rw
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut existing = existing.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        existing.sort_by_key(|call| std::cmp::Reverse(call.created_at));
+        for old in existing.into_iter().skip(constants::llm::AUDIT_LOG_LIMIT) {
+            rw.remove(old)
+                .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        }
+
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the logged LLM calls, newest first
+    pub fn get_llm_calls(&self) -> Result<Vec<LLMCallLog>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let calls: Result<Vec<LLMCallLog>, _> = r
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut calls = calls.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        calls.sort_by_key(|call| std::cmp::Reverse(call.created_at));
+
+        Ok(calls)
+    }
+
+    /// Store an answered review
+    pub fn record_review(&self, review: ReviewLog) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        rw.insert(review)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Index every review under its study day according to `rollover`
+    ///
+    /// Needed after the start of the study day changes.
+    pub fn restamp_review_days(&self, rollover: &DayRollover) -> Result<(), DatabaseError> {
+        let reviews = self.get_reviews()?;
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        for review in reviews {
+            let day = rollover.day_of(review.reviewed_at).to_string();
+            if day != review.day {
+                rw.upsert(ReviewLog { day, ..review })
+                    .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+            }
+        }
+
+        let flag: Option<UserSetting> = rw
+            .get()
+            .primary(constants::settings::RESTAMP_REVIEW_DAYS.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        if let Some(flag) = flag {
+            rw.remove(flag)
+                .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        }
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Give reviews migrated from before study days their study day, once
+    pub fn stamp_migrated_review_days(&self, rollover: &DayRollover) -> Result<(), DatabaseError> {
+        if self
+            .load_settings(constants::settings::RESTAMP_REVIEW_DAYS)?
+            .is_none()
+        {
+            return Ok(());
+        }
+        self.restamp_review_days(rollover)
+    }
+
+    /// Get every stored review, oldest first
+    pub fn get_reviews(&self) -> Result<Vec<ReviewLog>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let reviews: Result<Vec<ReviewLog>, _> = r
+            .scan()
+            .primary()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .all()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut reviews = reviews.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        reviews.sort_by_key(|review| review.reviewed_at);
+
+        Ok(reviews)
+    }
+
+    /// Get the reviews of one card, oldest first
+    pub fn get_card_reviews(&self, card_id: &str) -> Result<Vec<ReviewLog>, DatabaseError> {
+        self.get_reviews_by(ReviewLogKey::card_id, card_id, |review| {
+            review.card_id == card_id
+        })
+    }
+
+    /// Get the reviews of one study day, oldest first
+    pub fn get_reviews_on(&self, day: NaiveDate) -> Result<Vec<ReviewLog>, DatabaseError> {
+        let day = day.to_string();
+        self.get_reviews_by(ReviewLogKey::day, &day, |review| review.day == day)
+    }
+
+    /// Reviews whose secondary key starts with `prefix`, keeping those that match exactly
+    fn get_reviews_by(
+        &self,
+        key: ReviewLogKey,
+        prefix: &str,
+        matches: impl Fn(&ReviewLog) -> bool,
+    ) -> Result<Vec<ReviewLog>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let reviews: Result<Vec<ReviewLog>, _> = r
+            .scan()
+            .secondary(key)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .start_with(prefix.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?
+            .collect();
+        let mut reviews = reviews.map_err(|e: native_db::db_type::Error| DatabaseError::QueryError(e.to_string()))?;
+
+        reviews.retain(|review| matches(review));
+        reviews.sort_by_key(|review| review.reviewed_at);
+
+        Ok(reviews)
+    }
+
     /// Save or update user settings
     pub fn save_settings(&self, key: &str, value: &str) -> Result<(), DatabaseError> {
         let rw = self
@@ -328,6 +845,115 @@ impl DatabaseService {
 
         Ok(cached.map(|c| c.response))
     }
+
+    /// Save the user's correction of a word's explanation, replacing any earlier one
+    pub fn save_edited_explanation(
+        &self,
+        base_form: &str,
+        explanation: WordExplanation,
+    ) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let edited = EditedExplanation {
+            base_form: base_form.to_string(),
+            explanation,
+            updated_at: Utc::now(),
+        };
+
+        rw.upsert(edited)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the user's correction of a word's explanation
+    pub fn get_edited_explanation(
+        &self,
+        base_form: &str,
+    ) -> Result<Option<WordExplanation>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let edited: Option<EditedExplanation> = r
+            .get()
+            .primary(base_form.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(edited.map(|e| e.explanation))
+    }
+
+    /// Drop the user's correction of a word's explanation, if any
+    pub fn remove_edited_explanation(&self, base_form: &str) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let edited: Option<EditedExplanation> = rw
+            .get()
+            .primary(base_form.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        if let Some(edited) = edited {
+            rw.remove(edited)
+                .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        }
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Add one request and its token count to the usage of a day
+    pub fn record_llm_usage(&self, date: &str, tokens: u64) -> Result<(), DatabaseError> {
+        let rw = self
+            .db
+            .rw_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let existing: Option<LLMUsage> = rw
+            .get()
+            .primary(date.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let mut usage = existing.unwrap_or_else(|| LLMUsage {
+            date: date.to_string(),
+            requests: 0,
+            total_tokens: 0,
+            updated_at: Utc::now(),
+        });
+        usage.requests += 1;
+        usage.total_tokens += tokens;
+        usage.updated_at = Utc::now();
+
+        rw.upsert(usage)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rw.commit()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Get the LLM usage recorded for a day
+    pub fn get_llm_usage(&self, date: &str) -> Result<Option<LLMUsage>, DatabaseError> {
+        let r = self
+            .db
+            .r_transaction()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        let usage = r
+            .get()
+            .primary(date.to_string())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        Ok(usage)
+    }
 }
 
 /// Database service errors
@@ -349,3 +975,153 @@ impl std::fmt::Display for DatabaseError {
 }
 
 impl std::error::Error for DatabaseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::legacy::{CardTypeV1, ExampleSentenceV1, SRSDataV1, VocabularyCardV1};
+    use crate::models::{Language, VocabularyCard};
+    use chrono::{DateTime, FixedOffset};
+
+    #[test]
+    fn cards_from_before_languages_are_migrated_as_english() {
+        let database = DatabaseService::new_in_memory().unwrap();
+        let now = Utc::now();
+        let rw = database.db.rw_transaction().unwrap();
+        rw.insert(FlashCardV1 {
+            id: "card-1".to_string(),
+            deck_id: "deck-1".to_string(),
+            card_type: CardTypeV1::Vocabulary(VocabularyCardV1 {
+                kanji: "猫".to_string(),
+                hiragana: "ねこ".to_string(),
+                romaji: "neko".to_string(),
+                meaning: "cat".to_string(),
+                wrong_answers: Vec::new(),
+                example_sentences: vec![ExampleSentenceV1 {
+                    japanese: "猫がいます。".to_string(),
+                    english: "There is a cat.".to_string(),
+                }],
+                jlpt_level: crate::models::JLPTLevel::N5,
+            }),
+            srs_data: SRSDataV1 {
+                ease_factor: 2.5,
+                interval: 0,
+                repetitions: 0,
+                next_review: now,
+                is_new: true,
+            },
+            created_at: now,
+            updated_at: now,
+        })
+        .unwrap();
+        rw.commit().unwrap();
+
+        database.migrate().unwrap();
+
+        let cards = database.get_deck_cards("deck-1").unwrap();
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].card_type.language(), Language::English);
+        let example = &cards[0].card_type.example_sentences()[0];
+        assert_eq!(example.translation, "There is a cat.");
+        assert_eq!(example.language, Language::English);
+        assert!(cards[0].srs_data.fsrs.is_none());
+    }
+
+    #[test]
+    fn deck_cards_exclude_decks_sharing_an_id_prefix() {
+        let database = DatabaseService::new_in_memory().unwrap();
+        for (id, deck_id) in [("card-1", "deck-x"), ("card-2", "deck-x-2")] {
+            let card = CardType::Vocabulary(VocabularyCard {
+                kanji: id.to_string(),
+                hiragana: String::new(),
+                romaji: String::new(),
+                meaning: String::new(),
+                wrong_answers: Vec::new(),
+                example_sentences: Vec::new(),
+                jlpt_level: crate::models::JLPTLevel::N5,
+                language: Language::English,
+            });
+            database
+                .save_card(id.to_string(), deck_id.to_string(), card)
+                .unwrap();
+        }
+
+        let cards = database.get_deck_cards("deck-x").unwrap();
+        let ids: Vec<&str> = cards.iter().map(|card| card.id.as_str()).collect();
+        assert_eq!(ids, ["card-1"]);
+    }
+
+    #[test]
+    fn migrated_reviews_are_found_by_card_and_day() {
+        let database = DatabaseService::new_in_memory().unwrap();
+        let at = DateTime::parse_from_rfc3339("2026-03-11T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let rw = database.db.rw_transaction().unwrap();
+        for (id, card_id) in [("review-1", "card-1"), ("review-2", "card-10")] {
+            rw.insert(ReviewLogV1 {
+                id: id.to_string(),
+                card_id: card_id.to_string(),
+                quality: 4,
+                reviewed_at: at,
+            })
+            .unwrap();
+        }
+        rw.commit().unwrap();
+
+        database.migrate().unwrap();
+
+        // "card-10" shares the prefix but is another card
+        let reviews = database.get_card_reviews("card-1").unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert!(reviews[0].rescheduled);
+        let day = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        assert_eq!(database.get_reviews_on(day).unwrap().len(), 2);
+        assert!(
+            database
+                .get_reviews_on(day.succ_opt().unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn migrated_late_night_reviews_move_to_their_study_day() {
+        let database = DatabaseService::new_in_memory().unwrap();
+        // 23:30 on the 10th at UTC-05:00
+        let at = DateTime::parse_from_rfc3339("2026-03-11T04:30:00Z")
+            .unwrap()
+            .to_utc();
+        let rw = database.db.rw_transaction().unwrap();
+        rw.insert(ReviewLogV1 {
+            id: "review-1".to_string(),
+            card_id: "card-1".to_string(),
+            quality: 4,
+            reviewed_at: at,
+        })
+        .unwrap();
+        rw.commit().unwrap();
+
+        database.migrate().unwrap();
+        let rollover = DayRollover {
+            starts_at: 4,
+            utc_offset: FixedOffset::west_opt(5 * 3600),
+        };
+        database.stamp_migrated_review_days(&rollover).unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        assert_eq!(database.get_reviews_on(day).unwrap().len(), 1);
+        assert!(
+            database
+                .get_reviews_on(day.succ_opt().unwrap())
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            database
+                .load_settings(constants::settings::RESTAMP_REVIEW_DAYS)
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Local dictionary lookups
+//!
+//! Loads an optional JSON word list (for example converted from JMdict) with
+//! readings, English glosses, parts of speech and JLPT levels. Features that
+//! use it fall back to LLM data when no dictionary is installed.
+
+use crate::models::JLPTLevel;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Words ignored when comparing glosses
+const STOP_WORDS: &[&str] = &[
+    "a",
+    "an",
+    "the",
+    "to",
+    "of",
+    "in",
+    "on",
+    "at",
+    "for",
+    "with",
+    "by",
+    "be",
+    "is",
+    "and",
+    "or",
+    "something",
+    "someone",
+    "one",
+    "etc",
+];
+
+/// A single dictionary word
+#[derive(Debug, Clone, Deserialize)]
+pub struct DictionaryEntry {
+    pub word: String,
+    #[serde(default)]
+    pub readings: Vec<String>,
+    #[serde(default)]
+    pub glosses: Vec<String>,
+    /// Part-of-speech tags, e.g. `v1` or `n`
+    #[serde(default)]
+    pub pos: Vec<String>,
+    #[serde(default)]
+    pub jlpt: Option<JLPTLevel>,
+}
+
+/// In-memory dictionary indexed by written form
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    entries: Vec<DictionaryEntry>,
+    by_word: HashMap<String, usize>,
+}
+
+impl Dictionary {
+    /// Load a dictionary from a JSON array of entries
+    pub fn load(path: &Path) -> Result<Self, DictionaryError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| DictionaryError::IoError(format!("{}: {}", path.display(), e)))?;
+        let entries: Vec<DictionaryEntry> =
+            serde_json::from_str(&json).map_err(|e| DictionaryError::ParseError(e.to_string()))?;
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Build a dictionary from entries; the first entry for a word wins lookups
+    pub fn from_entries(entries: Vec<DictionaryEntry>) -> Self {
+        let mut by_word = HashMap::new();
+        for (index, entry) in entries.iter().enumerate() {
+            by_word.entry(entry.word.clone()).or_insert(index);
+        }
+        Self { entries, by_word }
+    }
+
+    /// Find the entry for a written form
+    pub fn lookup(&self, word: &str) -> Option<&DictionaryEntry> {
+        self.by_word.get(word).map(|&index| &self.entries[index])
+    }
+
+    /// All entries, in file order
+    pub fn entries(&self) -> &[DictionaryEntry] {
+        &self.entries
+    }
+}
+
+/// Lowercase content words of an English gloss, without stop words
+pub fn content_words(gloss: &str) -> HashSet<String> {
+    gloss
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| !word.is_empty() && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Check if two glosses share a content word
+pub fn glosses_overlap(a: &str, b: &str) -> bool {
+    !content_words(a).is_disjoint(&content_words(b))
+}
+
+/// Dictionary loading errors
+#[derive(Debug, Clone)]
+pub enum DictionaryError {
+    IoError(String),
+    ParseError(String),
+}
+
+impl std::fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DictionaryError::IoError(msg) => write!(f, "Dictionary read error: {}", msg),
+            DictionaryError::ParseError(msg) => write!(f, "Dictionary parse error: {}", msg),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Offline wrong answers for multiple-choice cards
+//!
+//! Picks plausible wrong meanings from the user's other cards and the local
+//! dictionary, so cards can be quizzed without an LLM. Candidates with the
+//! same part of speech and a nearby JLPT level are preferred, and candidates
+//! whose glosses share a content word with the correct answer are skipped as
+//! likely synonyms. Wrong answers are always in the card's own language, so
+//! the English dictionary glosses only serve English cards.
+
+use crate::models::{CardType, JLPTLevel, Language};
+use crate::services::dictionary::{Dictionary, glosses_overlap};
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Number of wrong answers shown with each card
+pub const WRONG_ANSWER_COUNT: usize = 3;
+
+/// A possible wrong answer
+#[derive(Debug, Clone)]
+struct Candidate {
+    word: String,
+    meaning: String,
+    pos: Option<&'static str>,
+    jlpt: JLPTLevel,
+    grammar: bool,
+    language: Language,
+}
+
+/// Wrong answer candidates gathered from cards and the dictionary
+#[derive(Debug, Clone, Default)]
+pub struct DistractorPool<'a> {
+    candidates: Vec<Candidate>,
+    dictionary: Option<&'a Dictionary>,
+}
+
+impl<'a> DistractorPool<'a> {
+    /// Collect candidates from existing cards and, if loaded, the dictionary
+    pub fn new<'c>(
+        cards: impl IntoIterator<Item = &'c CardType>,
+        dictionary: Option<&'a Dictionary>,
+    ) -> Self {
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+
+        for card in cards {
+            let (word, grammar) = match card {
+                CardType::Vocabulary(vocab) => (&vocab.kanji, false),
+                CardType::Grammar(grammar) => (&grammar.pattern, true),
+            };
+            if !seen.insert(word.clone()) {
+                continue;
+            }
+            candidates.push(Candidate {
+                word: word.clone(),
+                meaning: card.correct_answer().to_string(),
+                pos: if grammar {
+                    None
+                } else {
+                    dictionary.and_then(|dictionary| pos_class_of(dictionary, word))
+                },
+                jlpt: card.jlpt_level(),
+                grammar,
+                language: card.language(),
+            });
+        }
+
+        for entry in dictionary.map(Dictionary::entries).unwrap_or_default() {
+            let Some(gloss) = entry.glosses.first() else {
+                continue;
+            };
+            if !seen.insert(entry.word.clone()) {
+                continue;
+            }
+            candidates.push(Candidate {
+                word: entry.word.clone(),
+                meaning: gloss.clone(),
+                pos: entry.pos.first().map(|tag| pos_class(tag)),
+                jlpt: entry.jlpt.unwrap_or(JLPTLevel::Unknown),
+                grammar: false,
+                language: Language::English,
+            });
+        }
+
+        Self {
+            candidates,
+            dictionary,
+        }
+    }
+
+    /// Pick up to `count` wrong answers for a card
+    ///
+    /// Candidates are ranked by matching part of speech, then by JLPT level
+    /// distance; ties are broken by a hash of the card's word so each card
+    /// gets a stable but different selection.
+    pub fn distractors_for(&self, card: &CardType, count: usize) -> Vec<String> {
+        let (word, grammar) = match card {
+            CardType::Vocabulary(vocab) => (vocab.kanji.as_str(), false),
+            CardType::Grammar(grammar) => (grammar.pattern.as_str(), true),
+        };
+        let correct = card.correct_answer();
+        let level = card.jlpt_level();
+        let language = card.language();
+        let entry = self
+            .dictionary
+            .filter(|_| language == Language::English)
+            .and_then(|dictionary| dictionary.lookup(word));
+        let pos = entry
+            .and_then(|entry| entry.pos.first())
+            .map(|tag| pos_class(tag));
+        // Dictionary glosses of the word catch synonyms the card's meaning misses
+        let glosses: Vec<&str> = entry
+            .map(|entry| entry.glosses.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+
+        let mut ranked: Vec<(&Candidate, (bool, u8, u64))> = self
+            .candidates
+            .iter()
+            .filter(|candidate| {
+                candidate.grammar == grammar
+                    && candidate.language == language
+                    && candidate.word != word
+            })
+            .map(|candidate| {
+                let pos_matches = pos.is_none() || candidate.pos == pos;
+                let key = (
+                    !pos_matches,
+                    level_distance(level, candidate.jlpt),
+                    tie_breaker(word, &candidate.word),
+                );
+                (candidate, key)
+            })
+            .collect();
+        ranked.sort_by_key(|(_, key)| *key);
+
+        let mut chosen: Vec<String> = Vec::new();
+        for (candidate, _) in ranked {
+            if chosen.len() >= count {
+                break;
+            }
+            let meaning = candidate.meaning.as_str();
+            let overlaps = glosses_overlap(meaning, correct)
+                || glosses.iter().any(|gloss| glosses_overlap(meaning, gloss))
+                || chosen.iter().any(|other| glosses_overlap(meaning, other));
+            if !overlaps {
+                chosen.push(meaning.to_string());
+            }
+        }
+        chosen
+    }
+
+    /// Add wrong answers to a card that has fewer than `WRONG_ANSWER_COUNT`
+    ///
+    /// Returns `true` if the card was changed.
+    pub fn top_up(&self, card: &mut CardType) -> bool {
+        let missing = WRONG_ANSWER_COUNT.saturating_sub(card.wrong_answers().len());
+        if missing == 0 {
+            return false;
+        }
+
+        let existing: Vec<String> = card.wrong_answers().to_vec();
+        let extra: Vec<String> = self
+            .distractors_for(card, WRONG_ANSWER_COUNT + existing.len())
+            .into_iter()
+            .filter(|meaning| !existing.iter().any(|other| glosses_overlap(meaning, other)))
+            .take(missing)
+            .collect();
+        if extra.is_empty() {
+            return false;
+        }
+
+        match card {
+            CardType::Vocabulary(vocab) => vocab.wrong_answers.extend(extra),
+            CardType::Grammar(grammar) => grammar.wrong_answers.extend(extra),
+        }
+        true
+    }
+}
+
+/// Coarse part of speech of a JMdict-style tag, e.g. `v5k` and `v1` are both verbs
+fn pos_class(tag: &str) -> &'static str {
+    match tag {
+        t if t.starts_with("adj") => "adjective",
+        t if t.starts_with("adv") => "adverb",
+        t if t.starts_with('v') => "verb",
+        t if t.starts_with('n') => "noun",
+        t if t.starts_with("prt") => "particle",
+        _ => "other",
+    }
+}
+
+fn pos_class_of(dictionary: &Dictionary, word: &str) -> Option<&'static str> {
+    dictionary
+        .lookup(word)
+        .and_then(|entry| entry.pos.first())
+        .map(|tag| pos_class(tag))
+}
+
+/// Distance between JLPT levels; unknown levels count as close to anything
+fn level_distance(a: JLPTLevel, b: JLPTLevel) -> u8 {
+    match (level_number(a), level_number(b)) {
+        (Some(a), Some(b)) => a.abs_diff(b),
+        _ => 1,
+    }
+}
+
+fn level_number(level: JLPTLevel) -> Option<u8> {
+    match level {
+        JLPTLevel::N5 => Some(5),
+        JLPTLevel::N4 => Some(4),
+        JLPTLevel::N3 => Some(3),
+        JLPTLevel::N2 => Some(2),
+        JLPTLevel::N1 => Some(1),
+        JLPTLevel::Unknown => None,
+    }
+}
+
+fn tie_breaker(word: &str, candidate: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (word, candidate).hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::VocabularyCard;
+    use crate::services::dictionary::DictionaryEntry;
+
+    fn entry(word: &str, gloss: &str, pos: &str, jlpt: JLPTLevel) -> DictionaryEntry {
+        DictionaryEntry {
+            word: word.to_string(),
+            readings: Vec::new(),
+            glosses: vec![gloss.to_string()],
+            pos: vec![pos.to_string()],
+            jlpt: Some(jlpt),
+        }
+    }
+
+    fn vocabulary(kanji: &str, meaning: &str, level: JLPTLevel) -> CardType {
+        CardType::Vocabulary(VocabularyCard {
+            kanji: kanji.to_string(),
+            hiragana: String::new(),
+            romaji: String::new(),
+            meaning: meaning.to_string(),
+            wrong_answers: Vec::new(),
+            example_sentences: Vec::new(),
+            jlpt_level: level,
+            language: Language::English,
+        })
+    }
+
+    fn dictionary() -> Dictionary {
+        Dictionary::from_entries(vec![
+            entry("食べる", "to eat", "v1", JLPTLevel::N5),
+            entry("食う", "to eat (coarse)", "v5u", JLPTLevel::N3),
+            entry("飲む", "to drink", "v5m", JLPTLevel::N5),
+            entry("書く", "to write", "v5k", JLPTLevel::N5),
+            entry("読む", "to read", "v5m", JLPTLevel::N5),
+            entry("見る", "to see", "v1", JLPTLevel::N5),
+            entry("概念", "concept", "n", JLPTLevel::N1),
+            entry("水", "water", "n", JLPTLevel::N5),
+            entry("実施", "enforcement", "vs", JLPTLevel::N1),
+        ])
+    }
+
+    #[test]
+    fn distractors_match_pos_and_level_and_skip_synonyms() {
+        let dictionary = dictionary();
+        let pool = DistractorPool::new(&[], Some(&dictionary));
+
+        let wrong = pool.distractors_for(&vocabulary("食べる", "to eat", JLPTLevel::N5), 3);
+
+        assert_eq!(wrong.len(), 3);
+        for meaning in &wrong {
+            assert!(
+                ["to drink", "to write", "to read", "to see"].contains(&meaning.as_str()),
+                "unexpected distractor {}",
+                meaning
+            );
+        }
+    }
+
+    #[test]
+    fn cards_are_topped_up_from_other_cards_without_a_dictionary() {
+        let cards = [
+            vocabulary("猫", "cat", JLPTLevel::N5),
+            vocabulary("犬", "dog", JLPTLevel::N5),
+            vocabulary("子猫", "small cat", JLPTLevel::N5),
+            vocabulary("鳥", "bird", JLPTLevel::N4),
+        ];
+        let pool = DistractorPool::new(&cards, None);
+        let mut card = cards[0].clone();
+
+        assert!(pool.top_up(&mut card));
+        assert!(!pool.top_up(&mut card));
+
+        let mut wrong = card.wrong_answers().to_vec();
+        wrong.sort();
+        assert_eq!(wrong, ["bird", "dog"]);
+    }
+
+    #[test]
+    fn wrong_answers_stay_in_the_card_language() {
+        let dictionary = dictionary();
+        let spanish = |kanji: &str, meaning: &str| {
+            let mut card = vocabulary(kanji, meaning, JLPTLevel::N5);
+            if let CardType::Vocabulary(vocab) = &mut card {
+                vocab.language = Language::Spanish;
+            }
+            card
+        };
+        let cards = [
+            spanish("猫", "gato"),
+            spanish("犬", "perro"),
+            vocabulary("鳥", "bird", JLPTLevel::N5),
+        ];
+        let pool = DistractorPool::new(&cards, Some(&dictionary));
+
+        assert_eq!(pool.distractors_for(&cards[0], 3), ["perro"]);
+    }
+}
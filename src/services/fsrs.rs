@@ -0,0 +1,364 @@
+//! FSRS (Free Spaced Repetition Scheduler)
+//!
+//! An alternative to SM-2 that models a card's memory by its stability, the
+//! days until the recall probability falls to 90%, and its difficulty from 1
+//! to 10. The next review is due when the predicted recall probability falls
+//! to the desired retention. Implements the FSRS-4.5 formulas; the 17 weights
+//! default to the published values and can be fitted to the user's review
+//! history with [`optimize`].
+
+use crate::constants::srs::{FSRS_MIN_REVIEWS, PASSING_QUALITY};
+use crate::models::flashcard::{FsrsState, ReviewLog};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Shape of the forgetting curve
+const DECAY: f32 = -0.5;
+
+/// Chosen so that the recall probability is 90% after `stability` days
+const FACTOR: f32 = 19.0 / 81.0;
+
+/// Stability never drops below this many days
+const MIN_STABILITY: f32 = 0.01;
+
+/// Published FSRS-4.5 weights
+const DEFAULT_WEIGHTS: [f32; 17] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461, 2.1072,
+    0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+
+/// Range each weight is kept in while fitting
+const WEIGHT_BOUNDS: [(f32, f32); 17] = [
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (0.1, 100.0),
+    (1.0, 10.0),
+    (0.1, 5.0),
+    (0.1, 5.0),
+    (0.0, 0.5),
+    (0.0, 3.0),
+    (0.1, 0.8),
+    (0.01, 2.5),
+    (0.5, 5.0),
+    (0.01, 0.2),
+    (0.01, 0.9),
+    (0.01, 2.0),
+    (0.0, 1.0),
+    (1.0, 6.0),
+];
+
+/// Passes over all weights while fitting
+const OPTIMIZER_PASSES: usize = 12;
+
+/// How well a card was recalled, as FSRS grades answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Rating {
+    pub const ALL: [Rating; 4] = [Rating::Again, Rating::Hard, Rating::Good, Rating::Easy];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rating::Again => "Again",
+            Rating::Hard => "Hard",
+            Rating::Good => "Good",
+            Rating::Easy => "Easy",
+        }
+    }
+
+    /// SM-2 quality grade the rating is scheduled with
+    pub fn quality(self) -> u8 {
+        match self {
+            Rating::Again => 1,
+            Rating::Hard => 3,
+            Rating::Good => 4,
+            Rating::Easy => 5,
+        }
+    }
+
+    /// Rating of a review graded on the SM-2 quality scale
+    pub fn from_quality(quality: u8) -> Self {
+        match quality {
+            q if q < PASSING_QUALITY => Rating::Again,
+            3 => Rating::Hard,
+            4 => Rating::Good,
+            _ => Rating::Easy,
+        }
+    }
+
+    fn grade(self) -> f32 {
+        match self {
+            Rating::Again => 1.0,
+            Rating::Hard => 2.0,
+            Rating::Good => 3.0,
+            Rating::Easy => 4.0,
+        }
+    }
+}
+
+/// Weights of the FSRS model
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FsrsParameters {
+    pub weights: [f32; 17],
+}
+
+impl Default for FsrsParameters {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_WEIGHTS,
+        }
+    }
+}
+
+impl FsrsParameters {
+    /// Memory state after a review rated `rating` at `now`
+    pub fn next_state(
+        &self,
+        state: Option<&FsrsState>,
+        rating: Rating,
+        now: DateTime<Utc>,
+    ) -> FsrsState {
+        let Some(state) = state else {
+            return FsrsState {
+                stability: self.weights[rating as usize].max(MIN_STABILITY),
+                difficulty: self.initial_difficulty(rating),
+                last_review: now,
+            };
+        };
+
+        let recall = retrievability(state, now);
+        let stability = match rating {
+            Rating::Again => self.forget_stability(state, recall),
+            _ => self.recall_stability(state, recall, rating),
+        };
+        FsrsState {
+            stability: stability.max(MIN_STABILITY),
+            difficulty: self.next_difficulty(state.difficulty, rating),
+            last_review: now,
+        }
+    }
+
+    fn initial_difficulty(&self, rating: Rating) -> f32 {
+        (self.weights[4] - (rating.grade() - 3.0) * self.weights[5]).clamp(1.0, 10.0)
+    }
+
+    fn next_difficulty(&self, difficulty: f32, rating: Rating) -> f32 {
+        let w = &self.weights;
+        let changed = difficulty - w[6] * (rating.grade() - 3.0);
+        // Revert towards the initial difficulty of a Good answer
+        (w[7] * w[4] + (1.0 - w[7]) * changed).clamp(1.0, 10.0)
+    }
+
+    fn recall_stability(&self, state: &FsrsState, recall: f32, rating: Rating) -> f32 {
+        let w = &self.weights;
+        let modifier = match rating {
+            Rating::Hard => w[15],
+            Rating::Easy => w[16],
+            _ => 1.0,
+        };
+        state.stability
+            * (w[8].exp()
+                * (11.0 - state.difficulty)
+                * state.stability.powf(-w[9])
+                * ((w[10] * (1.0 - recall)).exp() - 1.0)
+                * modifier
+                + 1.0)
+    }
+
+    fn forget_stability(&self, state: &FsrsState, recall: f32) -> f32 {
+        let w = &self.weights;
+        let stability = w[11]
+            * state.difficulty.powf(-w[12])
+            * ((state.stability + 1.0).powf(w[13]) - 1.0)
+            * (w[14] * (1.0 - recall)).exp();
+        // Forgetting never makes a card easier to remember
+        stability.min(state.stability)
+    }
+}
+
+/// Predicted probability of recalling a card at `now`
+pub fn retrievability(state: &FsrsState, now: DateTime<Utc>) -> f32 {
+    let elapsed_days = (now - state.last_review).num_seconds().max(0) as f32 / 86_400.0;
+    (1.0 + FACTOR * elapsed_days / state.stability).powf(DECAY)
+}
+
+/// Whole days until the recall probability falls to `retention`, at least one
+pub fn interval_days(stability: f32, retention: f32) -> u32 {
+    let days = stability / FACTOR * (retention.powf(1.0 / DECAY) - 1.0);
+    days.round().max(1.0) as u32
+}
+
+/// Parameters fitted to the review history
+#[derive(Debug, Clone)]
+pub struct Optimization {
+    pub parameters: FsrsParameters,
+    /// Reviews the parameters were fitted to
+    pub reviews: usize,
+    /// Mean log loss of the predicted recall, before and after fitting
+    pub loss_before: f32,
+    pub loss_after: f32,
+}
+
+/// Fit the weights to the review history, starting from `start`
+///
+/// Only the first review of a card on each study day is used, since FSRS
+/// models memory between days, and reviews that did not reschedule the card
+/// are left out. Fitting is a coordinate descent on the log loss of
+/// the predicted recall, halving a weight's step when neither direction
+/// helps. Returns `None` with fewer than `FSRS_MIN_REVIEWS` usable reviews.
+pub fn optimize(history: &[ReviewLog], start: &FsrsParameters) -> Option<Optimization> {
+    let cards = card_histories(history);
+    let reviews: usize = cards
+        .iter()
+        .map(|reviews| reviews.len().saturating_sub(1))
+        .sum();
+    if reviews < FSRS_MIN_REVIEWS {
+        return None;
+    }
+
+    let mut parameters = *start;
+    let loss_before = log_loss(&parameters, &cards);
+    let mut loss = loss_before;
+    let mut steps = WEIGHT_BOUNDS.map(|(low, high)| (high - low) / 20.0);
+
+    for _ in 0..OPTIMIZER_PASSES {
+        for (i, &(low, high)) in WEIGHT_BOUNDS.iter().enumerate() {
+            let current = parameters.weights[i];
+            let improved = [current + steps[i], current - steps[i]]
+                .into_iter()
+                .find_map(|candidate| {
+                    let mut trial = parameters;
+                    trial.weights[i] = candidate.clamp(low, high);
+                    let trial_loss = log_loss(&trial, &cards);
+                    (trial_loss < loss).then_some((trial, trial_loss))
+                });
+            match improved {
+                Some((trial, trial_loss)) => {
+                    parameters = trial;
+                    loss = trial_loss;
+                }
+                None => steps[i] /= 2.0,
+            }
+        }
+    }
+
+    Some(Optimization {
+        parameters,
+        reviews,
+        loss_before,
+        loss_after: loss,
+    })
+}
+
+/// Each card's ratings in order, keeping the first rescheduling review per study day
+fn card_histories(history: &[ReviewLog]) -> Vec<Vec<(DateTime<Utc>, Rating)>> {
+    let mut cards: BTreeMap<&str, Vec<&ReviewLog>> = BTreeMap::new();
+    for review in history.iter().filter(|review| review.rescheduled) {
+        cards.entry(&review.card_id).or_default().push(review);
+    }
+
+    cards
+        .into_values()
+        .map(|mut reviews| {
+            reviews.sort_by_key(|review| review.reviewed_at);
+            reviews.dedup_by_key(|review| &review.day);
+            reviews
+                .into_iter()
+                .map(|review| (review.reviewed_at, Rating::from_quality(review.quality)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Mean log loss of the predicted recall over every review after a card's first
+fn log_loss(parameters: &FsrsParameters, cards: &[Vec<(DateTime<Utc>, Rating)>]) -> f32 {
+    let mut total = 0.0f64;
+    let mut count = 0usize;
+    for reviews in cards {
+        let mut state: Option<FsrsState> = None;
+        for &(reviewed_at, rating) in reviews {
+            if let Some(state) = &state {
+                let recall = f64::from(retrievability(state, reviewed_at)).clamp(1e-4, 1.0 - 1e-4);
+                total -= match rating {
+                    Rating::Again => (1.0 - recall).ln(),
+                    _ => recall.ln(),
+                };
+                count += 1;
+            }
+            state = Some(parameters.next_state(state.as_ref(), rating, reviewed_at));
+        }
+    }
+
+    if count == 0 {
+        0.0
+    } else {
+        (total / count as f64) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::flashcard::SchedulerKind;
+    use chrono::Duration;
+
+    #[test]
+    fn recall_grows_stability_and_lapses_shrink_it() {
+        let parameters = FsrsParameters::default();
+        let start = Utc::now();
+
+        let first = parameters.next_state(None, Rating::Good, start);
+        assert_eq!(first.stability, DEFAULT_WEIGHTS[2]);
+        assert_eq!(interval_days(first.stability, 0.9), 4);
+        assert_eq!(retrievability(&first, start), 1.0);
+
+        let due = start + Duration::days(4);
+        let recall = retrievability(&first, due);
+        assert!(recall < 0.9 && recall > 0.85, "recall {}", recall);
+
+        let recalled = parameters.next_state(Some(&first), Rating::Good, due);
+        let forgotten = parameters.next_state(Some(&first), Rating::Again, due);
+        assert!(recalled.stability > first.stability);
+        assert!(forgotten.stability < first.stability);
+        assert!(forgotten.difficulty > recalled.difficulty);
+    }
+
+    #[test]
+    fn optimizer_fits_a_history_of_easy_recall() {
+        let start = Utc::now() - Duration::days(200);
+        let review = |card: usize, day: i64| ReviewLog {
+            id: format!("{}-{}", card, day),
+            card_id: card.to_string(),
+            day: (start + Duration::days(day)).date_naive().to_string(),
+            quality: 4,
+            answer: None,
+            response_ms: None,
+            previous_interval: 0,
+            interval: 0,
+            ease_factor: 2.5,
+            scheduler: SchedulerKind::Fsrs,
+            rescheduled: true,
+            reviewed_at: start + Duration::days(day),
+        };
+
+        // Cards recalled after long gaps are easier than the defaults predict
+        let history: Vec<ReviewLog> = (0..40)
+            .flat_map(|card| [0, 20, 60, 150].map(|day| review(card, day)))
+            .collect();
+        let defaults = FsrsParameters::default();
+
+        let fitted = optimize(&history, &defaults).unwrap();
+        assert_eq!(fitted.reviews, 120);
+        assert!(fitted.loss_after < fitted.loss_before);
+        assert!(fitted.parameters.weights[2] > DEFAULT_WEIGHTS[2]);
+
+        assert!(optimize(&history[..40], &defaults).is_none());
+    }
+}
@@ -0,0 +1,312 @@
+//! Inputs for the AI-written learner report
+//!
+//! Collects what the app knows about the learner's progress (answers from the
+//! last practice session, the review log of the last days, cards whose ease
+//! has dropped and recent questions) into a plain-text summary that the LLM
+//! turns into a strengths and weaknesses report.
+
+use crate::constants;
+use crate::constants::srs::PASSING_QUALITY;
+use crate::models::{CardType, JLPTLevel};
+use crate::services::DatabaseService;
+use crate::services::database::DatabaseError;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+/// Cards below this ease factor count as struggling (new cards start at 2.5)
+const STRUGGLING_EASE: f32 = 2.3;
+
+/// Result of answering one card in a practice session
+#[derive(Debug, Clone)]
+pub struct ReviewOutcome {
+    /// Word or grammar pattern on the front of the card
+    pub front: String,
+    pub meaning: String,
+    pub jlpt_level: JLPTLevel,
+    pub correct: bool,
+}
+
+impl ReviewOutcome {
+    pub fn new(card: &CardType, correct: bool) -> Self {
+        Self {
+            front: card.front().to_string(),
+            meaning: card.correct_answer().to_string(),
+            jlpt_level: card.jlpt_level(),
+            correct,
+        }
+    }
+}
+
+/// Everything a learner report is written from
+#[derive(Debug, Clone, Default)]
+pub struct ReportInputs {
+    pub reviews: Vec<ReviewOutcome>,
+    /// Correct and total answers in the review log of the last days
+    pub recent_accuracy: (usize, usize),
+    /// Mean time taken per answer in the last days
+    pub average_response_secs: Option<f32>,
+    /// Card fronts with a wrong answer picked for them, and how often, most frequent first
+    pub confusions: Vec<(String, String, usize)>,
+    /// Reviewed cards with a lowered ease factor, hardest first
+    pub struggling_cards: Vec<(String, f32)>,
+    /// Most recent questions asked in Learning mode, newest first
+    pub recent_questions: Vec<String>,
+}
+
+impl ReportInputs {
+    /// Combine the outcomes of a session with the stored cards and questions
+    pub fn gather(
+        database: &DatabaseService,
+        reviews: Vec<ReviewOutcome>,
+    ) -> Result<Self, DatabaseError> {
+        let cards = database.get_all_cards()?;
+        let cards_by_id: HashMap<&str, &CardType> = cards
+            .iter()
+            .map(|card| (card.id.as_str(), &card.card_type))
+            .collect();
+
+        let since = Utc::now() - Duration::days(constants::llm::REPORT_HISTORY_DAYS);
+        let history: Vec<_> = database
+            .get_reviews()?
+            .into_iter()
+            .filter(|review| review.reviewed_at >= since)
+            .collect();
+        let correct = history
+            .iter()
+            .filter(|review| review.quality >= PASSING_QUALITY)
+            .count();
+        let response_times: Vec<u64> = history
+            .iter()
+            .filter_map(|review| review.response_ms)
+            .collect();
+        let average_response_secs = (!response_times.is_empty()).then(|| {
+            response_times.iter().sum::<u64>() as f32 / response_times.len() as f32 / 1000.0
+        });
+
+        let mut confusions: Vec<(String, String, usize)> = Vec::new();
+        for review in history
+            .iter()
+            .filter(|review| review.quality < PASSING_QUALITY)
+        {
+            let (Some(card), Some(answer)) =
+                (cards_by_id.get(review.card_id.as_str()), &review.answer)
+            else {
+                continue;
+            };
+            // Only multiple-choice picks are confusions, not written sentences
+            if !card.wrong_answers().contains(answer) {
+                continue;
+            }
+            let front = card.front();
+            match confusions
+                .iter_mut()
+                .find(|(seen, chosen, _)| seen == front && chosen == answer)
+            {
+                Some((_, _, count)) => *count += 1,
+                None => confusions.push((front.to_string(), answer.clone(), 1)),
+            }
+        }
+        confusions.sort_by_key(|(_, _, count)| std::cmp::Reverse(*count));
+        confusions.truncate(constants::llm::REPORT_SAMPLE_SIZE);
+
+        let mut struggling_cards: Vec<(String, f32)> = cards
+            .iter()
+            .filter(|card| !card.srs_data.is_new && card.srs_data.ease_factor < STRUGGLING_EASE)
+            .map(|card| {
+                (
+                    card.card_type.front().to_string(),
+                    card.srs_data.ease_factor,
+                )
+            })
+            .collect();
+        struggling_cards.sort_by(|a, b| a.1.total_cmp(&b.1));
+        struggling_cards.truncate(constants::llm::REPORT_SAMPLE_SIZE);
+
+        let recent_questions = database
+            .get_recent_questions(constants::llm::REPORT_SAMPLE_SIZE)?
+            .into_iter()
+            .map(|turn| turn.question)
+            .collect();
+
+        Ok(Self {
+            reviews,
+            recent_accuracy: (correct, history.len()),
+            average_response_secs,
+            confusions,
+            struggling_cards,
+            recent_questions,
+        })
+    }
+
+    /// Check if there is nothing to report on
+    pub fn is_empty(&self) -> bool {
+        self.reviews.is_empty()
+            && self.recent_accuracy.1 == 0
+            && self.struggling_cards.is_empty()
+            && self.recent_questions.is_empty()
+    }
+
+    /// Missed cards with the number of misses, most missed first
+    pub fn missed_cards(&self) -> Vec<(&ReviewOutcome, usize)> {
+        let mut missed: Vec<(&ReviewOutcome, usize)> = Vec::new();
+        for review in self.reviews.iter().filter(|review| !review.correct) {
+            match missed
+                .iter_mut()
+                .find(|(seen, _)| seen.front == review.front)
+            {
+                Some((_, count)) => *count += 1,
+                None => missed.push((review, 1)),
+            }
+        }
+        missed.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        missed.truncate(constants::llm::REPORT_SAMPLE_SIZE);
+        missed
+    }
+
+    /// Correct and total answers per JLPT level, weakest level first
+    pub fn level_accuracy(&self) -> Vec<(JLPTLevel, usize, usize)> {
+        let mut levels: Vec<(JLPTLevel, usize, usize)> = Vec::new();
+        for review in &self.reviews {
+            let index = match levels
+                .iter()
+                .position(|(level, _, _)| *level == review.jlpt_level)
+            {
+                Some(index) => index,
+                None => {
+                    levels.push((review.jlpt_level, 0, 0));
+                    levels.len() - 1
+                }
+            };
+            levels[index].1 += usize::from(review.correct);
+            levels[index].2 += 1;
+        }
+        // Compare correct/total ratios without floating point
+        levels.sort_by(|a, b| (a.1 * b.2).cmp(&(b.1 * a.2)));
+        levels
+    }
+
+    /// Plain-text summary used as the report prompt input
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+
+        if !self.reviews.is_empty() {
+            let correct = self.reviews.iter().filter(|review| review.correct).count();
+            lines.push(format!(
+                "Last practice session: {} of {} answers correct.",
+                correct,
+                self.reviews.len()
+            ));
+
+            lines.push("Accuracy by JLPT level:".to_string());
+            for (level, correct, total) in self.level_accuracy() {
+                lines.push(format!("- {}: {}/{}", level.as_str(), correct, total));
+            }
+
+            let missed = self.missed_cards();
+            if !missed.is_empty() {
+                lines.push("Missed cards:".to_string());
+                for (review, count) in missed {
+                    lines.push(format!(
+                        "- {} ({}, {}) missed {}x",
+                        review.front,
+                        review.meaning,
+                        review.jlpt_level.as_str(),
+                        count
+                    ));
+                }
+            }
+        }
+
+        let (correct, total) = self.recent_accuracy;
+        if total > 0 {
+            let mut line = format!(
+                "Last {} days: {} of {} answers correct.",
+                constants::llm::REPORT_HISTORY_DAYS,
+                correct,
+                total
+            );
+            if let Some(seconds) = self.average_response_secs {
+                line.push_str(&format!(" Average answer time {:.1}s.", seconds));
+            }
+            lines.push(line);
+        }
+
+        if !self.confusions.is_empty() {
+            lines.push("Wrong answers picked most often:".to_string());
+            for (front, answer, count) in &self.confusions {
+                lines.push(format!(
+                    "- {} mistaken for \"{}\" {}x",
+                    front, answer, count
+                ));
+            }
+        }
+
+        if !self.struggling_cards.is_empty() {
+            lines.push("Cards with the lowest ease factor:".to_string());
+            for (front, ease) in &self.struggling_cards {
+                lines.push(format!("- {} (ease {:.2})", front, ease));
+            }
+        }
+
+        if !self.recent_questions.is_empty() {
+            lines.push("Recent questions asked while reading:".to_string());
+            for question in &self.recent_questions {
+                lines.push(format!("- {}", question));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::flashcard::{ReviewLog, SchedulerKind};
+    use crate::models::{Language, VocabularyCard};
+
+    #[test]
+    fn only_multiple_choice_answers_count_as_confusions() {
+        let database = DatabaseService::new_in_memory().unwrap();
+        let card = CardType::Vocabulary(VocabularyCard {
+            kanji: "猫".to_string(),
+            hiragana: "ねこ".to_string(),
+            romaji: "neko".to_string(),
+            meaning: "cat".to_string(),
+            wrong_answers: vec!["dog".to_string()],
+            example_sentences: Vec::new(),
+            jlpt_level: JLPTLevel::N5,
+            language: Language::English,
+        });
+        database
+            .save_card("card-1".to_string(), "deck-1".to_string(), card)
+            .unwrap();
+        let now = Utc::now();
+        for (id, answer) in [("review-1", "dog"), ("review-2", "猫が好きだ。")] {
+            database
+                .record_review(ReviewLog {
+                    id: id.to_string(),
+                    card_id: "card-1".to_string(),
+                    day: now.date_naive().to_string(),
+                    quality: 1,
+                    answer: Some(answer.to_string()),
+                    response_ms: None,
+                    previous_interval: 0,
+                    interval: 0,
+                    ease_factor: 2.5,
+                    scheduler: SchedulerKind::Sm2,
+                    rescheduled: true,
+                    reviewed_at: now,
+                })
+                .unwrap();
+        }
+
+        let inputs = ReportInputs::gather(&database, Vec::new()).unwrap();
+
+        assert_eq!(
+            inputs.confusions,
+            [("猫".to_string(), "dog".to_string(), 1)]
+        );
+        assert_eq!(inputs.recent_accuracy, (0, 2));
+    }
+}
@@ -0,0 +1,211 @@
+//! Audit log of LLM calls
+//!
+//! Every provider call made by the service is stored with its prompt, raw
+//! response, latency, token usage and error, so a wrong answer can be traced
+//! back to the prompt that produced it. API keys are redacted before anything
+//! is stored, and only the newest `AUDIT_LOG_LIMIT` calls are kept.
+
+use super::mock::Fixture;
+use super::{ChatMessage, ChatRole, Completion, LLMError};
+use crate::models::deck::{LLMCallLog, LoggedMessage};
+use crate::services::DatabaseService;
+use chrono::Utc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Replaces secrets in logged text
+const REDACTED: &str = "[REDACTED]";
+
+/// Prefix of Google API keys, which are 39 characters long
+const GOOGLE_KEY_PREFIX: &str = "AIza";
+const GOOGLE_KEY_LENGTH: usize = 39;
+
+/// Sequence number of the calls logged by this process, keeping ids unique
+/// when calls finish in the same microsecond
+static NEXT_CALL: AtomicU64 = AtomicU64::new(0);
+
+/// Calls that could not be stored since the app started
+static UNRECORDED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// A finished provider call, as seen by the service
+pub(super) struct CallRecord<'a> {
+    pub provider: &'static str,
+    pub model: String,
+    pub fixture: Fixture<'a>,
+    pub system: Option<&'a str>,
+    pub messages: &'a [ChatMessage],
+    pub json_output: bool,
+    pub result: &'a Result<Completion, LLMError>,
+    pub latency: Duration,
+}
+
+/// Store a call in the audit log
+///
+/// Failures are reported on stderr and counted in [`unrecorded_calls`], so
+/// the developer view can tell the log is incomplete.
+pub(super) fn record(database: &DatabaseService, api_key: Option<&str>, call: CallRecord<'_>) {
+    let redact = |text: &str| redact(text, api_key);
+    let now = Utc::now();
+
+    let (response, total_tokens, error_kind, error) = match call.result {
+        Ok(completion) => {
+            let mut response = completion.text.clone();
+            for tool_call in &completion.tool_calls {
+                response.push_str(&format!("\n[call {} {}]", tool_call.name, tool_call.args));
+            }
+            (Some(redact(&response)), completion.total_tokens, None, None)
+        }
+        Err(e) => (
+            None,
+            0,
+            Some(e.variant_name().to_string()),
+            Some(redact(&e.to_string())),
+        ),
+    };
+
+    let entry = LLMCallLog {
+        id: format!(
+            "llm-{:020}-{:06}",
+            now.timestamp_micros(),
+            NEXT_CALL.fetch_add(1, Ordering::Relaxed)
+        ),
+        provider: call.provider.to_string(),
+        model: call.model,
+        kind: call.fixture.kind.to_string(),
+        word: call.fixture.word.map(redact),
+        system: call.system.map(redact),
+        messages: call
+            .messages
+            .iter()
+            .map(|message| LoggedMessage {
+                role: match message.role {
+                    ChatRole::User => "user",
+                    ChatRole::Model => "model",
+                }
+                .to_string(),
+                text: redact(&message_text(message)),
+            })
+            .collect(),
+        json_output: call.json_output,
+        response,
+        latency_ms: call.latency.as_millis() as u64,
+        total_tokens,
+        error_kind,
+        error,
+        created_at: now,
+    };
+
+    if let Err(e) = database.record_llm_call(entry) {
+        UNRECORDED_CALLS.fetch_add(1, Ordering::Relaxed);
+        eprintln!("Failed to record LLM call: {}", e);
+    }
+}
+
+/// Calls that could not be stored in the audit log since the app started
+pub fn unrecorded_calls() -> usize {
+    UNRECORDED_CALLS.load(Ordering::Relaxed)
+}
+
+/// Rebuild the messages of a logged call, e.g. to send it again
+///
+/// Function calls and results are kept as written-out text.
+pub(super) fn logged_messages(call: &LLMCallLog) -> Vec<ChatMessage> {
+    call.messages
+        .iter()
+        .map(|message| match message.role.as_str() {
+            "model" => ChatMessage::model(&message.text),
+            _ => ChatMessage::user(&message.text),
+        })
+        .collect()
+}
+
+/// Message text with its function calls and results written out
+fn message_text(message: &ChatMessage) -> String {
+    let mut text = message.text.clone();
+    for call in &message.tool_calls {
+        text.push_str(&format!("\n[call {} {}]", call.name, call.args));
+    }
+    for result in &message.tool_results {
+        text.push_str(&format!("\n[result {} {}]", result.name, result.response));
+    }
+    text.trim_start().to_string()
+}
+
+/// Remove the configured API key and anything shaped like a Google API key
+pub fn redact(text: &str, api_key: Option<&str>) -> String {
+    let mut redacted = match api_key.map(str::trim).filter(|key| !key.is_empty()) {
+        Some(key) => text.replace(key, REDACTED),
+        None => text.to_string(),
+    };
+
+    let is_key_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    let mut search_from = 0;
+    while let Some(offset) = redacted[search_from..].find(GOOGLE_KEY_PREFIX) {
+        let start = search_from + offset;
+        let length = redacted[start..]
+            .find(|c: char| !is_key_char(c))
+            .unwrap_or(redacted.len() - start);
+        if length == GOOGLE_KEY_LENGTH {
+            redacted.replace_range(start..start + length, REDACTED);
+            search_from = start + REDACTED.len();
+        } else {
+            search_from = start + GOOGLE_KEY_PREFIX.len();
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_keys_are_redacted() {
+        let google_key = format!("AIza{}", "x".repeat(35));
+        let text = format!("key=my-secret&other={}, AIzaShort", google_key);
+
+        assert_eq!(
+            redact(&text, Some("my-secret")),
+            "key=[REDACTED]&other=[REDACTED], AIzaShort"
+        );
+        assert_eq!(redact("nothing to hide", None), "nothing to hide");
+    }
+
+    #[test]
+    fn calls_are_logged_and_bounded() {
+        let database = DatabaseService::new_in_memory().unwrap();
+        let messages = [ChatMessage::user("explain 猫 with key sk-123")];
+        let failed = Err(LLMError::RateLimited { retry_after: None });
+
+        for _ in 0..crate::constants::llm::AUDIT_LOG_LIMIT + 2 {
+            record(
+                &database,
+                Some("sk-123"),
+                CallRecord {
+                    provider: "gemini",
+                    model: "test-model".to_string(),
+                    fixture: Fixture {
+                        kind: "explain",
+                        word: Some("猫"),
+                    },
+                    system: None,
+                    messages: &messages,
+                    json_output: true,
+                    result: &failed,
+                    latency: Duration::from_millis(5),
+                },
+            );
+        }
+
+        // Every call is stored under its own id, even when logged in the same microsecond
+        let calls = database.get_llm_calls().unwrap();
+        assert_eq!(calls.len(), crate::constants::llm::AUDIT_LOG_LIMIT);
+        assert_eq!(unrecorded_calls(), 0);
+        assert_eq!(calls[0].messages[0].text, "explain 猫 with key [REDACTED]");
+        assert_eq!(calls[0].error_kind.as_deref(), Some("RateLimited"));
+        assert_eq!(
+            logged_messages(&calls[0])[0].text,
+            calls[0].messages[0].text
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Minimal Gemini REST client
+//!
+//! Talks to the `generateContent` endpoint directly so that HTTP status codes
+//! and usage metadata stay visible to the service layer.
+
+use super::tools::{ToolCall, ToolDeclaration};
+use super::{ChatMessage, ChatRole, Completion, LLMError};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+const API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+/// HTTP client for a single Gemini model
+#[derive(Debug, Clone)]
+pub struct GeminiClient {
+    http: reqwest::Client,
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    total_token_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Candidate {
+    content: Option<Content>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Content {
+    #[serde(default)]
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Part {
+    text: Option<String>,
+    function_call: Option<FunctionCall>,
+    thought_signature: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+impl GeminiClient {
+    /// Create a client for the given model name (e.g. `gemini-2.5-pro`)
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            model: model.into(),
+        }
+    }
+
+    /// Name of the model requests are sent to
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Send a conversation, optionally with a system instruction, and return the next reply
+    ///
+    /// When `json_output` is set, the model is asked to reply with a JSON document.
+    /// The model may call any of `tools` instead of replying with text.
+    pub async fn chat(
+        &self,
+        api_key: &str,
+        system: Option<&str>,
+        messages: &[ChatMessage],
+        json_output: bool,
+        tools: &[ToolDeclaration],
+    ) -> Result<Completion, LLMError> {
+        let contents: Vec<_> = messages
+            .iter()
+            .map(|message| {
+                let role = match message.role {
+                    ChatRole::User => "user",
+                    ChatRole::Model => "model",
+                };
+                json!({ "role": role, "parts": message_parts(message) })
+            })
+            .collect();
+
+        let mut body = json!({ "contents": contents });
+        if let Some(system) = system {
+            body["systemInstruction"] = json!({ "parts": [{ "text": system }] });
+        }
+        if !tools.is_empty() {
+            let declarations: Vec<_> = tools
+                .iter()
+                .map(|tool| {
+                    json!({
+                        "name": tool.name,
+                        "description": tool.description,
+                        "parameters": tool.parameters,
+                    })
+                })
+                .collect();
+            body["tools"] = json!([{ "functionDeclarations": declarations }]);
+        }
+        if json_output {
+            body["generationConfig"] = json!({ "responseMimeType": "application/json" });
+        }
+
+        let response = self
+            .http
+            .post(format!("{}/{}:generateContent", API_BASE, self.model))
+            .header("x-goog-api-key", api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| LLMError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs);
+            let message = response.text().await.unwrap_or_default();
+
+            return Err(match status {
+                StatusCode::TOO_MANY_REQUESTS => LLMError::RateLimited { retry_after },
+                status if status.is_server_error() => {
+                    LLMError::ServerError(format!("{}: {}", status, message))
+                }
+                status => LLMError::ApiError(format!("{}: {}", status, message)),
+            });
+        }
+
+        let parsed: GenerateResponse = response
+            .json()
+            .await
+            .map_err(|e| LLMError::ParseError(e.to_string()))?;
+
+        let total_tokens = parsed
+            .usage_metadata
+            .map_or(0, |usage| usage.total_token_count);
+        let parts: Vec<Part> = parsed
+            .candidates
+            .into_iter()
+            .next()
+            .and_then(|candidate| candidate.content)
+            .map(|content| content.parts)
+            .unwrap_or_default();
+
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for part in parts {
+            if let Some(call) = part.function_call {
+                tool_calls.push(ToolCall {
+                    name: call.name,
+                    args: call.args,
+                    signature: part.thought_signature,
+                });
+            } else if let Some(part_text) = part.text {
+                text.push_str(&part_text);
+            }
+        }
+        if text.is_empty() && tool_calls.is_empty() {
+            return Err(LLMError::ParseError(
+                "Response contained no text".to_string(),
+            ));
+        }
+
+        Ok(Completion {
+            text,
+            tool_calls,
+            total_tokens,
+        })
+    }
+}
+
+/// Gemini parts for a message: its text, function calls and function responses
+fn message_parts(message: &ChatMessage) -> Vec<serde_json::Value> {
+    let mut parts = Vec::new();
+    if !message.text.is_empty() {
+        parts.push(json!({ "text": message.text }));
+    }
+    for call in &message.tool_calls {
+        let mut part = json!({ "functionCall": { "name": call.name, "args": call.args } });
+        if let Some(signature) = &call.signature {
+            part["thoughtSignature"] = json!(signature);
+        }
+        parts.push(part);
+    }
+    for result in &message.tool_results {
+        parts.push(json!({
+            "functionResponse": { "name": result.name, "response": result.response }
+        }));
+    }
+    if parts.is_empty() {
+        parts.push(json!({ "text": "" }));
+    }
+    parts
+}
@@ -0,0 +1,171 @@
+//! Request governor for LLM provider calls
+//!
+//! Every provider call goes through [`RequestGovernor::execute`], which enforces
+//! a requests-per-minute limit, retries rate-limit and server errors with
+//! jittered exponential backoff, and accounts token usage against a daily budget.
+
+use super::{Completion, LLMError};
+use crate::constants::llm;
+use crate::models::deck::LLMUsage;
+use crate::services::DatabaseService;
+use chrono::{Local, Utc};
+use rand::Rng;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Length of the sliding window used for rate limiting
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// User-configurable limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GovernorConfig {
+    pub requests_per_minute: u32,
+    /// Maximum tokens per day; `0` disables the budget
+    pub daily_token_budget: u64,
+}
+
+impl Default for GovernorConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: llm::DEFAULT_REQUESTS_PER_MINUTE,
+            daily_token_budget: llm::DEFAULT_DAILY_TOKEN_BUDGET,
+        }
+    }
+}
+
+/// Shared gatekeeper for all LLM requests
+pub struct RequestGovernor {
+    config: Mutex<GovernorConfig>,
+    recent_requests: Mutex<VecDeque<Instant>>,
+    database: Arc<DatabaseService>,
+}
+
+impl RequestGovernor {
+    /// Create a governor that records usage in the given database
+    pub fn new(config: GovernorConfig, database: Arc<DatabaseService>) -> Self {
+        Self {
+            config: Mutex::new(config),
+            recent_requests: Mutex::new(VecDeque::new()),
+            database,
+        }
+    }
+
+    /// Current limits
+    pub fn config(&self) -> GovernorConfig {
+        *self.config.lock().unwrap()
+    }
+
+    /// Replace the limits; takes effect for the next request
+    pub fn set_config(&self, config: GovernorConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// Usage recorded for the current local day
+    pub fn usage_today(&self) -> LLMUsage {
+        let date = today();
+        self.database
+            .get_llm_usage(&date)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to load LLM usage: {}", e);
+                None
+            })
+            .unwrap_or(LLMUsage {
+                date,
+                requests: 0,
+                total_tokens: 0,
+                updated_at: Utc::now(),
+            })
+    }
+
+    /// Run a provider call under the rate limit, retry policy and daily budget
+    ///
+    /// `call` is invoked once per attempt. Rate-limit (429) and server (5xx)
+    /// errors are retried up to `MAX_RETRIES` times; other errors are returned as is.
+    pub async fn execute<F, Fut>(&self, mut call: F) -> Result<Completion, LLMError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Completion, LLMError>>,
+    {
+        self.check_budget()?;
+
+        let mut attempt = 0;
+        loop {
+            self.wait_for_slot().await;
+
+            match call().await {
+                Ok(completion) => {
+                    if let Err(e) = self
+                        .database
+                        .record_llm_usage(&today(), completion.total_tokens)
+                    {
+                        eprintln!("Failed to record LLM usage: {}", e);
+                    }
+                    return Ok(completion);
+                }
+                Err(error) if error.is_retryable() && attempt < llm::MAX_RETRIES => {
+                    let delay = backoff_delay(attempt, error.retry_after());
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn check_budget(&self) -> Result<(), LLMError> {
+        let budget = self.config().daily_token_budget;
+        if budget == 0 {
+            return Ok(());
+        }
+
+        let used = self.usage_today().total_tokens;
+        if used >= budget {
+            return Err(LLMError::BudgetExceeded { used, budget });
+        }
+        Ok(())
+    }
+
+    /// Wait until a request fits in the requests-per-minute window, then claim it
+    async fn wait_for_slot(&self) {
+        loop {
+            let wait = {
+                let limit = self.config().requests_per_minute.max(1) as usize;
+                let mut recent = self.recent_requests.lock().unwrap();
+                let now = Instant::now();
+                while recent
+                    .front()
+                    .is_some_and(|sent| now.duration_since(*sent) >= RATE_WINDOW)
+                {
+                    recent.pop_front();
+                }
+
+                match recent.front() {
+                    Some(oldest) if recent.len() >= limit => {
+                        RATE_WINDOW - now.duration_since(*oldest)
+                    }
+                    _ => {
+                        recent.push_back(now);
+                        return;
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Exponential backoff with equal jitter, honouring a server-provided delay
+fn backoff_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    let exponential = llm::INITIAL_BACKOFF_MS.saturating_mul(1 << attempt.min(16));
+    let capped = exponential.min(llm::MAX_BACKOFF_MS);
+    let jittered = rand::rng().random_range(capped / 2..=capped);
+    let delay = Duration::from_millis(jittered);
+
+    retry_after.map_or(delay, |server_delay| server_delay.max(delay))
+}
+
+/// Current local date used as the usage key
+fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
@@ -0,0 +1,266 @@
+//! Offline LLM provider answering from fixture files
+//!
+//! Used for demo mode and tests, so both work without network access or an
+//! API key. A request is answered from the first file that exists:
+//! 1. `<dir>/<kind>/<word>.<ext>` when the request is about a single word
+//!    that is usable as a file name
+//! 2. `<dir>/prompts/<hash>.<ext>`, keyed by a stable hash of the full prompt
+//! 3. `<dir>/<kind>/_default.<ext>`, with `{word}` replaced by the word
+//!
+//! `<ext>` is `json` for requests that expect JSON and `txt` otherwise.
+
+use super::{ChatMessage, ChatRole, Completion, LLMError};
+use crate::constants;
+use std::path::{Path, PathBuf};
+
+/// Longest word, in bytes, looked up as a fixture file name
+const MAX_FIXTURE_NAME: usize = 100;
+
+/// What a request is for, used to pick its fixture file
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture<'a> {
+    /// Fixture subdirectory, e.g. `explain` or `card`
+    pub kind: &'a str,
+    /// Dictionary form of the word the request is about
+    pub word: Option<&'a str>,
+}
+
+/// Provider that reads canned responses from a fixture directory
+#[derive(Debug, Clone)]
+pub struct MockProvider {
+    dir: PathBuf,
+}
+
+impl MockProvider {
+    /// Create a provider reading fixtures from `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Create a provider reading the fixtures installed for demo mode
+    ///
+    /// Uses the directory named by `FIXTURE_DIR_VAR` when set, otherwise
+    /// `FIXTURE_DIR` next to the executable if it exists there, otherwise
+    /// `FIXTURE_DIR` relative to the working directory.
+    pub fn installed() -> Self {
+        if let Some(dir) = std::env::var_os(constants::llm::FIXTURE_DIR_VAR) {
+            return Self::new(dir);
+        }
+        let beside_executable = std::env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.parent()?.join(constants::llm::FIXTURE_DIR)))
+            .filter(|dir| dir.is_dir());
+        Self::new(beside_executable.unwrap_or_else(|| PathBuf::from(constants::llm::FIXTURE_DIR)))
+    }
+
+    /// Directory the fixtures are read from
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Answer a conversation from the fixture files
+    pub fn chat(
+        &self,
+        fixture: Fixture<'_>,
+        system: Option<&str>,
+        messages: &[ChatMessage],
+        json_output: bool,
+    ) -> Result<Completion, LLMError> {
+        let ext = if json_output { "json" } else { "txt" };
+        let kind_dir = self.dir.join(fixture.kind);
+
+        if let Some(word) = fixture.word.filter(|word| is_file_name(word))
+            && let Some(text) = read_fixture(&kind_dir.join(format!("{}.{}", word, ext)))
+        {
+            return Ok(completion(text));
+        }
+
+        let hash = prompt_hash(system, messages);
+        let prompt_file = self
+            .dir
+            .join("prompts")
+            .join(format!("{:016x}.{}", hash, ext));
+        if let Some(text) = read_fixture(&prompt_file) {
+            return Ok(completion(text));
+        }
+
+        let default_file = kind_dir.join(format!("_default.{}", ext));
+        let text = read_fixture(&default_file).ok_or_else(|| {
+            LLMError::ApiError(format!(
+                "No fixture for this request; add {} or {}",
+                prompt_file.display(),
+                default_file.display()
+            ))
+        })?;
+        let word = fixture.word.unwrap_or_default();
+        let word = if json_output {
+            // Keep the JSON valid whatever the word contains
+            let quoted = serde_json::to_string(word).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        } else {
+            word.to_string()
+        };
+
+        Ok(completion(text.replace("{word}", &word)))
+    }
+}
+
+/// Whether `word` names a file inside its directory rather than a path
+fn is_file_name(word: &str) -> bool {
+    !word.is_empty()
+        && word.len() <= MAX_FIXTURE_NAME
+        && word != "."
+        && word != ".."
+        && !word.contains(['/', '\\', ':', '\0'])
+}
+
+fn read_fixture(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+fn completion(text: String) -> Completion {
+    Completion {
+        text,
+        tool_calls: Vec::new(),
+        total_tokens: 0,
+    }
+}
+
+/// FNV-1a hash of the system instruction and messages
+///
+/// Unlike `DefaultHasher`, the result is stable across Rust versions, so
+/// fixture file names stay valid.
+pub fn prompt_hash(system: Option<&str>, messages: &[ChatMessage]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut parts = vec![system.unwrap_or_default()];
+    for message in messages {
+        parts.push(match message.role {
+            ChatRole::User => "user",
+            ChatRole::Model => "model",
+        });
+        parts.push(&message.text);
+    }
+
+    parts
+        .iter()
+        .flat_map(|part| part.bytes().chain([0]))
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh fixture directory under the system temp dir
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("nihonrs-fixtures-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("explain")).unwrap();
+        std::fs::create_dir_all(dir.join("prompts")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn prompt_hash_is_stable_and_role_sensitive() {
+        let user = [ChatMessage::user("こんにちは")];
+        let model = [ChatMessage::model("こんにちは")];
+
+        assert_eq!(prompt_hash(None, &user), prompt_hash(None, &user));
+        assert_ne!(prompt_hash(None, &user), prompt_hash(None, &model));
+        assert_ne!(prompt_hash(None, &user), prompt_hash(Some("system"), &user));
+        assert_eq!(prompt_hash(None, &[]), 0xaf63_bd4c_8601_b7df);
+    }
+
+    #[test]
+    fn word_fixture_wins_over_prompt_hash_and_default() {
+        let dir = fixture_dir("precedence");
+        let messages = [ChatMessage::user("explain 猫")];
+        let hash = prompt_hash(None, &messages);
+        std::fs::write(dir.join("explain/猫.txt"), "word").unwrap();
+        std::fs::write(dir.join(format!("prompts/{:016x}.txt", hash)), "prompt").unwrap();
+        std::fs::write(dir.join("explain/_default.txt"), "default {word}").unwrap();
+        let mock = MockProvider::new(&dir);
+
+        let by_word = Fixture {
+            kind: "explain",
+            word: Some("猫"),
+        };
+        let other_word = Fixture {
+            kind: "explain",
+            word: Some("犬"),
+        };
+        let unknown_prompt = [ChatMessage::user("explain 犬")];
+
+        assert_eq!(
+            mock.chat(by_word, None, &messages, false).unwrap().text,
+            "word"
+        );
+        assert_eq!(
+            mock.chat(other_word, None, &messages, false).unwrap().text,
+            "prompt"
+        );
+        assert_eq!(
+            mock.chat(other_word, None, &unknown_prompt, false)
+                .unwrap()
+                .text,
+            "default 犬"
+        );
+    }
+
+    #[test]
+    fn words_that_are_not_file_names_use_the_default() {
+        let dir = fixture_dir("paths");
+        std::fs::write(dir.join("secret.txt"), "secret").unwrap();
+        std::fs::write(dir.join("explain/_default.txt"), "default").unwrap();
+        let mock = MockProvider::new(&dir);
+        let long_word = "猫".repeat(50);
+
+        for word in ["../secret", "..", "", long_word.as_str()] {
+            let fixture = Fixture {
+                kind: "explain",
+                word: Some(word),
+            };
+            let text = mock.chat(fixture, None, &[], false).unwrap().text;
+            assert_eq!(text, "default", "word {:?}", word);
+        }
+    }
+
+    #[test]
+    fn missing_fixture_is_an_error() {
+        let dir = fixture_dir("missing");
+        let mock = MockProvider::new(&dir);
+        let fixture = Fixture {
+            kind: "explain",
+            word: None,
+        };
+
+        let result = mock.chat(fixture, None, &[ChatMessage::user("?")], true);
+
+        assert!(matches!(result, Err(LLMError::ApiError(_))));
+    }
+
+    #[test]
+    fn default_json_fixture_escapes_the_word() {
+        let dir = fixture_dir("escape");
+        std::fs::write(
+            dir.join("explain/_default.json"),
+            r#"{"meaning": "{word}"}"#,
+        )
+        .unwrap();
+        let mock = MockProvider::new(&dir);
+        let fixture = Fixture {
+            kind: "explain",
+            word: Some("\"quoted\""),
+        };
+
+        let text = mock.chat(fixture, None, &[], true).unwrap().text;
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(value["meaning"], "\"quoted\"");
+    }
+}
@@ -0,0 +1,1110 @@
+//! LLM service for AI-powered explanations
+//!
+//! This service integrates with the Gemini API to provide context-aware
+//! explanations for Japanese words and grammar, and to generate flashcard data.
+//! In demo mode and tests it answers from local fixture files instead.
+
+pub mod audit;
+pub mod gemini;
+pub mod governor;
+pub mod mock;
+pub mod tools;
+
+use crate::constants;
+use crate::models::deck::{GradedRewrite, LLMCallLog};
+use crate::models::{
+    CardType, ConversationReply, ConversationTurn, ExampleSentence, GrammarCard, JLPTLevel,
+    Language, PhraseAlignment, SentenceTranslation, VocabularyCard, WordExplanation, WordSegment,
+    WritingGrade,
+};
+use crate::services::DatabaseService;
+use crate::services::distractors::{DistractorPool, WRONG_ANSWER_COUNT};
+use gemini::GeminiClient;
+use governor::RequestGovernor;
+use mock::{Fixture, MockProvider};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tools::{ToolCall, ToolDeclaration, ToolExecutor, ToolResult};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// LLM service for generating explanations
+#[derive(Clone)]
+pub struct LLMService {
+    api_key: Option<String>,
+    user_context: String,
+    learner_report: String,
+    language: Language,
+    provider: Provider,
+    governor: Arc<RequestGovernor>,
+    /// Where every provider call is recorded, if anywhere
+    audit_log: Option<Arc<DatabaseService>>,
+}
+
+/// Backend that answers LLM requests
+#[derive(Debug, Clone)]
+pub enum Provider {
+    /// Gemini REST API, rate limited and budgeted by the governor
+    Gemini(GeminiClient),
+    /// Offline fixture files, for demo mode and tests
+    Mock(MockProvider),
+}
+
+impl Provider {
+    /// Provider name and the model (or fixture directory) it answers with
+    pub fn describe(&self) -> (&'static str, String) {
+        match self {
+            Provider::Gemini(gemini) => ("gemini", gemini.model().to_string()),
+            Provider::Mock(mock) => ("fixtures", mock.dir().display().to_string()),
+        }
+    }
+}
+
+/// Author of a message in a conversation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatRole {
+    User,
+    Model,
+}
+
+/// A single message in a multi-turn conversation
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub text: String,
+    /// Function calls made by the model in this message
+    pub tool_calls: Vec<ToolCall>,
+    /// Function outputs sent back to the model in this message
+    pub tool_results: Vec<ToolResult>,
+}
+
+impl ChatMessage {
+    pub fn user(text: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            text: text.into(),
+            tool_calls: Vec::new(),
+            tool_results: Vec::new(),
+        }
+    }
+
+    pub fn model(text: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::Model,
+            text: text.into(),
+            tool_calls: Vec::new(),
+            tool_results: Vec::new(),
+        }
+    }
+
+    /// Model turn that asked for function calls
+    pub fn tool_calls(text: impl Into<String>, calls: Vec<ToolCall>) -> Self {
+        Self {
+            tool_calls: calls,
+            ..Self::model(text)
+        }
+    }
+
+    /// User turn carrying the outputs of function calls
+    pub fn tool_results(results: Vec<ToolResult>) -> Self {
+        Self {
+            tool_results: results,
+            ..Self::user("")
+        }
+    }
+}
+
+/// Raw reply from a provider with its token usage
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub text: String,
+    /// Function calls the model wants run before it answers
+    pub tool_calls: Vec<ToolCall>,
+    pub total_tokens: u64,
+}
+
+/// Flashcard data returned by the LLM (see SPEC "Generate Flashcard Data")
+#[derive(Debug, Deserialize)]
+struct CardData {
+    meaning: String,
+    #[serde(default)]
+    reading: Option<String>,
+    romaji: String,
+    wrong_answers: Vec<String>,
+    jlpt_level: String,
+    #[serde(default)]
+    examples: Vec<ExampleSentence>,
+}
+
+/// Grammar flashcard data returned by the LLM
+#[derive(Debug, Deserialize)]
+struct GrammarCardData {
+    explanation: String,
+    #[serde(default)]
+    reading: String,
+    wrong_answers: Vec<String>,
+    jlpt_level: String,
+    #[serde(default)]
+    examples: Vec<ExampleSentence>,
+}
+
+/// Sentence translation returned by the LLM
+#[derive(Debug, Deserialize)]
+struct TranslationData {
+    translation: String,
+    #[serde(default)]
+    alignments: Vec<AlignmentData>,
+}
+
+/// One alignment as returned by the LLM: inclusive segment indices and the translated phrase
+#[derive(Debug, Deserialize)]
+struct AlignmentData {
+    segments: [usize; 2],
+    #[serde(alias = "english")]
+    phrase: String,
+}
+
+/// Graded reader rewrite returned by the LLM
+#[derive(Debug, Deserialize)]
+struct RewriteData {
+    sentences: Vec<RewrittenSentence>,
+}
+
+/// One rewritten sentence and the number of the original sentence it retells
+#[derive(Debug, Deserialize)]
+struct RewrittenSentence {
+    original: Option<usize>,
+    text: String,
+}
+
+impl LLMService {
+    /// Create a new LLM service
+    ///
+    /// All requests go through the shared `governor`, so rate limits and the
+    /// daily budget apply across every service instance.
+    pub fn new(
+        api_key: Option<String>,
+        user_context: String,
+        governor: Arc<RequestGovernor>,
+    ) -> Self {
+        Self {
+            api_key,
+            user_context,
+            learner_report: String::new(),
+            language: Language::default(),
+            provider: Provider::Gemini(GeminiClient::new(constants::llm::DEFAULT_MODEL)),
+            governor,
+            audit_log: None,
+        }
+    }
+
+    /// Request an explanation for a Japanese word
+    ///
+    /// # Arguments
+    /// * `surface` - The surface form of the word (kanji/kana)
+    /// * `reading` - The hiragana reading
+    /// * `base_form` - Dictionary form of the word
+    /// * `context` - The sentence the word appears in
+    ///
+    /// # Returns
+    /// A `WordExplanation` with meaning, grammar notes, examples, and JLPT level
+    pub async fn explain_word(
+        &self,
+        surface: &str,
+        reading: &str,
+        base_form: &str,
+        context: &str,
+    ) -> Result<WordExplanation, LLMError> {
+        let prompt = self.explanation_prompt(surface, reading, base_form, context);
+        let explanation = self
+            .complete_json(Fixture { kind: "explain", word: Some(base_form) }, &prompt)
+            .await?;
+        Ok(self.tag_explanation(explanation))
+    }
+
+    /// Explain a word again, addressing the learner's feedback on an earlier explanation
+    ///
+    /// `feedback` is free text such as "too advanced"; it may be empty when
+    /// the learner just wants another attempt.
+    pub async fn regenerate_explanation(
+        &self,
+        segment: &WordSegment,
+        context: &str,
+        previous: &WordExplanation,
+        feedback: &str,
+    ) -> Result<WordExplanation, LLMError> {
+        let mut prompt =
+            self.explanation_prompt(&segment.surface, &segment.reading, &segment.base_form, context);
+        let previous = serde_json::to_string_pretty(previous)
+            .map_err(|e| LLMError::ParseError(e.to_string()))?;
+        prompt.push_str(&format!(
+            "
+
+The learner was not satisfied with this earlier explanation:
+{previous}"
+        ));
+        if !feedback.trim().is_empty() {
+            prompt.push_str(&format!(
+                "
+
+Learner feedback: {}
+Write a new explanation that addresses this feedback.",
+                feedback.trim()
+            ));
+        }
+
+        let fixture = Fixture { kind: "explain", word: Some(&segment.base_form) };
+        let explanation = self.complete_json(fixture, &prompt).await?;
+        Ok(self.tag_explanation(explanation))
+    }
+
+    /// Prompt asking for the explanation of a word in its sentence
+    fn explanation_prompt(
+        &self,
+        surface: &str,
+        reading: &str,
+        base_form: &str,
+        context: &str,
+    ) -> String {
+        format!(
+            "You are teaching Japanese to a learner.
+
+Word/Phrase: {surface} (reading: {reading}, dictionary form: {base_form})
+Full sentence context: {context}
+
+{learner}
+
+Provide a JSON response with:
+1. The {language} meaning, as used in this sentence
+2. The hiragana reading of the word as it appears in this sentence
+3. Relevant grammar points, usage notes or nuances, and how the word fits in this sentence
+4. Two example sentences in Japanese with {language} translations
+5. The JLPT level (N5, N4, N3, N2, N1, or Unknown)
+
+Adjust complexity to match the learner's level and focus on their weaknesses.
+Keep the explanation concise but informative, and write it in {language}.
+
+Format as JSON matching this schema:
+{{
+  \"meaning\": string,
+  \"reading\": string,
+  \"grammar_notes\": string or null,
+  \"examples\": [{{\"japanese\": string, \"translation\": string}}],
+  \"jlpt_level\": string
+}}",
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        )
+    }
+
+    /// Answer a user's question about the text
+    ///
+    /// # Arguments
+    /// * `question` - The user's question
+    /// * `context` - The current text being studied
+    /// * `history` - Earlier (question, answer) pairs about the same text, oldest first
+    ///
+    /// # Returns
+    /// An answer string from the LLM
+    ///
+    /// Only the most recent turns that fit in `HISTORY_TOKEN_BUDGET` are sent.
+    /// With `tools`, the model may look up the student's data first; after
+    /// `MAX_TOOL_ROUNDS` rounds of calls it has to answer without them.
+    pub async fn answer_question(
+        &self,
+        question: &str,
+        context: &str,
+        history: &[(String, String)],
+        tools: Option<&dyn ToolExecutor>,
+    ) -> Result<String, LLMError> {
+        let mut system = format!(
+            "You are a patient Japanese tutor. The student is reading this text:
+{context}
+
+{learner}
+
+Answer the student's questions about the text in {language}, clearly and \
+concisely, adjusting the complexity to their level.",
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        );
+        if tools.is_some() {
+            system.push_str(
+                "\n\nYou can look up the dictionary and the student's own cards and \
+review history with the provided functions. Use them when the answer depends \
+on what the student has studied, and refer to that data in your answer. Only \
+add cards when the student asks for it.",
+            );
+        }
+
+        let mut messages = trim_history(history, constants::llm::HISTORY_TOKEN_BUDGET);
+        messages.push(ChatMessage::user(question));
+
+        let fixture = Fixture { kind: "answer", word: None };
+        let declarations = tools.map(ToolExecutor::declarations).unwrap_or_default();
+        for _ in 0..constants::llm::MAX_TOOL_ROUNDS {
+            let completion = self
+                .complete(fixture, Some(&system), &messages, false, &declarations)
+                .await?;
+            let Some(tools) = tools.filter(|_| !completion.tool_calls.is_empty()) else {
+                return Ok(completion.text);
+            };
+
+            let results = completion.tool_calls.iter().map(|call| tools.call(call)).collect();
+            messages.push(ChatMessage::tool_calls(completion.text, completion.tool_calls));
+            messages.push(ChatMessage::tool_results(results));
+        }
+
+        // Out of rounds: ask for an answer from what has been gathered so far
+        self.send(fixture, Some(&system), &messages, false).await
+    }
+
+    /// Generate a vocabulary flashcard for a word found in a text
+    ///
+    /// # Arguments
+    /// * `surface` - The surface form of the word (kanji/kana)
+    /// * `reading` - The hiragana reading
+    /// * `base_form` - Dictionary form of the word
+    /// * `context` - The text the word was taken from
+    ///
+    /// # Returns
+    /// A `VocabularyCard` with meaning, up to three distractors, examples, and JLPT level.
+    /// Its reading is the LLM's, falling back to `reading` when none is given.
+    pub async fn generate_vocabulary_card(
+        &self,
+        surface: &str,
+        reading: &str,
+        base_form: &str,
+        context: &str,
+    ) -> Result<VocabularyCard, LLMError> {
+        let prompt = format!(
+            "For the Japanese word '{base_form}' (reading: {reading}), appearing as '{surface}' in this text:
+{context}
+
+Provide a JSON response with:
+1. The {language} meaning of the dictionary form, as used in this text
+2. The hiragana reading of the dictionary form
+3. The Hepburn romaji of the dictionary form
+4. Exactly three semantically related but incorrect {language} meanings, similar in length and style to the correct one
+5. The JLPT level (N5, N4, N3, N2, N1, or Unknown)
+6. Two example sentences in Japanese with {language} translations
+
+{learner}
+
+Format as JSON matching this schema:
+{{
+  \"meaning\": string,
+  \"reading\": string,
+  \"romaji\": string,
+  \"wrong_answers\": [string, string, string],
+  \"jlpt_level\": string,
+  \"examples\": [{{\"japanese\": string, \"translation\": string}}]
+}}",
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        );
+
+        let fixture = Fixture { kind: "card", word: Some(base_form) };
+        // Missing wrong answers are filled in locally when the deck is opened
+        let data: CardData = self.complete_json(fixture, &prompt).await?;
+
+        Ok(VocabularyCard {
+            kanji: base_form.to_string(),
+            hiragana: data
+                .reading
+                .filter(|llm_reading| !llm_reading.trim().is_empty())
+                .unwrap_or_else(|| reading.to_string()),
+            romaji: data.romaji,
+            meaning: data.meaning,
+            wrong_answers: data.wrong_answers.into_iter().take(3).collect(),
+            example_sentences: self.tag_examples(data.examples),
+            jlpt_level: JLPTLevel::from_str(&data.jlpt_level),
+            language: self.language,
+        })
+    }
+
+    /// Generate a grammar flashcard for a pattern
+    ///
+    /// # Arguments
+    /// * `pattern` - The grammar pattern, e.g. "〜てしまう"
+    /// * `context` - A sentence using the pattern
+    /// * `pool` - Wrong answers to fill in for missing, repeated or correct ones
+    ///
+    /// # Returns
+    /// A `GrammarCard` with an explanation, three distinct distractors, examples, and JLPT level,
+    /// or a `ParseError` when three distractors cannot be found.
+    pub async fn generate_grammar_card(
+        &self,
+        pattern: &str,
+        context: &str,
+        pool: &DistractorPool<'_>,
+    ) -> Result<GrammarCard, LLMError> {
+        let prompt = format!(
+            "For the Japanese grammar pattern '{pattern}', as used in this text:
+{context}
+
+Provide a JSON response with:
+1. A short {language} explanation of what the pattern means
+2. The hiragana reading of the pattern
+3. Exactly three plausible but incorrect {language} explanations, similar in length and style to the correct one
+4. The JLPT level (N5, N4, N3, N2, N1, or Unknown)
+5. Two example sentences in Japanese with {language} translations
+
+{learner}
+
+Format as JSON matching this schema:
+{{
+  \"explanation\": string,
+  \"reading\": string,
+  \"wrong_answers\": [string, string, string],
+  \"jlpt_level\": string,
+  \"examples\": [{{\"japanese\": string, \"translation\": string}}]
+}}",
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        );
+
+        let fixture = Fixture { kind: "grammar", word: Some(pattern) };
+        let data: GrammarCardData = self.complete_json(fixture, &prompt).await?;
+
+        let mut wrong_answers: Vec<String> = Vec::new();
+        for answer in data.wrong_answers {
+            let answer = answer.trim().to_string();
+            let repeated = wrong_answers
+                .iter()
+                .chain([&data.explanation])
+                .any(|other| other.trim().eq_ignore_ascii_case(&answer));
+            if !answer.is_empty() && !repeated {
+                wrong_answers.push(answer);
+            }
+        }
+        wrong_answers.truncate(WRONG_ANSWER_COUNT);
+
+        let mut card = CardType::Grammar(GrammarCard {
+            pattern: pattern.to_string(),
+            pattern_reading: data.reading,
+            explanation: data.explanation,
+            wrong_answers,
+            example_sentences: self.tag_examples(data.examples),
+            jlpt_level: JLPTLevel::from_str(&data.jlpt_level),
+            language: self.language,
+        });
+        pool.top_up(&mut card);
+        match card {
+            CardType::Grammar(grammar) if grammar.wrong_answers.len() >= WRONG_ANSWER_COUNT => {
+                Ok(grammar)
+            }
+            _ => Err(LLMError::ParseError(format!(
+                "Fewer than {} distinct wrong answers for {}",
+                WRONG_ANSWER_COUNT, pattern
+            ))),
+        }
+    }
+
+    /// Translate a tokenized sentence and align the translated phrases with its segments
+    ///
+    /// # Arguments
+    /// * `segments` - The word segments of one sentence
+    ///
+    /// # Returns
+    /// A `SentenceTranslation` whose segment ranges index into `segments`.
+    /// Alignments that point outside the sentence or whose phrase is not
+    /// found in the translation are dropped.
+    pub async fn translate_sentence(
+        &self,
+        segments: &[WordSegment],
+    ) -> Result<SentenceTranslation, LLMError> {
+        let sentence: String = segments.iter().map(|segment| segment.surface.as_str()).collect();
+        let numbered = segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| format!("{}: {}", index, segment.surface))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!(
+            "Translate this Japanese sentence into natural {language} for a learner:
+{sentence}
+
+It is split into these numbered segments:
+{numbered}
+
+Then link each {language} phrase to the segments it translates. Each link covers \
+a run of consecutive segments, given as the first and last index, and a \
+{language} phrase copied exactly from your translation. Links must not overlap; \
+leave particles and punctuation without a clear {language} counterpart unlinked.
+
+{learner}
+
+Format as JSON matching this schema:
+{{
+  \"translation\": string,
+  \"alignments\": [{{\"segments\": [first, last], \"phrase\": string}}]
+}}",
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        );
+
+        let fixture = Fixture { kind: "translate", word: Some(&sentence) };
+        let data: TranslationData = self.complete_json(fixture, &prompt).await?;
+        Ok(resolve_alignments(data, segments.len()))
+    }
+
+    /// Rewrite a text at a JLPT level as a graded reader, keeping its meaning
+    ///
+    /// # Arguments
+    /// * `sentences` - The sentences of the text, in order
+    /// * `level` - JLPT level to rewrite at
+    ///
+    /// # Returns
+    /// A `GradedRewrite` linking each new sentence to the source sentence it
+    /// retells. Links to sentences that do not exist are dropped.
+    pub async fn rewrite_text(
+        &self,
+        sentences: &[String],
+        level: JLPTLevel,
+    ) -> Result<GradedRewrite, LLMError> {
+        let text: String = sentences.concat();
+        let numbered = sentences
+            .iter()
+            .enumerate()
+            .map(|(index, sentence)| format!("{}: {}", index, sentence))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!(
+            "Rewrite this Japanese text as a graded reader for a learner at JLPT {level}.
+Keep the meaning and the order of events, but use only vocabulary, kanji and \
+grammar up to {level}. Split long sentences and simplify where needed.
+
+The text, one numbered sentence per line:
+{numbered}
+
+For each sentence of your rewrite, give the number of the original sentence it \
+retells. Every rewritten sentence must end with 。, ！ or ？.
+
+Format as JSON matching this schema:
+{{
+  \"sentences\": [{{\"original\": number, \"text\": string}}]
+}}",
+            level = level.as_str(),
+        );
+
+        let fixture = Fixture { kind: "rewrite", word: Some(&text) };
+        let data: RewriteData = self.complete_json(fixture, &prompt).await?;
+        Ok(resolve_rewrite(data, sentences.len()))
+    }
+
+    /// Reply in character to a learner's Japanese and correct their mistakes
+    ///
+    /// # Arguments
+    /// * `message` - What the learner wrote
+    /// * `history` - Earlier turns of the same conversation, oldest first
+    /// * `level` - JLPT level the reply should be written at
+    ///
+    /// # Returns
+    /// The reply and corrections of `message`, each quoting the exact text it fixes
+    pub async fn conversation_reply(
+        &self,
+        message: &str,
+        history: &[ConversationTurn],
+        level: JLPTLevel,
+    ) -> Result<ConversationReply, LLMError> {
+        let system = format!(
+            "You are a friendly Japanese conversation partner. Chat naturally with the \
+student in Japanese, using only vocabulary and grammar up to JLPT {level}, and \
+keep your replies to a few sentences. Stay in character; never switch to {language} \
+in the reply.
+
+{learner}
+
+Also correct the student's latest message. For each mistake give the original \
+text copied exactly from the message, the corrected text, and a short {language} \
+explanation. Only correct real mistakes, not style. Use an empty list when the \
+message is correct.
+
+Format as JSON matching this schema:
+{{
+  \"reply\": string,
+  \"corrections\": [{{\"original\": string, \"corrected\": string, \"explanation\": string}}]
+}}",
+            level = level.as_str(),
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        );
+
+        // Earlier turns are sent as plain text; only the reply is kept of the model's answers
+        let turns: Vec<(String, String)> = history
+            .iter()
+            .map(|turn| (turn.message.clone(), turn.reply.reply.clone()))
+            .collect();
+        let mut messages = trim_history(&turns, constants::llm::HISTORY_TOKEN_BUDGET);
+        messages.push(ChatMessage::user(message));
+
+        let fixture = Fixture { kind: "conversation", word: Some(message) };
+        let text = self.send(fixture, Some(&system), &messages, true).await?;
+        parse_json(&text)
+    }
+
+    /// Grade a sentence the learner wrote to practice a card
+    ///
+    /// # Arguments
+    /// * `card` - The card whose word or grammar pattern should be used
+    /// * `sentence` - The learner's sentence
+    ///
+    /// # Returns
+    /// A `WritingGrade` with a 0-5 score, whether the target was used correctly,
+    /// and corrections quoting the exact text they fix
+    pub async fn grade_sentence(
+        &self,
+        card: &CardType,
+        sentence: &str,
+    ) -> Result<WritingGrade, LLMError> {
+        let target = match card {
+            CardType::Vocabulary(vocab) => format!(
+                "the word {} ({}), meaning \"{}\"",
+                vocab.kanji, vocab.hiragana, vocab.meaning
+            ),
+            CardType::Grammar(grammar) => format!(
+                "the grammar pattern {}, meaning \"{}\"",
+                grammar.pattern, grammar.explanation
+            ),
+        };
+        let prompt = format!(
+            "A Japanese learner was asked to write a sentence using {target}.
+
+Their sentence: {sentence}
+
+{learner}
+
+Grade the sentence for grammatical correctness, naturalness, and whether the \
+target was used correctly and with the right meaning. Give a score from 0 to 5: \
+5 correct and natural, 4 correct but slightly unnatural, 3 understandable with \
+minor mistakes, 2 major mistakes, 1 mostly wrong, 0 unrelated or not Japanese. \
+For each mistake give the original text copied exactly from the sentence, the \
+corrected text, and a short explanation. Write the explanations and feedback in \
+{language}, keeping the feedback to one or two sentences.
+
+Format as JSON matching this schema:
+{{
+  \"score\": number,
+  \"target_used\": boolean,
+  \"corrections\": [{{\"original\": string, \"corrected\": string, \"explanation\": string}}],
+  \"feedback\": string
+}}",
+            learner = self.learner_profile(),
+            language = self.language.name(),
+        );
+
+        let front = match card {
+            CardType::Vocabulary(vocab) => vocab.kanji.as_str(),
+            CardType::Grammar(grammar) => grammar.pattern.as_str(),
+        };
+        self.complete_json(Fixture { kind: "writing", word: Some(front) }, &prompt)
+            .await
+    }
+
+    /// Write an updated strengths and weaknesses report about the learner
+    ///
+    /// # Arguments
+    /// * `observations` - Summary of recent reviews and questions (see `ReportInputs::summary`)
+    /// * `previous` - The current report, which the new one should build on
+    ///
+    /// # Returns
+    /// The report as plain text
+    pub async fn generate_learner_report(
+        &self,
+        observations: &str,
+        previous: Option<&str>,
+    ) -> Result<String, LLMError> {
+        let prompt = format!(
+            "You keep a running report about a Japanese learner for their tutor.
+
+Learner profile: {user_context}
+
+Previous report:
+{previous}
+
+New observations:
+{observations}
+
+Write an updated report in plain text with these sections:
+Strengths, Weaknesses, Weak JLPT levels, Suggested focus.
+Keep what the new observations do not contradict, use concrete words and grammar \
+points as evidence, write in {language}, and stay under 250 words. Reply with the \
+report only.",
+            user_context = self.user_context,
+            language = self.language.name(),
+            previous = previous.unwrap_or("(none yet)"),
+        );
+
+        let fixture = Fixture { kind: "report", word: None };
+        let text = self
+            .send(fixture, None, &[ChatMessage::user(prompt)], false)
+            .await?;
+        Ok(text.trim().to_string())
+    }
+
+    /// Send a conversation to the provider and return the reply text
+    async fn send(
+        &self,
+        fixture: Fixture<'_>,
+        system: Option<&str>,
+        messages: &[ChatMessage],
+        json_output: bool,
+    ) -> Result<String, LLMError> {
+        let completion = self
+            .complete(fixture, system, messages, json_output, &[])
+            .await?;
+        if completion.text.is_empty() {
+            return Err(LLMError::ParseError("Response contained no text".to_string()));
+        }
+        Ok(completion.text)
+    }
+
+    /// Send a conversation, offering `tools`, and return the raw reply
+    ///
+    /// Gemini requests go through the governor; fixture lookups do not, as
+    /// they cost nothing. Fixtures never call tools.
+    async fn complete(
+        &self,
+        fixture: Fixture<'_>,
+        system: Option<&str>,
+        messages: &[ChatMessage],
+        json_output: bool,
+        tools: &[ToolDeclaration],
+    ) -> Result<Completion, LLMError> {
+        let started = Instant::now();
+        let result = match &self.provider {
+            Provider::Mock(mock) => mock.chat(fixture, system, messages, json_output),
+            Provider::Gemini(gemini) => match self.api_key.as_deref() {
+                Some(api_key) => {
+                    self.governor
+                        .execute(|| gemini.chat(api_key, system, messages, json_output, tools))
+                        .await
+                }
+                None => Err(LLMError::NotConfigured),
+            },
+        };
+
+        if let Some(database) = &self.audit_log {
+            let (provider, model) = self.provider.describe();
+            audit::record(
+                database,
+                self.api_key.as_deref(),
+                audit::CallRecord {
+                    provider,
+                    model,
+                    fixture,
+                    system,
+                    messages,
+                    json_output,
+                    result: &result,
+                    latency: started.elapsed(),
+                },
+            );
+        }
+        result
+    }
+
+    /// Send a logged call again, with the current provider, and return the reply text
+    ///
+    /// The new call is logged like any other.
+    pub async fn rerun(&self, call: &LLMCallLog) -> Result<String, LLMError> {
+        let fixture = Fixture {
+            kind: &call.kind,
+            word: call.word.as_deref(),
+        };
+        let messages = audit::logged_messages(call);
+        self.send(fixture, call.system.as_deref(), &messages, call.json_output)
+            .await
+    }
+
+    /// Send a prompt that expects a JSON reply and deserialize it
+    async fn complete_json<T: DeserializeOwned>(
+        &self,
+        fixture: Fixture<'_>,
+        prompt: &str,
+    ) -> Result<T, LLMError> {
+        let text = self
+            .send(fixture, None, &[ChatMessage::user(prompt)], true)
+            .await?;
+        parse_json(&text)
+    }
+
+    /// Tag an explanation and its examples with the language they were requested in
+    fn tag_explanation(&self, mut explanation: WordExplanation) -> WordExplanation {
+        explanation.examples = self.tag_examples(explanation.examples);
+        explanation.language = self.language;
+        explanation
+    }
+
+    /// Tag example sentences with the language their translations were requested in
+    fn tag_examples(&self, examples: Vec<ExampleSentence>) -> Vec<ExampleSentence> {
+        examples
+            .into_iter()
+            .map(|example| ExampleSentence {
+                language: self.language,
+                ..example
+            })
+            .collect()
+    }
+
+    /// Learner profile and report to include in every teaching prompt
+    fn learner_profile(&self) -> String {
+        let mut profile = format!("Learner profile: {}", self.user_context);
+        if !self.learner_report.trim().is_empty() {
+            profile.push_str("\n\nLearner report:\n");
+            profile.push_str(self.learner_report.trim());
+        }
+        profile
+    }
+
+    /// Check if the service is configured (has API key or runs offline)
+    pub fn is_configured(&self) -> bool {
+        matches!(self.provider, Provider::Mock(_)) || self.api_key.is_some()
+    }
+
+    /// Replace the backend, e.g. with fixtures for demo mode
+    pub fn set_provider(&mut self, provider: Provider) {
+        self.provider = provider;
+    }
+
+    /// Update the API key
+    pub fn set_api_key(&mut self, api_key: String) {
+        self.api_key = Some(api_key);
+    }
+
+    /// Record every provider call in `database`'s audit log
+    pub fn set_audit_log(&mut self, database: Arc<DatabaseService>) {
+        self.audit_log = Some(database);
+    }
+
+    /// Update user context for personalized responses
+    pub fn set_user_context(&mut self, context: String) {
+        self.user_context = context;
+    }
+
+    /// Set the language meanings, explanations and translations are written in
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    /// Update the learner report included in explanation prompts
+    pub fn set_learner_report(&mut self, report: String) {
+        self.learner_report = report;
+    }
+}
+
+/// Turn LLM alignments into byte ranges, dropping invalid or overlapping ones
+fn resolve_alignments(data: TranslationData, segment_count: usize) -> SentenceTranslation {
+    let mut alignments: Vec<PhraseAlignment> = Vec::new();
+    for alignment in data.alignments {
+        let [first, last] = alignment.segments;
+        let phrase = alignment.phrase.trim();
+        if first > last || last >= segment_count || phrase.is_empty() {
+            continue;
+        }
+        let overlaps_segments = alignments
+            .iter()
+            .any(|a| a.segments.start <= last && first < a.segments.end);
+        if overlaps_segments {
+            continue;
+        }
+
+        // The same phrase can appear twice; take the first free occurrence
+        let range = data
+            .translation
+            .match_indices(phrase)
+            .map(|(start, _)| start..start + phrase.len())
+            .find(|range| {
+                !alignments
+                    .iter()
+                    .any(|a| a.phrase.start < range.end && range.start < a.phrase.end)
+            });
+        if let Some(range) = range {
+            alignments.push(PhraseAlignment {
+                segments: first..last + 1,
+                phrase: range,
+            });
+        }
+    }
+    alignments.sort_by_key(|alignment| alignment.phrase.start);
+
+    SentenceTranslation {
+        translation: data.translation,
+        alignments,
+    }
+}
+
+/// Join rewritten sentences into a text, keeping links to existing source sentences
+fn resolve_rewrite(data: RewriteData, source_count: usize) -> GradedRewrite {
+    let mut text = String::new();
+    let mut sentence_map = Vec::new();
+    for sentence in data.sentences {
+        let sentence_text = sentence.text.trim();
+        if sentence_text.is_empty() {
+            continue;
+        }
+        text.push_str(sentence_text);
+        // Sentences are told apart by their final punctuation
+        if !sentence_text.ends_with(['。', '！', '？', '!', '?']) {
+            text.push('。');
+        }
+        sentence_map.push(sentence.original.filter(|&index| index < source_count));
+    }
+
+    GradedRewrite { text, sentence_map }
+}
+
+/// Turn the most recent (question, answer) pairs that fit in `budget` tokens into chat messages
+fn trim_history(history: &[(String, String)], budget: usize) -> Vec<ChatMessage> {
+    let mut used = 0;
+    let kept = history
+        .iter()
+        .rev()
+        .take_while(|(question, answer)| {
+            used += estimate_tokens(question) + estimate_tokens(answer);
+            used <= budget
+        })
+        .count();
+
+    history[history.len() - kept..]
+        .iter()
+        .flat_map(|(question, answer)| [ChatMessage::user(question), ChatMessage::model(answer)])
+        .collect()
+}
+
+/// Rough token count: one token per CJK character, one per four other characters
+fn estimate_tokens(text: &str) -> usize {
+    let (cjk, other) = text
+        .chars()
+        .fold((0usize, 0usize), |(cjk, other), c| {
+            if c >= '\u{3000}' {
+                (cjk + 1, other)
+            } else {
+                (cjk, other + 1)
+            }
+        });
+    cjk + other.div_ceil(4)
+}
+
+/// Parse a JSON reply, tolerating Markdown code fences around it
+fn parse_json<T: DeserializeOwned>(response: &str) -> Result<T, LLMError> {
+    let trimmed = response.trim();
+    let body = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(trimmed);
+
+    serde_json::from_str(body.trim()).map_err(|e| LLMError::ParseError(e.to_string()))
+}
+
+/// LLM service errors
+#[derive(Debug, Clone)]
+pub enum LLMError {
+    NotConfigured,
+    NetworkError(String),
+    ApiError(String),
+    ParseError(String),
+    /// The provider answered 429 Too Many Requests
+    RateLimited { retry_after: Option<Duration> },
+    /// The provider answered with a 5xx status
+    ServerError(String),
+    /// The daily token budget has been used up
+    BudgetExceeded { used: u64, budget: u64 },
+}
+
+impl LLMError {
+    /// Name of the variant, as stored in the audit log
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            LLMError::NotConfigured => "NotConfigured",
+            LLMError::NetworkError(_) => "NetworkError",
+            LLMError::ApiError(_) => "ApiError",
+            LLMError::ParseError(_) => "ParseError",
+            LLMError::RateLimited { .. } => "RateLimited",
+            LLMError::ServerError(_) => "ServerError",
+            LLMError::BudgetExceeded { .. } => "BudgetExceeded",
+        }
+    }
+
+    /// Check if the request may succeed when retried later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, LLMError::RateLimited { .. } | LLMError::ServerError(_))
+    }
+
+    /// Delay requested by the provider before retrying, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            LLMError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for LLMError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LLMError::NotConfigured => write!(f, "LLM service not configured. Please add your API key in settings."),
+            LLMError::NetworkError(msg) => write!(f, "Network error: {}", msg),
+            LLMError::ApiError(msg) => write!(f, "API error: {}", msg),
+            LLMError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            LLMError::RateLimited { .. } => write!(f, "Rate limited by the LLM provider. Please try again later."),
+            LLMError::ServerError(msg) => write!(f, "LLM provider error: {}", msg),
+            LLMError::BudgetExceeded { used, budget } => write!(
+                f,
+                "Daily token budget exceeded ({} of {} tokens used). Raise the budget in settings or try again tomorrow.",
+                used, budget
+            ),
+        }
+    }
+}
+
+/// LLM service answering from the bundled fixtures, for tests
+#[cfg(test)]
+pub(crate) fn mock_service() -> LLMService {
+    let database = Arc::new(DatabaseService::new_in_memory().expect("in-memory database"));
+    let governor = Arc::new(RequestGovernor::new(Default::default(), database));
+    let mut llm = LLMService::new(None, "Beginner learning N5".to_string(), governor);
+    llm.set_provider(Provider::Mock(MockProvider::new(constants::llm::TEST_FIXTURE_DIR)));
+    llm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::futures::executor::block_on;
+
+    fn grammar(pattern: &str, explanation: &str) -> CardType {
+        CardType::Grammar(GrammarCard {
+            pattern: pattern.to_string(),
+            pattern_reading: String::new(),
+            explanation: explanation.to_string(),
+            wrong_answers: Vec::new(),
+            example_sentences: Vec::new(),
+            jlpt_level: JLPTLevel::N4,
+            language: Language::English,
+        })
+    }
+
+    #[test]
+    fn grammar_distractors_are_deduplicated_and_topped_up() {
+        let llm = mock_service();
+        // The fixture repeats the correct answer and one distractor
+        let generate = |pool: &DistractorPool<'_>| {
+            block_on(llm.generate_grammar_card("〜ばかり", "食べたばかりです。", pool))
+        };
+
+        let others = [
+            grammar("〜たい", "want to"),
+            grammar("〜すぎる", "too much"),
+        ];
+        let mut card = generate(&DistractorPool::new(&others, None)).unwrap();
+        assert_eq!(card.wrong_answers[0], "only");
+        card.wrong_answers[1..].sort();
+        assert_eq!(card.wrong_answers, ["only", "too much", "want to"]);
+
+        let error = generate(&DistractorPool::default()).unwrap_err();
+        assert!(matches!(error, LLMError::ParseError(_)), "{:?}", error);
+    }
+}
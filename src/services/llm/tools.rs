@@ -0,0 +1,41 @@
+//! Function-calling types shared by the providers
+//!
+//! The service sends [`ToolDeclaration`]s with a request; the model may answer
+//! with [`ToolCall`]s instead of text. Their [`ToolResult`]s are sent back in
+//! the next request until the model replies with text.
+
+use serde_json::Value;
+
+/// A function the model may call
+#[derive(Debug, Clone)]
+pub struct ToolDeclaration {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// JSON schema of the arguments object
+    pub parameters: Value,
+}
+
+/// A function call requested by the model
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub name: String,
+    pub args: Value,
+    /// Opaque signature Gemini attaches to calls; must be sent back unchanged
+    pub signature: Option<String>,
+}
+
+/// Output of a function call, sent back to the model
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolResult {
+    pub name: String,
+    pub response: Value,
+}
+
+/// Runs the functions the model asks for
+pub trait ToolExecutor: Sync {
+    /// Functions offered to the model
+    fn declarations(&self) -> Vec<ToolDeclaration>;
+
+    /// Run one call; failures are reported to the model inside the result
+    fn call(&self, call: &ToolCall) -> ToolResult;
+}
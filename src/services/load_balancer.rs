@@ -0,0 +1,203 @@
+//! Interval fuzz and review load balancing
+//!
+//! Cards added together from one text are answered together and would keep
+//! coming due on the same days. Day intervals get a little random fuzz to
+//! spread them out, and the load balancer picks, within the fuzz range, the day
+//! with the fewest reviews already due according to a forecast.
+
+use crate::models::flashcard::{FlashCard, SRSData};
+use crate::services::study_day::DayRollover;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use std::collections::HashMap;
+
+/// Intervals shorter than this many days are never fuzzed
+const MIN_FUZZED_INTERVAL: u32 = 3;
+
+/// Share of the interval the due date may move by, for intervals up to a length in days
+const FUZZ_FACTORS: [(u32, f32); 3] = [(7, 0.15), (20, 0.1), (u32::MAX, 0.05)];
+
+/// Whether day intervals are fuzzed, and balanced across days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadBalancing {
+    pub fuzz: bool,
+    /// Pick the least loaded day within the fuzz range instead of a random one
+    pub balance: bool,
+}
+
+impl Default for LoadBalancing {
+    fn default() -> Self {
+        Self {
+            fuzz: true,
+            balance: true,
+        }
+    }
+}
+
+/// Days an interval may be fuzzed to, shortest first
+pub fn fuzz_range(interval: u32) -> (u32, u32) {
+    if interval < MIN_FUZZED_INTERVAL {
+        return (interval, interval);
+    }
+    let factor = FUZZ_FACTORS
+        .iter()
+        .find(|(max, _)| interval <= *max)
+        .map_or(0.0, |(_, factor)| *factor);
+    let delta = ((interval as f32 * factor).round() as u32).max(1);
+    (interval - delta, interval + delta)
+}
+
+/// Reviews due on each study day
+#[derive(Debug, Clone, Default)]
+pub struct DueForecast {
+    due: HashMap<NaiveDate, usize>,
+}
+
+impl DueForecast {
+    /// Count the reviews of `cards` due on each study day
+    ///
+    /// New, suspended and learning cards are left out: they do not come due
+    /// on a day interval.
+    pub fn new<'a>(cards: impl IntoIterator<Item = &'a FlashCard>, rollover: &DayRollover) -> Self {
+        let mut forecast = Self::default();
+        for card in cards {
+            forecast.add(&card.srs_data, rollover);
+        }
+        forecast
+    }
+
+    /// Count a card's review on the day it is due
+    pub fn add(&mut self, srs: &SRSData, rollover: &DayRollover) {
+        if let Some(day) = due_day(srs, rollover) {
+            *self.due.entry(day).or_default() += 1;
+        }
+    }
+
+    /// Stop counting a card's review, before it is rescheduled
+    pub fn remove(&mut self, srs: &SRSData, rollover: &DayRollover) {
+        if let Some(count) = due_day(srs, rollover).and_then(|day| self.due.get_mut(&day)) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    /// Reviews due on a study day
+    pub fn due_on(&self, day: NaiveDate) -> usize {
+        self.due.get(&day).copied().unwrap_or_default()
+    }
+}
+
+/// Study day a card's review is due on, if it is due on a day interval
+fn due_day(srs: &SRSData, rollover: &DayRollover) -> Option<NaiveDate> {
+    let on_interval = !srs.is_new && !srs.suspended && srs.learning.is_none();
+    on_interval.then(|| rollover.day_of(srs.next_review))
+}
+
+/// Fuzz the day interval of a card just answered at `now`
+///
+/// Cards still in learning steps keep their delay. With balancing, the least
+/// loaded day in the fuzz range is picked, ties at random.
+pub fn fuzz_interval(
+    srs: &mut SRSData,
+    now: DateTime<Utc>,
+    options: &LoadBalancing,
+    forecast: &DueForecast,
+    rollover: &DayRollover,
+    rng: &mut impl Rng,
+) {
+    if !options.fuzz || srs.learning.is_some() {
+        return;
+    }
+
+    let (shortest, longest) = fuzz_range(srs.interval);
+    let candidates: Vec<u32> = (shortest..=longest).collect();
+    let candidates = if options.balance {
+        let load =
+            |days: u32| forecast.due_on(rollover.day_of(now + Duration::days(i64::from(days))));
+        let least = candidates
+            .iter()
+            .map(|&days| load(days))
+            .min()
+            .unwrap_or_default();
+        candidates
+            .into_iter()
+            .filter(|&days| load(days) == least)
+            .collect()
+    } else {
+        candidates
+    };
+
+    if let Some(&interval) = candidates.choose(rng) {
+        srs.interval = interval;
+        srs.next_review = now + Duration::days(i64::from(interval));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn fuzz_widens_with_the_interval() {
+        // Interval, then the range it may be fuzzed to
+        let cases = [
+            (1, (1, 1)),
+            (2, (2, 2)),
+            (3, (2, 4)),
+            (7, (6, 8)),
+            (20, (18, 22)),
+            (100, (95, 105)),
+        ];
+        for (interval, range) in cases {
+            assert_eq!(fuzz_range(interval), range, "{}", interval);
+        }
+    }
+
+    #[test]
+    fn balancing_picks_the_least_loaded_day() {
+        let rollover = DayRollover {
+            starts_at: 4,
+            utc_offset: Some(FixedOffset::east_opt(0).unwrap()),
+        };
+        let now = DateTime::parse_from_rfc3339("2026-03-11T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let day = |days: i64| rollover.day_of(now + Duration::days(days));
+
+        // An interval of 10 may land 9 to 11 days out; day 10 is free
+        let mut forecast = DueForecast {
+            due: HashMap::from([(day(9), 5), (day(11), 3)]),
+        };
+        let mut rng = rand::rng();
+        for _ in 0..20 {
+            let mut srs = SRSData {
+                interval: 10,
+                is_new: false,
+                ..SRSData::new_card(now)
+            };
+            fuzz_interval(
+                &mut srs,
+                now,
+                &LoadBalancing::default(),
+                &forecast,
+                &rollover,
+                &mut rng,
+            );
+            assert_eq!(srs.interval, 10);
+            assert_eq!(srs.next_review, now + Duration::days(10));
+        }
+
+        // Answering the card moves its review to the new day
+        let srs = SRSData {
+            interval: 10,
+            is_new: false,
+            next_review: now + Duration::days(10),
+            ..SRSData::new_card(now)
+        };
+        forecast.add(&srs, &rollover);
+        assert_eq!(forecast.due_on(day(10)), 1);
+        forecast.remove(&srs, &rollover);
+        assert_eq!(forecast.due_on(day(10)), 0);
+    }
+}
@@ -4,10 +4,30 @@
 //! - LLM integration (Gemini API)
 //! - Database operations (native_db)
 //! - Text tokenization (lindera)
+//! - Batch flashcard generation from texts
+//! - Learner report inputs from review history
+//! - Local dictionary lookups and cross-checks of LLM output
+//! - Offline wrong answers for multiple-choice cards
+//! - Local functions the tutor can call (dictionary, cards, review state)
+//! - Spaced repetition scheduling with SM-2 or FSRS
+//! - Study days and daily review limits
+//! - Practice queues across decks
 
+pub mod card_generator;
+pub mod custom_study;
 pub mod llm;
 pub mod database;
+pub mod dictionary;
+pub mod distractors;
+pub mod fsrs;
+pub mod learner_report;
+pub mod load_balancer;
+pub mod scheduler;
+pub mod study_day;
+pub mod study_queue;
 pub mod tokenizer;
+pub mod tutor_tools;
+pub mod verification;
 
 // Re-export service interfaces
 pub use llm::LLMService;
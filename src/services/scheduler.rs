@@ -0,0 +1,569 @@
+//! Spaced repetition scheduling
+//!
+//! Computes the next `SRSData` of a card from a review graded on the SM-2
+//! quality scale: 0 (complete blackout) to 5 (perfect recall). Grades below 3
+//! count as failures and restart the card's repetitions. Multiple-choice
+//! answers are graded by correctness and response time; sentence writing is
+//! graded by the LLM. Decks use SM-2 or FSRS to pick the next interval.
+//!
+//! Before a new card gets day intervals it goes through short learning steps,
+//! and a forgotten card through relearning steps, so it comes back within the
+//! same session.
+
+use crate::constants::srs::{
+    DEFAULT_LEARNING_STEPS, DEFAULT_LEECH_THRESHOLD, DEFAULT_RELEARNING_STEPS, DESIRED_RETENTION,
+    FAST_ANSWER_SECONDS, MAX_QUALITY, PASSING_QUALITY,
+};
+use crate::models::flashcard::{LearningStep, ReviewLog, SRSData, SchedulerKind};
+use crate::services::fsrs::{self, FsrsParameters, Rating};
+use chrono::{DateTime, Duration, Utc};
+
+/// Quality of a wrong multiple-choice answer: recalled incorrectly
+const WRONG_ANSWER_QUALITY: u8 = 1;
+
+/// Quality of a correct multiple-choice answer given after `elapsed`
+///
+/// Quick answers count as perfect recall, slower ones as recalled with hesitation.
+pub fn multiple_choice_quality(correct: bool, elapsed: std::time::Duration) -> u8 {
+    if !correct {
+        WRONG_ANSWER_QUALITY
+    } else if elapsed.as_secs() < FAST_ANSWER_SECONDS {
+        MAX_QUALITY
+    } else {
+        MAX_QUALITY - 1
+    }
+}
+
+/// Ease factors never drop below this
+const MIN_EASE_FACTOR: f32 = 1.3;
+
+/// Delays before a card is shown again while it is learned or relearned
+#[derive(Debug, Clone, PartialEq)]
+pub struct LearningSteps {
+    /// Steps of a new card before its first day interval
+    pub learning: Vec<Duration>,
+    /// Steps of a forgotten card before it returns to day intervals
+    pub relearning: Vec<Duration>,
+}
+
+impl Default for LearningSteps {
+    fn default() -> Self {
+        Self {
+            learning: parse_steps(DEFAULT_LEARNING_STEPS).unwrap_or_default(),
+            relearning: parse_steps(DEFAULT_RELEARNING_STEPS).unwrap_or_default(),
+        }
+    }
+}
+
+/// Parse steps written like "1m 10m 1h", in seconds, minutes, hours or days
+///
+/// A bare number counts as minutes. Returns `None` unless every step is a
+/// positive duration; an empty text means no steps.
+pub fn parse_steps(text: &str) -> Option<Vec<Duration>> {
+    text.split([' ', ','])
+        .filter(|step| !step.is_empty())
+        .map(|step| {
+            let (amount, unit) = match step.find(|c: char| !c.is_ascii_digit()) {
+                Some(split) => step.split_at(split),
+                None => (step, "m"),
+            };
+            let amount: i64 = amount.parse().ok().filter(|&amount| amount > 0)?;
+            match unit {
+                "s" => Some(Duration::seconds(amount)),
+                "m" => Some(Duration::minutes(amount)),
+                "h" => Some(Duration::hours(amount)),
+                "d" => Some(Duration::days(amount)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// When a card counts as a leech
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeechPolicy {
+    /// Lapses after which a card is tagged as a leech
+    pub threshold: u32,
+    /// Suspend leeches as well as tagging them
+    pub suspend: bool,
+}
+
+impl Default for LeechPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_LEECH_THRESHOLD,
+            suspend: false,
+        }
+    }
+}
+
+/// Tag a card as a leech once its lapses reach the threshold, suspending it if asked
+///
+/// Returns whether the card just became a leech.
+pub fn tag_leech(srs: &mut SRSData, policy: &LeechPolicy) -> bool {
+    if srs.leech || policy.threshold == 0 || srs.lapses < policy.threshold {
+        return false;
+    }
+    srs.leech = true;
+    srs.suspended = policy.suspend;
+    true
+}
+
+/// Untag a leech whose content was fixed, keeping its schedule
+///
+/// Lapses start over so the fixed card gets a full threshold of chances.
+pub fn clear_leech(srs: &mut SRSData) {
+    srs.lapses = 0;
+    srs.leech = false;
+    srs.suspended = false;
+}
+
+/// Wrong answer picked most often in a card's failed reviews, if any was recorded
+pub fn usual_mistake(reviews: &[ReviewLog]) -> Option<&str> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for answer in reviews
+        .iter()
+        .filter(|review| review.quality < PASSING_QUALITY)
+        .filter_map(|review| review.answer.as_deref())
+    {
+        match counts.iter_mut().find(|(seen, _)| *seen == answer) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((answer, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(answer, _)| answer)
+}
+
+/// Short description of a delay until the next review, e.g. "10m" or "4d"
+pub fn format_delay(delay: Duration) -> String {
+    let minutes = delay.num_minutes();
+    let days = delay.num_days();
+    if minutes < 1 {
+        "<1m".to_string()
+    } else if minutes < 60 {
+        format!("{}m", minutes)
+    } else if days < 1 {
+        format!("{}h", delay.num_hours())
+    } else if days < 30 {
+        format!("{}d", days)
+    } else if days < 365 {
+        format!("{:.1}mo", days as f32 / 30.0)
+    } else {
+        format!("{:.1}y", days as f32 / 365.0)
+    }
+}
+
+/// Short description of a range of delays, e.g. "3–5d", or one delay when both ends match
+pub fn format_delay_range(shortest: Duration, longest: Duration) -> String {
+    let (shortest, longest) = (format_delay(shortest), format_delay(longest));
+    if shortest == longest {
+        return shortest;
+    }
+    let unit_start = |text: &str| text.trim_end_matches(char::is_alphabetic).len();
+    let (short_unit, long_unit) = (
+        &shortest[unit_start(&shortest)..],
+        &longest[unit_start(&longest)..],
+    );
+    if short_unit == long_unit {
+        format!("{}–{}", &shortest[..unit_start(&shortest)], longest)
+    } else {
+        format!("{}–{}", shortest, longest)
+    }
+}
+
+/// Write steps the way [`parse_steps`] reads them, in the largest whole unit
+pub fn format_steps(steps: &[Duration]) -> String {
+    steps
+        .iter()
+        .map(|step| {
+            let seconds = step.num_seconds();
+            [(86_400, "d"), (3_600, "h"), (60, "m")]
+                .into_iter()
+                .find(|(unit, _)| seconds % unit == 0)
+                .map_or(format!("{}s", seconds), |(unit, suffix)| {
+                    format!("{}{}", seconds / unit, suffix)
+                })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Apply one answer graded `quality` at `now`, moving the card through its steps
+///
+/// A new card starts on the first learning step. Passing a step moves the card
+/// to the next one and failing sends it back to the first; passing the last
+/// step graduates it to the day intervals of the deck's scheduler. A reviewed
+/// card that is forgotten enters the relearning steps, and graduates from them
+/// with the interval its lapse was given.
+pub fn answer(
+    srs: &SRSData,
+    quality: u8,
+    now: DateTime<Utc>,
+    scheduler: SchedulerKind,
+    parameters: &FsrsParameters,
+    steps: &LearningSteps,
+) -> SRSData {
+    let passed = quality >= PASSING_QUALITY;
+    let step = match srs.learning {
+        Some(step) => step,
+        None if srs.is_new => LearningStep {
+            index: 0,
+            relearning: false,
+        },
+        None => {
+            let mut next = review(srs, quality, now, scheduler, parameters);
+            if !passed {
+                next.lapses += 1;
+            }
+            return match steps.relearning.first() {
+                Some(&delay) if !passed => SRSData {
+                    next_review: now + delay,
+                    learning: Some(LearningStep {
+                        index: 0,
+                        relearning: true,
+                    }),
+                    ..next
+                },
+                _ => next,
+            };
+        }
+    };
+
+    let delays = if step.relearning {
+        &steps.relearning
+    } else {
+        &steps.learning
+    };
+    let index = if passed { step.index + 1 } else { 0 };
+    if let Some(&delay) = delays.get(index) {
+        return SRSData {
+            next_review: now + delay,
+            is_new: false,
+            learning: Some(LearningStep { index, ..step }),
+            ..srs.clone()
+        };
+    }
+
+    // Graduate: the lapse already set the interval of a relearned card
+    let graduating = SRSData {
+        learning: None,
+        ..srs.clone()
+    };
+    if step.relearning {
+        SRSData {
+            next_review: now + Duration::days(i64::from(graduating.interval)),
+            ..graduating
+        }
+    } else {
+        review(&graduating, quality, now, scheduler, parameters)
+    }
+}
+
+/// Apply one review graded `quality` at `now` with the deck's scheduler
+///
+/// The SM-2 fields and the FSRS memory state are both kept up to date, so a
+/// deck can switch schedulers without losing progress; the scheduler only
+/// picks the next interval.
+pub fn review(
+    srs: &SRSData,
+    quality: u8,
+    now: DateTime<Utc>,
+    scheduler: SchedulerKind,
+    parameters: &FsrsParameters,
+) -> SRSData {
+    let mut next = sm2(srs, quality, now);
+    let state = parameters.next_state(srs.fsrs.as_ref(), Rating::from_quality(quality), now);
+    if scheduler == SchedulerKind::Fsrs {
+        next.interval = fsrs::interval_days(state.stability, DESIRED_RETENTION);
+        next.next_review = now + Duration::days(i64::from(next.interval));
+    }
+    next.fsrs = Some(state);
+    next
+}
+
+/// Apply one review graded `quality` at `now` with the SM-2 rules
+pub fn sm2(srs: &SRSData, quality: u8, now: DateTime<Utc>) -> SRSData {
+    let quality = quality.min(MAX_QUALITY);
+
+    let (repetitions, interval) = if quality >= PASSING_QUALITY {
+        let interval = match srs.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (srs.interval as f32 * srs.ease_factor).round() as u32,
+        };
+        (srs.repetitions + 1, interval)
+    } else {
+        (0, 1)
+    };
+
+    let missed = f32::from(MAX_QUALITY - quality);
+    let ease_factor =
+        (srs.ease_factor + 0.1 - missed * (0.08 + missed * 0.02)).max(MIN_EASE_FACTOR);
+
+    SRSData {
+        ease_factor,
+        interval,
+        repetitions,
+        next_review: now + Duration::days(i64::from(interval)),
+        is_new: false,
+        learning: None,
+        ..srs.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_review_restarts_repetitions() {
+        let now = Utc::now();
+        let learned = SRSData {
+            interval: 15,
+            repetitions: 3,
+            is_new: false,
+            ..SRSData::new_card(now)
+        };
+
+        let passed = sm2(&learned, 4, now);
+        assert_eq!((passed.repetitions, passed.interval), (4, 38));
+        assert_eq!(passed.next_review, now + Duration::days(38));
+
+        let failed = sm2(&learned, 1, now);
+        assert_eq!((failed.repetitions, failed.interval), (0, 1));
+        assert!((failed.ease_factor - 1.96).abs() < 1e-4);
+    }
+
+    #[test]
+    fn review_sequences_follow_sm2() {
+        // Qualities in order, then the resulting repetitions, interval and ease factor
+        let cases: [(&[u8], u32, u32, f32); 6] = [
+            (&[5, 5, 5], 3, 16, 2.8),
+            (&[4, 4, 4, 4], 4, 38, 2.5),
+            (&[3, 3, 3], 3, 13, 2.08),
+            (&[4, 4, 1], 0, 1, 1.96),
+            (&[4, 4, 1, 4, 4], 2, 6, 1.96),
+            (&[0, 0, 0, 0, 0], 0, 1, MIN_EASE_FACTOR),
+        ];
+        let now = Utc::now();
+
+        for (qualities, repetitions, interval, ease_factor) in cases {
+            let mut srs = SRSData::new_card(now);
+            for &quality in qualities {
+                srs = sm2(&srs, quality, now);
+            }
+
+            assert_eq!(
+                (srs.repetitions, srs.interval),
+                (repetitions, interval),
+                "{:?}",
+                qualities
+            );
+            assert!(
+                (srs.ease_factor - ease_factor).abs() < 1e-4,
+                "{:?}: ease factor {}",
+                qualities,
+                srs.ease_factor
+            );
+            assert_eq!(srs.next_review, now + Duration::days(i64::from(interval)));
+            assert!(!srs.is_new);
+        }
+    }
+
+    #[test]
+    fn multiple_choice_answers_map_to_qualities() {
+        let cases = [
+            (true, 2, 5),
+            (true, FAST_ANSWER_SECONDS, 4),
+            (true, 60, 4),
+            (false, 2, 1),
+            (false, 60, 1),
+        ];
+
+        for (correct, seconds, quality) in cases {
+            assert_eq!(
+                multiple_choice_quality(correct, std::time::Duration::from_secs(seconds)),
+                quality,
+                "correct: {}, {} s",
+                correct,
+                seconds
+            );
+        }
+    }
+
+    #[test]
+    fn fsrs_decks_take_the_fsrs_interval() {
+        let now = Utc::now();
+        let new_card = SRSData::new_card(now);
+        let parameters = FsrsParameters::default();
+
+        let sm2_deck = review(&new_card, 4, now, SchedulerKind::Sm2, &parameters);
+        let fsrs_deck = review(&new_card, 4, now, SchedulerKind::Fsrs, &parameters);
+
+        assert_eq!(sm2_deck.interval, 1);
+        assert_eq!(fsrs_deck.interval, 4);
+        assert_eq!(sm2_deck.fsrs, fsrs_deck.fsrs);
+        assert!(sm2_deck.fsrs.is_some());
+    }
+
+    #[test]
+    fn new_cards_step_through_learning_before_day_intervals() {
+        let start = Utc::now();
+        let steps = LearningSteps::default();
+        let parameters = FsrsParameters::default();
+        let mut srs = SRSData::new_card(start);
+
+        // Quality, minutes since the start, then the step index and the delay until the next review
+        let answers = [
+            (4, 0, Some(1), Duration::minutes(10)),
+            (1, 10, Some(0), Duration::minutes(1)),
+            (4, 11, Some(1), Duration::minutes(10)),
+            (4, 21, None, Duration::days(1)),
+        ];
+        for (quality, minutes, index, delay) in answers {
+            let now = start + Duration::minutes(minutes);
+            srs = answer(&srs, quality, now, SchedulerKind::Sm2, &parameters, &steps);
+            assert_eq!(
+                srs.learning.map(|step| step.index),
+                index,
+                "at {} min",
+                minutes
+            );
+            assert_eq!(srs.next_review, now + delay, "at {} min", minutes);
+            assert!(!srs.is_new);
+        }
+        assert_eq!(srs.repetitions, 1);
+
+        // A lapse relearns for ten minutes, then keeps the lapse interval
+        let lapse = start + Duration::days(1);
+        srs = answer(&srs, 1, lapse, SchedulerKind::Sm2, &parameters, &steps);
+        assert_eq!(
+            srs.learning,
+            Some(LearningStep {
+                index: 0,
+                relearning: true
+            })
+        );
+        assert_eq!(srs.next_review, lapse + Duration::minutes(10));
+        assert_eq!((srs.repetitions, srs.interval, srs.lapses), (0, 1, 1));
+
+        let relearned = lapse + Duration::minutes(10);
+        srs = answer(&srs, 4, relearned, SchedulerKind::Sm2, &parameters, &steps);
+        assert_eq!(srs.learning, None);
+        assert_eq!(srs.next_review, relearned + Duration::days(1));
+
+        // Without steps a card gets day intervals straight away
+        let no_steps = LearningSteps {
+            learning: Vec::new(),
+            relearning: Vec::new(),
+        };
+        let new_card = SRSData {
+            is_new: true,
+            learning: None,
+            ..srs.clone()
+        };
+        let graduated = answer(
+            &new_card,
+            1,
+            start,
+            SchedulerKind::Sm2,
+            &parameters,
+            &no_steps,
+        );
+        assert_eq!(graduated.learning, None);
+        assert_eq!(graduated.next_review, start + Duration::days(1));
+    }
+
+    #[test]
+    fn steps_and_delays_are_written_with_units() {
+        let cases = [
+            (
+                "1m 10m",
+                Some(vec![Duration::minutes(1), Duration::minutes(10)]),
+            ),
+            (
+                "30s, 2h 1d",
+                Some(vec![
+                    Duration::seconds(30),
+                    Duration::hours(2),
+                    Duration::days(1),
+                ]),
+            ),
+            ("15", Some(vec![Duration::minutes(15)])),
+            ("  ", Some(Vec::new())),
+            ("0m", None),
+            ("10x", None),
+            ("m", None),
+        ];
+
+        for (text, steps) in cases {
+            assert_eq!(parse_steps(text), steps, "{:?}", text);
+        }
+        assert_eq!(
+            format_steps(&parse_steps("90s 60m 1440").unwrap()),
+            "90s 1h 1d"
+        );
+
+        let delays = [
+            (Duration::seconds(30), "<1m"),
+            (Duration::minutes(10), "10m"),
+            (Duration::hours(5), "5h"),
+            (Duration::days(4), "4d"),
+            (Duration::days(45), "1.5mo"),
+            (Duration::days(730), "2.0y"),
+        ];
+        for (delay, text) in delays {
+            assert_eq!(format_delay(delay), text);
+        }
+        assert_eq!(
+            format_delay_range(Duration::days(3), Duration::days(5)),
+            "3–5d"
+        );
+        assert_eq!(
+            format_delay_range(Duration::days(28), Duration::days(32)),
+            "28d–1.1mo"
+        );
+    }
+
+    #[test]
+    fn repeated_lapses_tag_a_leech() {
+        let now = Utc::now();
+        let steps = LearningSteps::default();
+        let parameters = FsrsParameters::default();
+        let policy = LeechPolicy {
+            threshold: 3,
+            suspend: true,
+        };
+        let mut srs = SRSData {
+            is_new: false,
+            interval: 10,
+            ..SRSData::new_card(now)
+        };
+
+        let fail = |srs: &SRSData| answer(srs, 1, now, SchedulerKind::Sm2, &parameters, &steps);
+        let pass = |srs: &SRSData| answer(srs, 4, now, SchedulerKind::Sm2, &parameters, &steps);
+
+        // Failing again during the relearning steps is not another lapse
+        srs = fail(&fail(&srs));
+        assert_eq!(srs.lapses, 1);
+        assert!(!tag_leech(&mut srs, &policy));
+
+        srs = fail(&pass(&srs));
+        assert_eq!(srs.lapses, 2);
+        assert!(!tag_leech(&mut srs, &policy));
+
+        srs = fail(&pass(&srs));
+        assert_eq!(srs.lapses, 3);
+        assert!(tag_leech(&mut srs, &policy));
+        assert!(srs.suspended);
+        assert!(!tag_leech(&mut srs, &policy));
+
+        clear_leech(&mut srs);
+        assert!(!srs.leech && !srs.suspended);
+        assert_eq!(srs.lapses, 0);
+    }
+}
@@ -0,0 +1,299 @@
+//! Study days and daily limits
+//!
+//! Daily review and new-card limits are counted per study day rather than per
+//! calendar day: a study day starts at a configurable hour in the learner's
+//! timezone, so a session past midnight still counts toward the evening it
+//! started in. Counts come from the stored `ReviewLog`s that rescheduled
+//! their card, so custom sessions that leave scheduling alone do not count.
+
+use crate::constants::srs::{
+    DEFAULT_DAILY_REVIEW_LIMIT, DEFAULT_DAY_STARTS_AT, DEFAULT_NEW_CARDS_PER_DAY, PASSING_QUALITY,
+};
+use crate::models::flashcard::{FlashCard, ReviewLog};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, Offset, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// When a study day starts, and in which timezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRollover {
+    /// Hour of the day, 0-23, at which the next study day starts
+    pub starts_at: u32,
+    /// Offset from UTC, or the system timezone when `None`
+    pub utc_offset: Option<FixedOffset>,
+}
+
+impl Default for DayRollover {
+    fn default() -> Self {
+        Self {
+            starts_at: DEFAULT_DAY_STARTS_AT,
+            utc_offset: None,
+        }
+    }
+}
+
+impl DayRollover {
+    /// Study day that the moment `at` belongs to
+    pub fn day_of(&self, at: DateTime<Utc>) -> NaiveDate {
+        let offset = self.offset_at(at);
+        (at.with_timezone(&offset) - Duration::hours(i64::from(self.starts_at))).date_naive()
+    }
+
+    /// Moment the study day containing `at` started
+    pub fn day_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let offset = self.offset_at(at);
+        let start = self
+            .day_of(at)
+            .and_hms_opt(self.starts_at, 0, 0)
+            .unwrap_or_default();
+        offset
+            .from_local_datetime(&start)
+            .single()
+            .map_or(at, |start| start.with_timezone(&Utc))
+    }
+
+    fn offset_at(&self, at: DateTime<Utc>) -> FixedOffset {
+        self.utc_offset
+            .unwrap_or_else(|| Local.offset_from_utc_datetime(&at.naive_utc()).fix())
+    }
+}
+
+/// Parse a UTC offset like "+9", "-05:30" or "UTC+9"; an empty text means the system timezone
+///
+/// Returns `None` when the text is not a valid offset.
+pub fn parse_utc_offset(text: &str) -> Option<Option<FixedOffset>> {
+    let text = text.trim();
+    let text = text.strip_prefix("UTC").unwrap_or(text).trim();
+    if text.is_empty() {
+        return Some(None);
+    }
+
+    let (sign, rest) = if let Some(rest) = text.strip_prefix('+') {
+        (1, rest)
+    } else {
+        (-1, text.strip_prefix('-')?)
+    };
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let hours: i32 = hours.parse().ok().filter(|hours| *hours <= 14)?;
+    let minutes: i32 = minutes.parse().ok().filter(|minutes| *minutes < 60)?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(Some)
+}
+
+/// Write a UTC offset the way [`parse_utc_offset`] reads it
+pub fn format_utc_offset(offset: Option<FixedOffset>) -> String {
+    offset.map(|offset| offset.to_string()).unwrap_or_default()
+}
+
+/// Reviews and new cards allowed per study day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyLimits {
+    pub reviews: usize,
+    pub new_cards: usize,
+}
+
+impl Default for DailyLimits {
+    fn default() -> Self {
+        Self {
+            reviews: DEFAULT_DAILY_REVIEW_LIMIT,
+            new_cards: DEFAULT_NEW_CARDS_PER_DAY,
+        }
+    }
+}
+
+/// Cards studied so far in the current study day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DailyCounts {
+    /// Cards reviewed today that were first studied on an earlier day
+    pub reviews: usize,
+    /// Cards studied for the first time today
+    pub new_cards: usize,
+}
+
+/// Ids of the cards studied in the study day containing `now`
+pub fn studied_today<'a>(
+    history: &'a [ReviewLog],
+    rollover: &DayRollover,
+    now: DateTime<Utc>,
+) -> HashSet<&'a str> {
+    let today = rollover.day_start(now);
+    history
+        .iter()
+        .filter(|review| review.rescheduled && review.reviewed_at >= today)
+        .map(|review| review.card_id.as_str())
+        .collect()
+}
+
+impl DailyCounts {
+    /// Count the cards studied in the study day containing `now`
+    pub fn today(history: &[ReviewLog], rollover: &DayRollover, now: DateTime<Utc>) -> Self {
+        let today = rollover.day_start(now);
+        let mut first_reviews: HashMap<&str, DateTime<Utc>> = HashMap::new();
+        for review in history.iter().filter(|review| review.rescheduled) {
+            let first = first_reviews
+                .entry(review.card_id.as_str())
+                .or_insert(review.reviewed_at);
+            *first = (*first).min(review.reviewed_at);
+        }
+        let studied_today = studied_today(history, rollover, now);
+
+        let new_cards = studied_today
+            .iter()
+            .filter(|card_id| first_reviews[*card_id] >= today)
+            .count();
+        Self {
+            reviews: studied_today.len() - new_cards,
+            new_cards,
+        }
+    }
+}
+
+/// Answers given in one study day, crammed ones included
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DayStats {
+    pub answers: usize,
+    /// Answers graded at or above `PASSING_QUALITY`
+    pub correct: usize,
+    /// Mean time taken per timed answer
+    pub average_response: Option<Duration>,
+}
+
+impl DayStats {
+    pub fn new(reviews: &[ReviewLog]) -> Self {
+        let times: Vec<u64> = reviews
+            .iter()
+            .filter_map(|review| review.response_ms)
+            .collect();
+        let average_response = (!times.is_empty()).then(|| {
+            Duration::milliseconds((times.iter().sum::<u64>() / times.len() as u64) as i64)
+        });
+        Self {
+            answers: reviews.len(),
+            correct: reviews
+                .iter()
+                .filter(|review| review.quality >= PASSING_QUALITY)
+                .count(),
+            average_response,
+        }
+    }
+}
+
+/// Cards to study now within what is left of today's limits
+///
+/// Due reviews come first, most overdue first, followed by new cards. Cards in
+/// learning steps are always included, since they were already started, and
+/// suspended cards never are.
+pub fn within_limits(
+    cards: Vec<FlashCard>,
+    limits: &DailyLimits,
+    counts: &DailyCounts,
+    now: DateTime<Utc>,
+) -> Vec<FlashCard> {
+    let (mut new_cards, mut due): (Vec<_>, Vec<_>) = cards
+        .into_iter()
+        .filter(|card| !card.srs_data.suspended)
+        .filter(|card| card.srs_data.is_new || card.srs_data.next_review <= now)
+        .partition(|card| card.srs_data.is_new);
+    due.sort_by_key(|card| card.srs_data.next_review);
+    new_cards.truncate(limits.new_cards.saturating_sub(counts.new_cards));
+
+    let mut reviews_left = limits.reviews.saturating_sub(counts.reviews);
+    due.retain(|card| {
+        if card.srs_data.learning.is_some() {
+            return true;
+        }
+        let keep = reviews_left > 0;
+        reviews_left = reviews_left.saturating_sub(1);
+        keep
+    });
+
+    due.extend(new_cards);
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::flashcard::SchedulerKind;
+
+    #[test]
+    fn late_sessions_count_toward_the_previous_day() {
+        let tokyo = parse_utc_offset("+9").unwrap();
+        let rollover = DayRollover {
+            starts_at: 4,
+            utc_offset: tokyo,
+        };
+        let at = |text: &str| DateTime::parse_from_rfc3339(text).unwrap().to_utc();
+        let date = |text: &str| NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap();
+
+        // Local time in Tokyo, then the study day it belongs to
+        let cases = [
+            ("2026-03-10T23:30:00+09:00", "2026-03-10"),
+            ("2026-03-11T01:30:00+09:00", "2026-03-10"),
+            ("2026-03-11T04:00:00+09:00", "2026-03-11"),
+            ("2026-03-11T12:00:00+09:00", "2026-03-11"),
+        ];
+        for (time, day) in cases {
+            assert_eq!(rollover.day_of(at(time)), date(day), "{}", time);
+        }
+        assert_eq!(
+            rollover.day_start(at("2026-03-11T01:30:00+09:00")),
+            at("2026-03-10T04:00:00+09:00")
+        );
+
+        assert_eq!(parse_utc_offset(""), Some(None));
+        assert_eq!(
+            parse_utc_offset("UTC-05:30"),
+            Some(FixedOffset::west_opt(5 * 3600 + 1800))
+        );
+        assert_eq!(parse_utc_offset("9"), None);
+        assert_eq!(format_utc_offset(tokyo), "+09:00");
+    }
+
+    #[test]
+    fn counts_split_new_cards_from_reviews() {
+        let rollover = DayRollover {
+            starts_at: 4,
+            utc_offset: Some(FixedOffset::east_opt(0).unwrap()),
+        };
+        let now = DateTime::parse_from_rfc3339("2026-03-11T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let review = |card: &str, hours_ago: i64| ReviewLog {
+            id: format!("{}-{}", card, hours_ago),
+            card_id: card.to_string(),
+            day: rollover
+                .day_of(now - Duration::hours(hours_ago))
+                .to_string(),
+            quality: 4,
+            answer: None,
+            response_ms: None,
+            previous_interval: 0,
+            interval: 1,
+            ease_factor: 2.5,
+            scheduler: SchedulerKind::Sm2,
+            rescheduled: true,
+            reviewed_at: now - Duration::hours(hours_ago),
+        };
+        let crammed = ReviewLog {
+            rescheduled: false,
+            ..review("crammed", 1)
+        };
+
+        // "old" was first studied yesterday, "fresh" twice today, "early" before
+        // the rollover; "crammed" was answered in a session that did not reschedule it
+        let history = [
+            review("old", 30),
+            review("old", 1),
+            review("fresh", 2),
+            review("fresh", 1),
+            review("early", 9),
+            crammed,
+        ];
+        assert_eq!(
+            DailyCounts::today(&history, &rollover, now),
+            DailyCounts {
+                reviews: 1,
+                new_cards: 1
+            }
+        );
+    }
+}
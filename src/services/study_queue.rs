@@ -0,0 +1,278 @@
+//! Practice queue builder
+//!
+//! Gathers the due reviews and new cards of one or several decks into the
+//! order they are practiced in. Reviews are ordered by due date or shuffled,
+//! new cards are mixed in between them, and siblings (cards with the same word
+//! or pattern on the front) are buried until the next study day so they do not
+//! give each other away.
+
+use crate::constants::srs::DEFAULT_REVIEWS_PER_NEW_CARD;
+use crate::models::flashcard::FlashCard;
+use crate::services::study_day::{DailyCounts, DailyLimits, within_limits};
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+/// Order reviews are practiced in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueOrder {
+    /// Most overdue first
+    #[default]
+    DueDate,
+    Random,
+}
+
+impl QueueOrder {
+    pub const ALL: [QueueOrder; 2] = [QueueOrder::DueDate, QueueOrder::Random];
+
+    /// Name stored in settings
+    pub fn tag(&self) -> &'static str {
+        match self {
+            QueueOrder::DueDate => "due",
+            QueueOrder::Random => "random",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueueOrder::DueDate => "Due date",
+            QueueOrder::Random => "Random",
+        }
+    }
+
+    /// Parse a stored order
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|order| order.tag() == tag.trim())
+    }
+}
+
+/// How a practice queue is built
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueOptions {
+    pub order: QueueOrder,
+    /// Reviews shown between two new cards; 0 shows new cards first
+    pub reviews_per_new_card: usize,
+    /// Leave out cards sharing a front with a card studied today or earlier in the queue
+    pub bury_siblings: bool,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        Self {
+            order: QueueOrder::default(),
+            reviews_per_new_card: DEFAULT_REVIEWS_PER_NEW_CARD,
+            bury_siblings: true,
+        }
+    }
+}
+
+/// Build the practice queue from the cards of the chosen decks
+///
+/// `studied_today` holds the cards of any deck already studied this study
+/// day. Cards are only buried by a sibling that is itself practiced today, and
+/// buried siblings do not count toward the daily limits; cards in learning
+/// steps are never buried, since they were already started.
+pub fn build(
+    mut cards: Vec<FlashCard>,
+    studied_today: &[FlashCard],
+    limits: &DailyLimits,
+    counts: &DailyCounts,
+    options: &QueueOptions,
+    now: DateTime<Utc>,
+) -> Vec<FlashCard> {
+    // Reviews before new cards so a sibling pair keeps the card already started
+    cards.sort_by(|a, b| {
+        (
+            a.srs_data.is_new,
+            a.srs_data.next_review,
+            a.created_at,
+            &a.id,
+        )
+            .cmp(&(
+                b.srs_data.is_new,
+                b.srs_data.next_review,
+                b.created_at,
+                &b.id,
+            ))
+    });
+
+    // Burying frees room within the limits, which may let in more siblings to bury
+    let queue = loop {
+        let queue = within_limits(cards.clone(), limits, counts, now);
+        if !options.bury_siblings {
+            break queue;
+        }
+        let buried = buried_siblings(&cards, &queue, studied_today);
+        if buried.is_empty() {
+            break queue;
+        }
+        cards.retain(|card| !buried.contains(card.id.as_str()));
+    };
+
+    let (mut new_cards, mut reviews): (Vec<_>, Vec<_>) =
+        queue.into_iter().partition(|card| card.srs_data.is_new);
+    if options.order == QueueOrder::Random {
+        let mut rng = rand::rng();
+        reviews.shuffle(&mut rng);
+        new_cards.shuffle(&mut rng);
+    }
+
+    interleave(reviews, new_cards, options.reviews_per_new_card)
+}
+
+/// Ids of the queued cards sharing a front with a card studied today or queued before them
+///
+/// `cards` gives the order in which siblings claim their front.
+fn buried_siblings(
+    cards: &[FlashCard],
+    queue: &[FlashCard],
+    studied_today: &[FlashCard],
+) -> HashSet<String> {
+    let queued: HashSet<&str> = queue.iter().map(|card| card.id.as_str()).collect();
+    let studied: HashSet<&str> = studied_today.iter().map(|card| card.id.as_str()).collect();
+    let mut fronts: HashSet<&str> = studied_today
+        .iter()
+        .map(|card| card.card_type.front())
+        .collect();
+    cards
+        .iter()
+        .filter(|card| queued.contains(card.id.as_str()))
+        .filter(|card| {
+            card.srs_data.learning.is_none()
+                && !studied.contains(card.id.as_str())
+                && !fronts.insert(card.card_type.front())
+        })
+        .map(|card| card.id.clone())
+        .collect()
+}
+
+/// Put a new card after every `reviews_per_new_card` reviews; leftovers go last
+fn interleave(
+    reviews: Vec<FlashCard>,
+    new_cards: Vec<FlashCard>,
+    reviews_per_new_card: usize,
+) -> Vec<FlashCard> {
+    if reviews_per_new_card == 0 {
+        return new_cards.into_iter().chain(reviews).collect();
+    }
+
+    let mut queue = Vec::with_capacity(reviews.len() + new_cards.len());
+    let mut new_cards = new_cards.into_iter();
+    for (i, review) in reviews.into_iter().enumerate() {
+        queue.push(review);
+        if (i + 1) % reviews_per_new_card == 0 {
+            queue.extend(new_cards.next());
+        }
+    }
+    queue.extend(new_cards);
+    queue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::flashcard::{CardType, SRSData, VocabularyCard};
+    use crate::models::{JLPTLevel, Language};
+    use chrono::Duration;
+
+    fn card(id: &str, front: &str, is_new: bool, now: DateTime<Utc>) -> FlashCard {
+        FlashCard {
+            id: id.to_string(),
+            deck_id: "deck".to_string(),
+            card_type: CardType::Vocabulary(VocabularyCard {
+                kanji: front.to_string(),
+                hiragana: String::new(),
+                romaji: String::new(),
+                meaning: String::new(),
+                wrong_answers: Vec::new(),
+                example_sentences: Vec::new(),
+                jlpt_level: JLPTLevel::N5,
+                language: Language::English,
+            }),
+            srs_data: SRSData {
+                interval: 1,
+                repetitions: u32::from(!is_new),
+                next_review: now - Duration::hours(1),
+                is_new,
+                ..SRSData::new_card(now)
+            },
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn new_cards_are_mixed_in_and_siblings_buried() {
+        let now = Utc::now();
+        let cards = vec![
+            card("new-1", "猫", true, now),
+            card("new-2", "犬", true, now),
+            card("review-1", "水", false, now),
+            card("review-2", "火", false, now),
+            card("review-3", "木", false, now),
+            card("review-4", "金", false, now),
+            // Siblings of a review, and of a card studied earlier today in another deck
+            card("sibling-new", "水", true, now),
+            card("sibling-studied", "山", false, now),
+        ];
+        let studied = card("studied", "山", false, now);
+
+        let options = QueueOptions {
+            order: QueueOrder::DueDate,
+            reviews_per_new_card: 2,
+            bury_siblings: true,
+        };
+        let queue = build(
+            cards,
+            &[studied],
+            &DailyLimits::default(),
+            &DailyCounts::default(),
+            &options,
+            now,
+        );
+
+        let ids: Vec<&str> = queue.iter().map(|card| card.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "review-1", "review-2", "new-1", "review-3", "review-4", "new-2"
+            ]
+        );
+    }
+
+    #[test]
+    fn siblings_not_studied_today_do_not_bury() {
+        let now = Utc::now();
+        let mut review_next_week = card("review", "水", false, now);
+        review_next_week.srs_data.next_review = now + Duration::days(7);
+        let mut over_limit = card("over-limit", "火", false, now);
+        over_limit.srs_data.next_review = now - Duration::minutes(1);
+        let cards = vec![
+            review_next_week,
+            card("due", "木", false, now),
+            over_limit,
+            card("new-2-water", "水", true, now),
+            card("new-3-fire", "火", true, now),
+            card("new-1-wood", "木", true, now),
+        ];
+        let limits = DailyLimits {
+            reviews: 1,
+            new_cards: 2,
+        };
+
+        let queue = build(
+            cards,
+            &[],
+            &limits,
+            &DailyCounts::default(),
+            &QueueOptions::default(),
+            now,
+        );
+
+        // The wood card is buried by its due review, freeing a slot for the fire card
+        let ids: Vec<&str> = queue.iter().map(|card| card.id.as_str()).collect();
+        assert_eq!(ids, ["due", "new-2-water", "new-3-fire"]);
+    }
+}
@@ -0,0 +1,353 @@
+//! Local functions the tutor can call while answering questions
+//!
+//! Gives the LLM read access to the dictionary, the user's cards and their
+//! review state, and lets it add cards to the tutor deck, so answers can be
+//! grounded in what the learner has actually studied.
+
+use crate::constants;
+use crate::models::flashcard::{FlashCard, ReviewLog};
+use crate::models::{CardType, JLPTLevel, Language, VocabularyCard};
+use crate::services::DatabaseService;
+use crate::services::database::DatabaseError;
+use crate::services::dictionary::{Dictionary, DictionaryEntry};
+use crate::services::llm::tools::{ToolCall, ToolDeclaration, ToolExecutor, ToolResult};
+use chrono::Utc;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Tool implementations backed by the database and the optional dictionary
+pub struct TutorTools {
+    database: Arc<DatabaseService>,
+    dictionary: Option<Arc<Dictionary>>,
+    /// Language of the meanings of added cards
+    language: Language,
+}
+
+impl TutorTools {
+    pub fn new(
+        database: Arc<DatabaseService>,
+        dictionary: Option<Arc<Dictionary>>,
+        language: Language,
+    ) -> Self {
+        Self {
+            database,
+            dictionary,
+            language,
+        }
+    }
+
+    fn lookup_dictionary(&self, args: &Value) -> Result<Value, String> {
+        let word = string_arg(args, "word")?;
+        let Some(dictionary) = &self.dictionary else {
+            return Ok(json!({ "error": "No dictionary is installed" }));
+        };
+
+        let entries: Vec<Value> = match dictionary.lookup(word) {
+            Some(entry) => vec![entry_json(entry)],
+            // Not a written form; try it as a reading
+            None => dictionary
+                .entries()
+                .iter()
+                .filter(|entry| entry.readings.iter().any(|reading| reading == word))
+                .take(constants::llm::MAX_TOOL_RESULTS)
+                .map(entry_json)
+                .collect(),
+        };
+        Ok(json!({ "entries": entries }))
+    }
+
+    fn search_cards(&self, args: &Value) -> Result<Value, String> {
+        let query = string_arg(args, "query")?.to_lowercase();
+        let deck_names = self.deck_names()?;
+
+        let cards: Vec<Value> = self
+            .all_cards()?
+            .iter()
+            .filter(|card| card_text(&card.card_type).to_lowercase().contains(&query))
+            .take(constants::llm::MAX_TOOL_RESULTS)
+            .map(|card| {
+                let mut value = card_json(card);
+                value["deck"] = json!(deck_names.get(&card.deck_id).unwrap_or(&card.deck_id));
+                value
+            })
+            .collect();
+        Ok(json!({ "cards": cards }))
+    }
+
+    fn review_history(&self, args: &Value) -> Result<Value, String> {
+        let word = string_arg(args, "word")?;
+
+        let cards: Vec<Value> = self
+            .all_cards()?
+            .iter()
+            .filter(|card| card_front(&card.card_type) == word)
+            .map(|card| {
+                let reviews = self
+                    .database
+                    .get_card_reviews(&card.id)
+                    .map_err(|e| e.to_string())?;
+                let srs = &card.srs_data;
+                Ok(json!({
+                    "front": card_front(&card.card_type),
+                    "is_new": srs.is_new,
+                    "in_learning_steps": srs.learning.is_some(),
+                    "lapses": srs.lapses,
+                    "leech": srs.leech,
+                    "consecutive_correct": srs.repetitions,
+                    "interval_days": srs.interval,
+                    "ease_factor": srs.ease_factor,
+                    "next_review": srs.next_review.to_rfc3339(),
+                    "reviews": reviews.iter().map(review_json).collect::<Vec<_>>(),
+                }))
+            })
+            .collect::<Result<_, String>>()?;
+        if cards.is_empty() {
+            return Ok(json!({ "error": format!("No card for {}", word) }));
+        }
+        Ok(json!({ "cards": cards }))
+    }
+
+    fn add_card(&self, args: &Value) -> Result<Value, String> {
+        let word = string_arg(args, "word")?;
+        let reading = string_arg(args, "reading")?;
+        let meaning = string_arg(args, "meaning")?;
+        let jlpt_level = args["jlpt_level"]
+            .as_str()
+            .map_or(JLPTLevel::Unknown, JLPTLevel::from_str);
+
+        if self
+            .all_cards()?
+            .iter()
+            .any(|card| card_front(&card.card_type) == word)
+        {
+            return Ok(json!({ "error": format!("There already is a card for {}", word) }));
+        }
+
+        let card = CardType::Vocabulary(VocabularyCard {
+            kanji: word.to_string(),
+            hiragana: reading.to_string(),
+            romaji: String::new(),
+            meaning: meaning.to_string(),
+            // Filled in from other cards and the dictionary when the deck is opened
+            wrong_answers: Vec::new(),
+            example_sentences: Vec::new(),
+            jlpt_level,
+            language: self.language,
+        });
+        let id = add_tutor_card(&self.database, card).map_err(|e| e.to_string())?;
+        Ok(json!({ "added": id, "deck": constants::app::TUTOR_DECK_NAME }))
+    }
+
+    fn all_cards(&self) -> Result<Vec<FlashCard>, String> {
+        self.database.get_all_cards().map_err(|e| e.to_string())
+    }
+
+    fn deck_names(&self) -> Result<HashMap<String, String>, String> {
+        Ok(self
+            .database
+            .load_decks()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|deck| (deck.id, deck.name))
+            .collect())
+    }
+}
+
+impl ToolExecutor for TutorTools {
+    fn declarations(&self) -> Vec<ToolDeclaration> {
+        let word = json!({
+            "type": "object",
+            "properties": { "word": { "type": "string", "description": "Word in dictionary form, or its kana reading" } },
+            "required": ["word"],
+        });
+        vec![
+            ToolDeclaration {
+                name: "lookup_dictionary",
+                description: "Look up readings, English glosses, part of speech and JLPT level of a Japanese word in the local dictionary.",
+                parameters: word.clone(),
+            },
+            ToolDeclaration {
+                name: "search_cards",
+                description: "Search the student's flashcards by word, reading or English meaning.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": { "query": { "type": "string" } },
+                    "required": ["query"],
+                }),
+            },
+            ToolDeclaration {
+                name: "get_review_history",
+                description: "Get the student's review state for the card of a word: whether it is new, consecutive correct answers, interval, ease and next review date, and every past answer with its grade, the answer chosen and the time taken.",
+                parameters: word,
+            },
+            ToolDeclaration {
+                name: "add_card",
+                description: "Add a vocabulary flashcard to the student's Tutor Cards deck. Only use this when the student asks for a card.",
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "word": { "type": "string", "description": "Dictionary form" },
+                        "reading": { "type": "string", "description": "Hiragana reading" },
+                        "meaning": { "type": "string", "description": "Short meaning, in the language of your answer" },
+                        "jlpt_level": { "type": "string", "description": "N5 to N1, if known" },
+                    },
+                    "required": ["word", "reading", "meaning"],
+                }),
+            },
+        ]
+    }
+
+    fn call(&self, call: &ToolCall) -> ToolResult {
+        let response = match call.name.as_str() {
+            "lookup_dictionary" => self.lookup_dictionary(&call.args),
+            "search_cards" => self.search_cards(&call.args),
+            "get_review_history" => self.review_history(&call.args),
+            "add_card" => self.add_card(&call.args),
+            name => Err(format!("Unknown function {}", name)),
+        };
+
+        ToolResult {
+            name: call.name.clone(),
+            response: response.unwrap_or_else(|error| json!({ "error": error })),
+        }
+    }
+}
+
+/// Save a card in the tutor deck, creating the deck on first use
+///
+/// Returns the new card's id.
+pub fn add_tutor_card(database: &DatabaseService, card: CardType) -> Result<String, DatabaseError> {
+    let deck_id = constants::app::TUTOR_DECK_ID;
+    if !database.load_decks()?.iter().any(|deck| deck.id == deck_id) {
+        database.create_deck(
+            deck_id.to_string(),
+            constants::app::TUTOR_DECK_NAME.to_string(),
+            "Cards added while studying with the tutor".to_string(),
+        )?;
+    }
+
+    let id = format!("{}-{}", deck_id, Utc::now().timestamp_micros());
+    database.save_card(id.clone(), deck_id.to_string(), card)?;
+    Ok(id)
+}
+
+fn string_arg<'a>(args: &'a Value, name: &str) -> Result<&'a str, String> {
+    args[name]
+        .as_str()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("Missing argument {}", name))
+}
+
+fn card_front(card: &CardType) -> &str {
+    match card {
+        CardType::Vocabulary(vocab) => &vocab.kanji,
+        CardType::Grammar(grammar) => &grammar.pattern,
+    }
+}
+
+/// Searchable text of a card: front, reading and meaning
+fn card_text(card: &CardType) -> String {
+    match card {
+        CardType::Vocabulary(vocab) => {
+            format!("{} {} {}", vocab.kanji, vocab.hiragana, vocab.meaning)
+        }
+        CardType::Grammar(grammar) => format!(
+            "{} {} {}",
+            grammar.pattern, grammar.pattern_reading, grammar.explanation
+        ),
+    }
+}
+
+fn card_json(card: &FlashCard) -> Value {
+    match &card.card_type {
+        CardType::Vocabulary(vocab) => json!({
+            "type": "vocabulary",
+            "word": vocab.kanji,
+            "reading": vocab.hiragana,
+            "meaning": vocab.meaning,
+            "meaning_language": vocab.language.tag(),
+            "jlpt_level": vocab.jlpt_level.as_str(),
+        }),
+        CardType::Grammar(grammar) => json!({
+            "type": "grammar",
+            "pattern": grammar.pattern,
+            "meaning": grammar.explanation,
+            "meaning_language": grammar.language.tag(),
+            "jlpt_level": grammar.jlpt_level.as_str(),
+        }),
+    }
+}
+
+fn review_json(review: &ReviewLog) -> Value {
+    json!({
+        "day": review.day,
+        "grade": review.quality,
+        "answer": review.answer,
+        "response_seconds": review.response_ms.map(|ms| ms as f64 / 1000.0),
+        "interval_days": review.interval,
+        "rescheduled": review.rescheduled,
+    })
+}
+
+fn entry_json(entry: &DictionaryEntry) -> Value {
+    json!({
+        "word": entry.word,
+        "readings": entry.readings,
+        "glosses": entry.glosses,
+        "pos": entry.pos,
+        "jlpt_level": entry.jlpt.map(|level| level.as_str().to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(tools: &TutorTools, name: &str, args: Value) -> Value {
+        let call = ToolCall {
+            name: name.to_string(),
+            args,
+            signature: None,
+        };
+        tools.call(&call).response
+    }
+
+    #[test]
+    fn added_cards_can_be_found_and_reviewed() {
+        let database = Arc::new(DatabaseService::new_in_memory().unwrap());
+        let tools = TutorTools::new(Arc::clone(&database), None, Language::English);
+
+        let added = call(
+            &tools,
+            "add_card",
+            json!({ "word": "猫", "reading": "ねこ", "meaning": "cat", "jlpt_level": "N5" }),
+        );
+        assert!(added["added"].is_string(), "{}", added);
+        let duplicate = call(
+            &tools,
+            "add_card",
+            json!({ "word": "猫", "reading": "ねこ", "meaning": "cat" }),
+        );
+        assert!(duplicate["error"].is_string());
+
+        let found = call(&tools, "search_cards", json!({ "query": "CAT" }));
+        assert_eq!(found["cards"][0]["word"], "猫");
+        assert_eq!(found["cards"][0]["deck"], constants::app::TUTOR_DECK_NAME);
+
+        let history = call(&tools, "get_review_history", json!({ "word": "猫" }));
+        assert_eq!(history["cards"][0]["is_new"], true);
+        assert_eq!(history["cards"][0]["reviews"], json!([]));
+    }
+
+    #[test]
+    fn bad_calls_are_reported_to_the_model() {
+        let database = Arc::new(DatabaseService::new_in_memory().unwrap());
+        let tools = TutorTools::new(database, None, Language::English);
+
+        assert!(call(&tools, "delete_everything", json!({}))["error"].is_string());
+        assert!(call(&tools, "search_cards", json!({}))["error"].is_string());
+        assert!(call(&tools, "lookup_dictionary", json!({ "word": "猫" }))["error"].is_string());
+    }
+}
@@ -0,0 +1,296 @@
+//! Cross-checks of LLM output against local data
+//!
+//! Readings are compared with the tokenizer's reading, or with the dictionary
+//! readings when the tokenizer has none and the word is in dictionary form.
+//! Meanings and JLPT levels are compared with the dictionary when one is
+//! loaded; meanings only for English, the language of the glosses. Differences are reported as [`Mismatch`]es so the UI can offer to
+//! regenerate or to accept the local data.
+
+use crate::models::{JLPTLevel, Language, VocabularyCard, WordExplanation, WordSegment};
+use crate::services::dictionary::{Dictionary, glosses_overlap};
+
+/// A difference between LLM output and local data
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mismatch {
+    Reading { llm: String, local: String },
+    Meaning { llm: String, glosses: Vec<String> },
+    JlptLevel { llm: JLPTLevel, local: JLPTLevel },
+}
+
+impl Mismatch {
+    /// One-line description for the warning badge
+    pub fn describe(&self) -> String {
+        match self {
+            Mismatch::Reading { llm, local } => {
+                format!("Reading {} differs from the local reading {}", llm, local)
+            }
+            Mismatch::Meaning { llm, glosses } => format!(
+                "Meaning \"{}\" matches none of the dictionary glosses: {}",
+                llm,
+                glosses.join("; ")
+            ),
+            Mismatch::JlptLevel { llm, local } => format!(
+                "JLPT level {} differs from the dictionary level {}",
+                llm.as_str(),
+                local.as_str()
+            ),
+        }
+    }
+}
+
+/// Check an explanation against the segment it explains
+///
+/// The explanation's reading is that of the surface form, so dictionary
+/// readings only apply when the surface is the dictionary form.
+pub fn verify_explanation(
+    segment: &WordSegment,
+    explanation: &WordExplanation,
+    dictionary: Option<&Dictionary>,
+) -> Vec<Mismatch> {
+    verify(
+        &segment.base_form,
+        Some(&segment.reading),
+        segment.surface == segment.base_form,
+        explanation.reading.as_deref(),
+        (explanation.language == Language::English).then_some(explanation.meaning.as_str()),
+        JLPTLevel::from_str(&explanation.jlpt_level),
+        dictionary,
+    )
+}
+
+/// Check a card, optionally against the tokenizer's reading of its dictionary form
+pub fn verify_card(
+    card: &VocabularyCard,
+    local_reading: Option<&str>,
+    dictionary: Option<&Dictionary>,
+) -> Vec<Mismatch> {
+    verify(
+        &card.kanji,
+        local_reading,
+        true,
+        Some(&card.hiragana),
+        (card.language == Language::English).then_some(card.meaning.as_str()),
+        card.jlpt_level,
+        dictionary,
+    )
+}
+
+/// Replace the flagged parts of an explanation with the local data
+pub fn accept_local_explanation(explanation: &mut WordExplanation, mismatches: &[Mismatch]) {
+    for mismatch in mismatches {
+        match mismatch {
+            Mismatch::Reading { local, .. } => explanation.reading = Some(local.clone()),
+            Mismatch::Meaning { glosses, .. } => explanation.meaning = glosses.join("; "),
+            Mismatch::JlptLevel { local, .. } => {
+                explanation.jlpt_level = local.as_str().to_string()
+            }
+        }
+    }
+}
+
+/// Replace the flagged parts of a card with the local data
+pub fn accept_local_card(card: &mut VocabularyCard, mismatches: &[Mismatch]) {
+    for mismatch in mismatches {
+        match mismatch {
+            Mismatch::Reading { local, .. } => card.hiragana = local.clone(),
+            Mismatch::Meaning { glosses, .. } => card.meaning = glosses.join("; "),
+            Mismatch::JlptLevel { local, .. } => card.jlpt_level = *local,
+        }
+    }
+}
+
+fn verify(
+    word: &str,
+    local_reading: Option<&str>,
+    use_dictionary_readings: bool,
+    llm_reading: Option<&str>,
+    llm_meaning: Option<&str>,
+    llm_level: JLPTLevel,
+    dictionary: Option<&Dictionary>,
+) -> Vec<Mismatch> {
+    let entry = dictionary.and_then(|dictionary| dictionary.lookup(word));
+    let mut mismatches = Vec::new();
+
+    // The tokenizer only knows readings of kana it can see; otherwise use the dictionary
+    let local_readings: Vec<String> = match local_reading.filter(|reading| is_kana(reading)) {
+        Some(reading) => vec![to_hiragana(reading)],
+        None => entry
+            .filter(|_| use_dictionary_readings)
+            .map(|entry| entry.readings.iter().map(|r| to_hiragana(r)).collect())
+            .unwrap_or_default(),
+    };
+    if let Some(llm) = llm_reading.filter(|reading| !reading.trim().is_empty())
+        && !local_readings.is_empty()
+        && !local_readings.contains(&to_hiragana(llm.trim()))
+    {
+        mismatches.push(Mismatch::Reading {
+            llm: llm.trim().to_string(),
+            local: local_readings[0].clone(),
+        });
+    }
+
+    if let Some(entry) = entry {
+        if let Some(llm_meaning) = llm_meaning
+            && !entry.glosses.is_empty()
+            && !entry
+                .glosses
+                .iter()
+                .any(|gloss| glosses_overlap(llm_meaning, gloss))
+        {
+            mismatches.push(Mismatch::Meaning {
+                llm: llm_meaning.to_string(),
+                glosses: entry.glosses.clone(),
+            });
+        }
+
+        if let Some(local) = entry.jlpt.filter(|level| *level != JLPTLevel::Unknown)
+            && local != llm_level
+        {
+            mismatches.push(Mismatch::JlptLevel {
+                llm: llm_level,
+                local,
+            });
+        }
+    }
+
+    mismatches
+}
+
+fn is_kana(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| matches!(c, '\u{3041}'..='\u{3096}' | '\u{30A1}'..='\u{30FA}' | 'ー'))
+}
+
+/// Fold katakana into hiragana so both scripts compare equal
+fn to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::dictionary::DictionaryEntry;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::from_entries(vec![DictionaryEntry {
+            word: "今日".to_string(),
+            readings: vec!["きょう".to_string(), "こんにち".to_string()],
+            glosses: vec!["today".to_string(), "these days".to_string()],
+            pos: vec!["n".to_string()],
+            jlpt: Some(JLPTLevel::N5),
+        }])
+    }
+
+    fn card(kanji: &str, hiragana: &str, meaning: &str, level: JLPTLevel) -> VocabularyCard {
+        VocabularyCard {
+            kanji: kanji.to_string(),
+            hiragana: hiragana.to_string(),
+            romaji: String::new(),
+            meaning: meaning.to_string(),
+            wrong_answers: Vec::new(),
+            example_sentences: Vec::new(),
+            jlpt_level: level,
+            language: Language::English,
+        }
+    }
+
+    #[test]
+    fn matching_card_has_no_mismatches() {
+        let dictionary = dictionary();
+        let cases = [
+            (card("今日", "きょう", "today", JLPTLevel::N5), None),
+            (
+                card("今日", "コンニチ", "nowadays, these days", JLPTLevel::N5),
+                None,
+            ),
+            (
+                card("食べる", "たべる", "to eat", JLPTLevel::N5),
+                Some("タベル"),
+            ),
+            // Spanish meanings are not compared with the English glosses
+            (
+                VocabularyCard {
+                    language: Language::Spanish,
+                    ..card("今日", "きょう", "hoy", JLPTLevel::N5)
+                },
+                None,
+            ),
+            // Without kana from the tokenizer or a dictionary entry there is nothing to compare
+            (card("猫", "いぬ", "dog", JLPTLevel::N1), Some("猫")),
+        ];
+
+        for (card, local_reading) in cases {
+            assert_eq!(
+                verify_card(&card, local_reading, Some(&dictionary)),
+                [],
+                "{:?}",
+                card
+            );
+        }
+    }
+
+    #[test]
+    fn mismatches_are_reported_and_local_data_can_be_accepted() {
+        let dictionary = dictionary();
+        let mut card = card("今日", "いま", "now", JLPTLevel::N3);
+
+        let mismatches = verify_card(&card, None, Some(&dictionary));
+
+        assert_eq!(
+            mismatches,
+            [
+                Mismatch::Reading {
+                    llm: "いま".to_string(),
+                    local: "きょう".to_string()
+                },
+                Mismatch::Meaning {
+                    llm: "now".to_string(),
+                    glosses: vec!["today".to_string(), "these days".to_string()]
+                },
+                Mismatch::JlptLevel {
+                    llm: JLPTLevel::N3,
+                    local: JLPTLevel::N5
+                },
+            ]
+        );
+
+        accept_local_card(&mut card, &mismatches);
+        assert_eq!(card.hiragana, "きょう");
+        assert_eq!(card.meaning, "today; these days");
+        assert_eq!(card.jlpt_level, JLPTLevel::N5);
+        assert_eq!(verify_card(&card, None, Some(&dictionary)), []);
+    }
+
+    #[test]
+    fn tokenizer_reading_is_checked_without_a_dictionary() {
+        let segment = WordSegment {
+            surface: "たべた".to_string(),
+            reading: "たべた".to_string(),
+            base_form: "食べる".to_string(),
+            explanation: None,
+            is_selected: false,
+        };
+        let mut explanation = WordExplanation {
+            meaning: "ate".to_string(),
+            reading: Some("のんだ".to_string()),
+            grammar_notes: None,
+            examples: Vec::new(),
+            jlpt_level: "N5".to_string(),
+            user_edited: false,
+            language: Language::English,
+        };
+
+        let mismatches = verify_explanation(&segment, &explanation, None);
+        assert_eq!(mismatches.len(), 1);
+
+        accept_local_explanation(&mut explanation, &mismatches);
+        assert_eq!(explanation.reading.as_deref(), Some("たべた"));
+    }
+}
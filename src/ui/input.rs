@@ -1,6 +1,6 @@
 //! Text input styles using Catppuccin color palette
 
-use iced::widget::text_input;
+use iced::widget::{text_editor, text_input};
 use iced::{Background, Border};
 
 /// Catppuccin-inspired text input style matching shadcn aesthetics
@@ -52,3 +52,22 @@ pub fn text_input_style(theme: &iced::Theme, status: text_input::Status) -> text
         selection: selection_color,
     }
 }
+
+/// Multi-line text editor style matching `text_input_style`
+pub fn text_editor_style(theme: &iced::Theme, status: text_editor::Status) -> text_editor::Style {
+    let status = match status {
+        text_editor::Status::Active => text_input::Status::Active,
+        text_editor::Status::Hovered => text_input::Status::Hovered,
+        text_editor::Status::Focused { is_hovered } => text_input::Status::Focused { is_hovered },
+        text_editor::Status::Disabled => text_input::Status::Disabled,
+    };
+    let style = text_input_style(theme, status);
+
+    text_editor::Style {
+        background: style.background,
+        border: style.border,
+        placeholder: style.placeholder,
+        value: style.value,
+        selection: style.selection,
+    }
+}
@@ -11,7 +11,7 @@ pub mod utils;
 
 // Re-export commonly used styles
 pub use button::button_style;
-pub use input::text_input_style;
+pub use input::{text_editor_style, text_input_style};
 pub use container::section_style;
 pub use slider::slider_style;
 pub use theme::get_theme;
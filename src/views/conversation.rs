@@ -0,0 +1,432 @@
+//! Conversation view - Chat in Japanese with a tutor that corrects mistakes
+//!
+//! This view implements:
+//! - A chat where the tutor replies in character at the chosen JLPT level
+//! - Corrections of each message shown inline as removed and added text
+//! - Turning a correction into a grammar card with one click
+
+use crate::constants::ui;
+use crate::models::{
+    CardType, ConversationReply, ConversationTurn, ExampleSentence, GrammarCard, JLPTLevel,
+    Language,
+};
+use crate::ui::{button_style, section_style, text_input_style};
+use iced::widget::{Space, button, column, container, row, scrollable, text, text_input};
+use iced::{Alignment, Color, Element, Fill, Length, Task};
+use std::collections::HashSet;
+
+/// Background of text the tutor corrected away
+const REMOVED_HIGHLIGHT: Color = Color::from_rgb(0.75, 0.3, 0.3);
+/// Background of the tutor's replacement text
+const ADDED_HIGHLIGHT: Color = Color::from_rgb(0.3, 0.6, 0.35);
+
+pub struct ConversationView {
+    turns: Vec<ConversationTurn>,
+    input: String,
+    level: JLPTLevel,
+    // Waiting for the reply to the last turn
+    awaiting_reply: bool,
+    error: Option<String>,
+    // (turn, correction) pairs already turned into cards
+    carded: HashSet<(usize, usize)>,
+    status: Option<String>,
+}
+
+impl Default for ConversationView {
+    fn default() -> Self {
+        Self {
+            turns: Vec::new(),
+            input: String::new(),
+            level: JLPTLevel::N5,
+            awaiting_reply: false,
+            error: None,
+            carded: HashSet::new(),
+            status: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    BackToHome,
+    InputChanged(String),
+    Send,
+    ReplyReceived(ConversationReply),
+    ReplyFailed(String),
+    LevelSelected(JLPTLevel),
+    /// Make a card from correction `.1` of turn `.0`
+    MakeCard(usize, usize),
+    NewConversation,
+}
+
+impl ConversationView {
+    /// Level the tutor writes at
+    pub fn level(&self) -> JLPTLevel {
+        self.level
+    }
+
+    /// Set the level, e.g. from saved settings
+    pub fn set_level(&mut self, level: JLPTLevel) {
+        self.level = level;
+    }
+
+    /// Message waiting for its reply
+    pub fn pending_message(&self) -> Option<&str> {
+        self.awaiting_reply
+            .then(|| self.turns.last())
+            .flatten()
+            .map(|turn| turn.message.as_str())
+    }
+
+    /// Replied turns, oldest first (excludes a message still waiting for its reply)
+    pub fn answered_turns(&self) -> &[ConversationTurn] {
+        let answered = self.turns.len() - usize::from(self.awaiting_reply);
+        &self.turns[..answered]
+    }
+
+    /// Grammar card practicing a correction: the fix and why, with the fixed sentence
+    ///
+    /// `language` is the one the tutor was asked to explain corrections in.
+    pub fn mistake_card(
+        &self,
+        turn: usize,
+        correction: usize,
+        language: Language,
+    ) -> Option<CardType> {
+        let conversation_turn = self.turns.get(turn)?;
+        let correction = conversation_turn.reply.corrections.get(correction)?;
+
+        Some(CardType::Grammar(GrammarCard {
+            pattern: correction.corrected.clone(),
+            pattern_reading: String::new(),
+            explanation: correction.explanation.clone(),
+            // Filled in from other cards when the deck is opened
+            wrong_answers: Vec::new(),
+            example_sentences: vec![ExampleSentence {
+                japanese: conversation_turn.corrected_message(),
+                translation: format!("Instead of: {}", conversation_turn.message),
+                language,
+            }],
+            jlpt_level: self.level,
+            language,
+        }))
+    }
+
+    /// Record that a correction was saved as a card
+    pub fn mark_card_added(&mut self, turn: usize, correction: usize) {
+        self.carded.insert((turn, correction));
+        self.status = Some("Card added to Tutor Cards.".to_string());
+    }
+
+    /// Show or clear a status message below the chat
+    pub fn set_status(&mut self, status: Option<String>) {
+        self.status = status;
+    }
+
+    fn can_send(&self) -> bool {
+        !self.input.trim().is_empty() && !self.awaiting_reply
+    }
+}
+
+impl ConversationView {
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::BackToHome => Task::none(),
+            Message::InputChanged(input) => {
+                self.input = input;
+                Task::none()
+            }
+            Message::Send => {
+                // The parent sends the message to the LLM once it is pending
+                if self.can_send() {
+                    let message = self.input.trim().to_string();
+                    self.input.clear();
+                    self.turns.push(ConversationTurn::new(message));
+                    self.awaiting_reply = true;
+                    self.error = None;
+                    self.status = None;
+                }
+                Task::none()
+            }
+            Message::ReplyReceived(reply) => {
+                if self.awaiting_reply
+                    && let Some(turn) = self.turns.last_mut()
+                {
+                    turn.reply = reply;
+                }
+                self.awaiting_reply = false;
+                Task::none()
+            }
+            Message::ReplyFailed(error) => {
+                if self.awaiting_reply {
+                    // Give the message back so it can be sent again
+                    if let Some(turn) = self.turns.pop() {
+                        self.input = turn.message;
+                    }
+                }
+                self.awaiting_reply = false;
+                self.error = Some(error);
+                Task::none()
+            }
+            Message::LevelSelected(level) => {
+                self.level = level;
+                Task::none()
+            }
+            // Saved by the parent, which owns the database
+            Message::MakeCard(_, _) => Task::none(),
+            Message::NewConversation => {
+                if !self.awaiting_reply {
+                    self.turns.clear();
+                    self.carded.clear();
+                    self.error = None;
+                    self.status = None;
+                }
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let turns = self
+            .turns
+            .iter()
+            .enumerate()
+            .fold(column![].spacing(15), |col, (index, turn)| {
+                col.push(self.turn_view(index, turn))
+            });
+
+        let placeholder = if self.turns.is_empty() {
+            column![text("Say hello in Japanese to start the conversation.").size(14)]
+        } else {
+            column![]
+        };
+
+        let error = match &self.error {
+            Some(error) => column![text(error).size(12)],
+            None => column![],
+        };
+
+        let status = match &self.status {
+            Some(status) => column![text(status).size(12)],
+            None => column![],
+        };
+
+        let content = column![
+            self.header(),
+            self.level_selector(),
+            Space::new().height(10),
+            placeholder,
+            turns,
+            self.input_row(),
+            error,
+            status,
+        ]
+        .spacing(10)
+        .padding(20)
+        .width(Length::Fill)
+        .max_width(ui::MAX_CONTENT_WIDTH)
+        .align_x(Alignment::Center);
+
+        scrollable(container(content).width(Length::Fill).center_x(Fill))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn header(&self) -> Element<'_, Message> {
+        let title = text("Conversation Practice").size(32);
+
+        let subtitle = text("Chat in Japanese; mistakes are corrected as you go").size(16);
+
+        let new_button = button("New Conversation")
+            .on_press_maybe((!self.awaiting_reply).then_some(Message::NewConversation))
+            .padding(10)
+            .style(button_style);
+
+        let back_button = button("← Back to Home")
+            .on_press(Message::BackToHome)
+            .padding(10)
+            .style(button_style);
+
+        row![
+            column![title, subtitle].spacing(5).width(Length::Fill),
+            new_button,
+            back_button
+        ]
+        .align_y(Alignment::Center)
+        .spacing(10)
+        .into()
+    }
+
+    fn level_selector(&self) -> Element<'_, Message> {
+        let buttons = JLPTLevel::ALL.iter().fold(
+            row![text("Tutor level:").size(14)]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            |row, &level| {
+                let selected = level == self.level;
+                row.push(
+                    button(text(level.as_str().to_string()).size(14))
+                        .on_press_maybe((!selected).then_some(Message::LevelSelected(level)))
+                        .padding(8)
+                        .style(move |theme: &iced::Theme, status| {
+                            let mut style = button_style(theme, status);
+                            if selected {
+                                style.background = Some(level.color().into());
+                                style.text_color = Color::WHITE;
+                            }
+                            style
+                        }),
+                )
+            },
+        );
+        buttons.into()
+    }
+
+    fn turn_view<'a>(&'a self, index: usize, turn: &'a ConversationTurn) -> Element<'a, Message> {
+        let you = column![text("You").size(12), self.marked_message(turn)].spacing(5);
+
+        let reply = if self.awaiting_reply && index + 1 == self.turns.len() {
+            text("Thinking...").size(14)
+        } else {
+            text(&turn.reply.reply).size(16)
+        };
+        let tutor = container(column![text("Tutor").size(12), reply].spacing(5))
+            .padding(12)
+            .width(Length::Fill)
+            .style(|theme: &iced::Theme| {
+                let palette = theme.extended_palette();
+                let mut style = section_style(theme);
+                style.background = Some(palette.background.weak.color.into());
+                style
+            });
+
+        let corrections = turn.reply.corrections.iter().enumerate().fold(
+            column![].spacing(8),
+            |col, (correction_index, correction)| {
+                let added = self.carded.contains(&(index, correction_index));
+                let card_button =
+                    button(text(if added { "Card Added ✓" } else { "Make Card" }).size(12))
+                        .on_press_maybe(
+                            (!added).then_some(Message::MakeCard(index, correction_index)),
+                        )
+                        .padding(6)
+                        .style(button_style);
+
+                col.push(
+                    row![
+                        column![
+                            row![
+                                highlighted(&correction.original, REMOVED_HIGHLIGHT),
+                                text("→").size(14),
+                                highlighted(&correction.corrected, ADDED_HIGHLIGHT),
+                            ]
+                            .spacing(5)
+                            .align_y(Alignment::Center),
+                            text(&correction.explanation).size(12),
+                        ]
+                        .spacing(4)
+                        .width(Length::Fill),
+                        card_button,
+                    ]
+                    .spacing(10)
+                    .align_y(Alignment::Center),
+                )
+            },
+        );
+
+        container(column![you, corrections, tutor].spacing(10))
+            .padding(15)
+            .width(Length::Fill)
+            .style(section_style)
+            .into()
+    }
+
+    /// The learner's message with each correction shown as removed then added text
+    fn marked_message<'a>(&self, turn: &'a ConversationTurn) -> Element<'a, Message> {
+        let mut pieces: Vec<Element<'a, Message>> = Vec::new();
+        for (piece, correction) in turn.pieces() {
+            match correction {
+                Some(index) => {
+                    pieces.push(highlighted(piece, REMOVED_HIGHLIGHT));
+                    pieces.push(highlighted(
+                        &turn.reply.corrections[index].corrected,
+                        ADDED_HIGHLIGHT,
+                    ));
+                }
+                None => pieces.push(text(piece).size(16).into()),
+            }
+        }
+        row(pieces).spacing(2).wrap().into()
+    }
+
+    fn input_row(&self) -> Element<'_, Message> {
+        let input = text_input("日本語で書いてください... (Write in Japanese)", &self.input)
+            .on_input(Message::InputChanged)
+            .on_submit(Message::Send)
+            .padding(12)
+            .size(16)
+            .style(text_input_style);
+
+        let send_button = button("Send")
+            .on_press_maybe(self.can_send().then_some(Message::Send))
+            .padding(12)
+            .style(button_style);
+
+        row![input, send_button].spacing(10).into()
+    }
+}
+
+/// Text on a colored background, for corrected spans
+fn highlighted<'a>(content: &'a str, color: Color) -> Element<'a, Message> {
+    container(text(content).size(16).color(Color::WHITE))
+        .padding([0, 2])
+        .style(move |_theme: &iced::Theme| container::Style {
+            background: Some(color.into()),
+            border: iced::Border {
+                radius: 3.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::llm::mock_service;
+    use iced::futures::executor::block_on;
+
+    #[test]
+    fn corrections_can_be_turned_into_cards() {
+        let mut view = ConversationView::default();
+        let _ = view.update(Message::InputChanged(
+            "私は日本語を勉強するです。".to_string(),
+        ));
+        let _ = view.update(Message::Send);
+        let message = view.pending_message().expect("message pending").to_string();
+
+        let reply = block_on(mock_service().conversation_reply(
+            &message,
+            view.answered_turns(),
+            view.level(),
+        ))
+        .unwrap();
+        let _ = view.update(Message::ReplyReceived(reply));
+        assert!(view.pending_message().is_none());
+
+        let turn = &view.answered_turns()[0];
+        assert_eq!(turn.corrected_message(), "私は日本語を勉強しています。");
+
+        let Some(CardType::Grammar(card)) = view.mistake_card(0, 0, Language::English) else {
+            panic!("expected a grammar card");
+        };
+        assert_eq!(card.pattern, "勉強しています");
+        assert_eq!(
+            card.example_sentences[0].japanese,
+            "私は日本語を勉強しています。"
+        );
+        assert!(view.mistake_card(0, 1, Language::English).is_none());
+    }
+}
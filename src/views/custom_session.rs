@@ -0,0 +1,319 @@
+//! Custom session builder, shown in Practice mode
+//!
+//! Lets users:
+//! - Filter cards by deck, card kind, JLPT level and tags
+//! - Keep only cards failed or added in the last N days, or only overdue ones
+//! - Choose whether answers in the session reschedule the cards
+//! - Save the filters as named presets and load them again
+
+use crate::models::{DeckInfo, JLPTLevel};
+use crate::services::custom_study::{CardKind, CardTag, StudyFilter, StudyPreset};
+use crate::ui::{button_style, section_style, text_input_style};
+use iced::widget::{button, column, container, row, text, text_input, toggler};
+use iced::{Alignment, Element, Length, Task};
+
+#[derive(Debug, Clone, Default)]
+pub struct CustomSessionView {
+    decks: Vec<DeckInfo>,
+    deck_ids: Vec<String>,
+    kinds: Vec<CardKind>,
+    levels: Vec<JLPTLevel>,
+    tags: Vec<CardTag>,
+    failed_within_days: String,
+    added_within_days: String,
+    overdue_only: bool,
+    affects_scheduling: bool,
+    presets: Vec<StudyPreset>,
+    preset_name: String,
+    status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleDeck(String),
+    ToggleKind(CardKind),
+    ToggleLevel(JLPTLevel),
+    ToggleTag(CardTag),
+    FailedWithinDaysChanged(String),
+    AddedWithinDaysChanged(String),
+    OverdueOnlyToggled(bool),
+    AffectsSchedulingToggled(bool),
+    PresetNameChanged(String),
+    SavePreset,
+    LoadPreset(String),
+    DeletePreset(String),
+    Start,
+    Cancel,
+}
+
+/// Add `item` to the list, or remove it if it is already there
+fn toggle<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if let Some(position) = items.iter().position(|existing| *existing == item) {
+        items.remove(position);
+    } else {
+        items.push(item);
+    }
+}
+
+/// Parse a day count; an empty field means no limit
+fn parse_days(text: &str) -> Option<Option<u32>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(None);
+    }
+    text.parse().ok().filter(|days| *days > 0).map(Some)
+}
+
+impl CustomSessionView {
+    /// Open the builder with the decks to choose from and the saved presets
+    pub fn new(decks: Vec<DeckInfo>, presets: Vec<StudyPreset>) -> Self {
+        Self {
+            decks,
+            presets,
+            ..Default::default()
+        }
+    }
+
+    /// Filter entered by the user, if the day counts are valid
+    pub fn filter(&self) -> Option<StudyFilter> {
+        Some(StudyFilter {
+            deck_ids: self.deck_ids.clone(),
+            kinds: self.kinds.clone(),
+            levels: self.levels.clone(),
+            tags: self.tags.clone(),
+            failed_within_days: parse_days(&self.failed_within_days)?,
+            added_within_days: parse_days(&self.added_within_days)?,
+            overdue_only: self.overdue_only,
+            affects_scheduling: self.affects_scheduling,
+        })
+    }
+
+    /// Fill the builder from a filter
+    fn set_filter(&mut self, filter: StudyFilter) {
+        let days = |days: Option<u32>| days.map(|days| days.to_string()).unwrap_or_default();
+        self.deck_ids = filter.deck_ids;
+        self.kinds = filter.kinds;
+        self.levels = filter.levels;
+        self.tags = filter.tags;
+        self.failed_within_days = days(filter.failed_within_days);
+        self.added_within_days = days(filter.added_within_days);
+        self.overdue_only = filter.overdue_only;
+        self.affects_scheduling = filter.affects_scheduling;
+    }
+
+    /// Saved presets, to persist after a change
+    pub fn presets(&self) -> &[StudyPreset] {
+        &self.presets
+    }
+
+    pub fn set_status(&mut self, status: Option<String>) {
+        self.status = status;
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ToggleDeck(id) => toggle(&mut self.deck_ids, id),
+            Message::ToggleKind(kind) => toggle(&mut self.kinds, kind),
+            Message::ToggleLevel(level) => toggle(&mut self.levels, level),
+            Message::ToggleTag(tag) => toggle(&mut self.tags, tag),
+            Message::FailedWithinDaysChanged(value) => self.failed_within_days = value,
+            Message::AddedWithinDaysChanged(value) => self.added_within_days = value,
+            Message::OverdueOnlyToggled(enabled) => self.overdue_only = enabled,
+            Message::AffectsSchedulingToggled(enabled) => self.affects_scheduling = enabled,
+            Message::PresetNameChanged(name) => self.preset_name = name,
+            Message::SavePreset => {
+                let name = self.preset_name.trim().to_string();
+                if let Some(filter) = self.filter().filter(|_| !name.is_empty()) {
+                    self.presets.retain(|preset| preset.name != name);
+                    self.presets.push(StudyPreset { name, filter });
+                    self.status = Some("Preset saved.".to_string());
+                }
+            }
+            Message::LoadPreset(name) => {
+                if let Some(preset) = self.presets.iter().find(|preset| preset.name == name) {
+                    self.set_filter(preset.filter.clone());
+                    self.preset_name = name;
+                }
+            }
+            Message::DeletePreset(name) => self.presets.retain(|preset| preset.name != name),
+            Message::Start | Message::Cancel => {}
+        }
+        Task::none()
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let decks = self.decks.iter().fold(row![].spacing(8), |row, deck| {
+            row.push(choice(
+                &deck.name,
+                self.deck_ids.contains(&deck.id),
+                Message::ToggleDeck(deck.id.clone()),
+            ))
+        });
+        let kinds = CardKind::ALL.iter().fold(row![].spacing(8), |row, &kind| {
+            row.push(choice(
+                kind.name(),
+                self.kinds.contains(&kind),
+                Message::ToggleKind(kind),
+            ))
+        });
+        let levels = JLPTLevel::ALL.iter().fold(row![].spacing(8), |row, level| {
+            row.push(choice(
+                level.as_str(),
+                self.levels.contains(level),
+                Message::ToggleLevel(*level),
+            ))
+        });
+        let tags = CardTag::ALL.iter().fold(row![].spacing(8), |row, &tag| {
+            row.push(choice(
+                tag.name(),
+                self.tags.contains(&tag),
+                Message::ToggleTag(tag),
+            ))
+        });
+
+        let filters = column![
+            text("Nothing selected in a row matches every card.").size(14),
+            labeled("Decks", decks.wrap().into()),
+            labeled("Kind", kinds.into()),
+            labeled("JLPT level", levels.into()),
+            labeled("Tags", tags.into()),
+            days_input(
+                "Failed in the last N days",
+                &self.failed_within_days,
+                Message::FailedWithinDaysChanged
+            ),
+            days_input(
+                "Added in the last N days",
+                &self.added_within_days,
+                Message::AddedWithinDaysChanged
+            ),
+            toggler(self.overdue_only)
+                .label("Overdue cards only")
+                .on_toggle(Message::OverdueOnlyToggled),
+            toggler(self.affects_scheduling)
+                .label("Reschedule the cards answered in this session")
+                .on_toggle(Message::AffectsSchedulingToggled),
+        ]
+        .spacing(12);
+
+        let valid = self.filter().is_some();
+        let status = if valid {
+            self.status.clone().unwrap_or_default()
+        } else {
+            "Day counts must be whole numbers above 0, or empty.".to_string()
+        };
+
+        let content = column![
+            text("Custom Session").size(32),
+            container(filters)
+                .padding(15)
+                .width(Length::Fill)
+                .style(section_style),
+            self.presets_section(),
+            text(status).size(14),
+            row![
+                button("Start Session")
+                    .on_press_maybe(valid.then_some(Message::Start))
+                    .padding(12)
+                    .style(button_style),
+                button("Cancel")
+                    .on_press(Message::Cancel)
+                    .padding(12)
+                    .style(button_style),
+            ]
+            .spacing(10),
+        ]
+        .spacing(20);
+
+        content.into()
+    }
+
+    fn presets_section(&self) -> Element<'_, Message> {
+        let presets = self
+            .presets
+            .iter()
+            .fold(column![].spacing(8), |col, preset| {
+                col.push(
+                    row![
+                        text(&preset.name).width(Length::Fill),
+                        button("Load")
+                            .on_press(Message::LoadPreset(preset.name.clone()))
+                            .padding(8)
+                            .style(button_style),
+                        button("Delete")
+                            .on_press(Message::DeletePreset(preset.name.clone()))
+                            .padding(8)
+                            .style(button_style),
+                    ]
+                    .align_y(Alignment::Center)
+                    .spacing(10),
+                )
+            });
+        let can_save = !self.preset_name.trim().is_empty() && self.filter().is_some();
+
+        container(
+            column![
+                text("Presets").size(20),
+                presets,
+                row![
+                    text_input("Preset name", &self.preset_name)
+                        .on_input(Message::PresetNameChanged)
+                        .on_submit(Message::SavePreset)
+                        .padding(10)
+                        .style(text_input_style),
+                    button("Save Preset")
+                        .on_press_maybe(can_save.then_some(Message::SavePreset))
+                        .padding(10)
+                        .style(button_style),
+                ]
+                .spacing(10),
+            ]
+            .spacing(10),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(section_style)
+        .into()
+    }
+}
+
+/// Button that shows whether its option is selected
+fn choice<'a>(label: &'a str, selected: bool, message: Message) -> Element<'a, Message> {
+    button(text(label).size(14))
+        .on_press(message)
+        .padding(8)
+        .style(move |theme: &iced::Theme, status| {
+            let mut style = button_style(theme, status);
+            if selected {
+                style.background = Some(theme.palette().primary.into());
+                style.text_color = iced::Color::WHITE;
+            }
+            style
+        })
+        .into()
+}
+
+fn labeled<'a>(label: &'a str, options: Element<'a, Message>) -> Element<'a, Message> {
+    row![text(label).width(Length::Fixed(110.0)), options]
+        .align_y(Alignment::Center)
+        .spacing(12)
+        .into()
+}
+
+fn days_input<'a>(
+    label: &'a str,
+    value: &'a str,
+    on_input: fn(String) -> Message,
+) -> Element<'a, Message> {
+    row![
+        text(label),
+        text_input("Any", value)
+            .on_input(on_input)
+            .padding(10)
+            .width(Length::Fixed(100.0))
+            .style(text_input_style),
+    ]
+    .align_y(Alignment::Center)
+    .spacing(12)
+    .into()
+}
@@ -0,0 +1,295 @@
+//! Developer view - Browse the audit log of LLM calls
+//!
+//! This view implements:
+//! - A list of recent LLM calls, filtered by text and to failed calls
+//! - The full prompt, raw response, latency and token usage of a call
+//! - Re-running a call with the current provider to compare replies
+
+use crate::constants::ui;
+use crate::models::deck::LLMCallLog;
+use crate::ui::{button_style, section_style, text_input_style};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, toggler};
+use iced::{Alignment, Element, Fill, Length, Task};
+
+#[derive(Default)]
+pub struct DeveloperView {
+    calls: Vec<LLMCallLog>,
+    filter: String,
+    errors_only: bool,
+    selected: Option<String>,
+    rerunning: bool,
+    rerun_error: Option<String>,
+    // Calls that could not be stored in the audit log
+    unrecorded_calls: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    BackToSettings,
+    FilterChanged(String),
+    ErrorsOnlyToggled(bool),
+    Select(String),
+    Rerun,
+    /// The re-run finished, with the error if it failed
+    RerunFinished(Option<String>),
+}
+
+impl DeveloperView {
+    /// Replace the listed calls, keeping the selection when it is still logged
+    pub fn set_calls(&mut self, calls: Vec<LLMCallLog>) {
+        if let Some(selected) = &self.selected
+            && !calls.iter().any(|call| &call.id == selected)
+        {
+            self.selected = None;
+        }
+        self.calls = calls;
+    }
+
+    /// Report calls that are missing from the audit log because storing them failed
+    pub fn set_unrecorded_calls(&mut self, count: usize) {
+        self.unrecorded_calls = count;
+    }
+
+    /// Select the newest call, e.g. the one a re-run just logged
+    pub fn select_newest(&mut self) {
+        self.selected = self.calls.first().map(|call| call.id.clone());
+    }
+
+    /// Call to send again, once a re-run was requested
+    pub fn pending_rerun(&self) -> Option<&LLMCallLog> {
+        self.rerunning.then(|| self.selected_call()).flatten()
+    }
+
+    fn selected_call(&self) -> Option<&LLMCallLog> {
+        let selected = self.selected.as_ref()?;
+        self.calls.iter().find(|call| &call.id == selected)
+    }
+
+    /// Calls matching the filter text and the errors-only toggle
+    fn filtered_calls(&self) -> impl Iterator<Item = &LLMCallLog> {
+        let filter = self.filter.trim().to_lowercase();
+        self.calls.iter().filter(move |call| {
+            if self.errors_only && call.error_kind.is_none() {
+                return false;
+            }
+            filter.is_empty()
+                || [
+                    Some(&call.kind),
+                    Some(&call.model),
+                    call.word.as_ref(),
+                    call.error_kind.as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .chain(call.messages.iter().map(|message| &message.text))
+                .any(|field| field.to_lowercase().contains(&filter))
+        })
+    }
+}
+
+impl DeveloperView {
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::BackToSettings => Task::none(),
+            Message::FilterChanged(filter) => {
+                self.filter = filter;
+                Task::none()
+            }
+            Message::ErrorsOnlyToggled(errors_only) => {
+                self.errors_only = errors_only;
+                Task::none()
+            }
+            Message::Select(id) => {
+                if !self.rerunning {
+                    self.selected = Some(id);
+                    self.rerun_error = None;
+                }
+                Task::none()
+            }
+            Message::Rerun => {
+                // The parent sends the call again once it is pending
+                if self.selected_call().is_some() {
+                    self.rerunning = true;
+                    self.rerun_error = None;
+                }
+                Task::none()
+            }
+            Message::RerunFinished(error) => {
+                self.rerunning = false;
+                self.rerun_error = error;
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let title = text("Developer Log").size(32);
+        let subtitle = text(format!(
+            "The last {} LLM calls, with API keys redacted",
+            crate::constants::llm::AUDIT_LOG_LIMIT
+        ))
+        .size(16);
+        let unrecorded = if self.unrecorded_calls > 0 {
+            text(format!(
+                "{} call(s) could not be stored and are missing from the log; see stderr",
+                self.unrecorded_calls
+            ))
+            .size(14)
+        } else {
+            text("")
+        };
+        let back_button = button("← Back to Settings")
+            .on_press(Message::BackToSettings)
+            .padding(10)
+            .style(button_style);
+        let header = row![
+            column![title, subtitle, unrecorded]
+                .spacing(5)
+                .width(Length::Fill),
+            back_button
+        ]
+        .align_y(Alignment::Center)
+        .spacing(10);
+
+        let filters = row![
+            text_input("Filter by kind, model, word or prompt...", &self.filter)
+                .on_input(Message::FilterChanged)
+                .padding(10)
+                .width(Length::Fill)
+                .style(text_input_style),
+            toggler(self.errors_only)
+                .label("Errors only")
+                .on_toggle(Message::ErrorsOnlyToggled),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center);
+
+        let content = column![
+            header,
+            filters,
+            row![self.call_list(), self.call_details()].spacing(15),
+        ]
+        .spacing(15)
+        .padding(20)
+        .width(Length::Fill)
+        .max_width(ui::MAX_CONTENT_WIDTH);
+
+        container(content).width(Length::Fill).center_x(Fill).into()
+    }
+
+    fn call_list(&self) -> Element<'_, Message> {
+        let calls = self
+            .filtered_calls()
+            .fold(column![].spacing(6), |col, call| {
+                let outcome = match &call.error_kind {
+                    Some(kind) => format!("✗ {}", kind),
+                    None => format!("{} tokens", call.total_tokens),
+                };
+                let selected = self.selected.as_ref() == Some(&call.id);
+                col.push(
+                    button(
+                        column![
+                            text(format!(
+                                "{} · {}",
+                                call.created_at.format("%m-%d %H:%M:%S"),
+                                call.kind
+                            ))
+                            .size(14),
+                            text(format!("{} ms · {}", call.latency_ms, outcome)).size(12),
+                        ]
+                        .spacing(2),
+                    )
+                    .on_press_maybe((!selected).then(|| Message::Select(call.id.clone())))
+                    .padding(8)
+                    .width(Length::Fill)
+                    .style(button_style),
+                )
+            });
+
+        let list: Element<'_, Message> = if self.calls.is_empty() {
+            text("No LLM calls have been logged yet.").size(14).into()
+        } else {
+            scrollable(calls).into()
+        };
+
+        container(list)
+            .padding(10)
+            .width(Length::FillPortion(1))
+            .height(Length::Fill)
+            .style(section_style)
+            .into()
+    }
+
+    fn call_details(&self) -> Element<'_, Message> {
+        let Some(call) = self.selected_call() else {
+            return container(text("Select a call to see its prompt and response.").size(14))
+                .padding(15)
+                .width(Length::FillPortion(2))
+                .height(Length::Fill)
+                .style(section_style)
+                .into();
+        };
+
+        let summary = text(format!(
+            "{} · {} · {} · {} ms · {} tokens{}",
+            call.provider,
+            call.model,
+            call.created_at.format("%Y-%m-%d %H:%M:%S"),
+            call.latency_ms,
+            call.total_tokens,
+            if call.json_output { " · JSON" } else { "" }
+        ))
+        .size(12);
+
+        let rerun_button = button(if self.rerunning {
+            "Re-running..."
+        } else {
+            "Re-run"
+        })
+        .on_press_maybe((!self.rerunning).then_some(Message::Rerun))
+        .padding(8)
+        .style(button_style);
+
+        let mut details = column![
+            row![
+                text(format!("{} call", call.kind))
+                    .size(20)
+                    .width(Length::Fill),
+                rerun_button
+            ]
+            .align_y(Alignment::Center),
+            summary,
+        ]
+        .spacing(10);
+
+        if let Some(error) = &self.rerun_error {
+            details = details.push(text(format!("Re-run failed: {}", error)).size(12));
+        }
+        if let Some(system) = &call.system {
+            details = details.push(labeled("System", system));
+        }
+        for message in &call.messages {
+            details = details.push(labeled(&message.role, &message.text));
+        }
+        if let Some(response) = &call.response {
+            details = details.push(labeled("Response", response));
+        }
+        if let (Some(kind), Some(error)) = (&call.error_kind, &call.error) {
+            details = details.push(labeled(kind, error));
+        }
+
+        container(scrollable(details))
+            .padding(15)
+            .width(Length::FillPortion(2))
+            .height(Length::Fill)
+            .style(section_style)
+            .into()
+    }
+}
+
+/// A logged text under a small heading
+fn labeled<'a>(label: &'a str, content: &'a str) -> Element<'a, Message> {
+    column![text(label).size(12), text(content).size(14)]
+        .spacing(4)
+        .into()
+}
@@ -2,21 +2,38 @@
 //!
 //! This view allows users to:
 //! - Input Japanese text for practice or learning
-//! - View and select existing decks
+//! - Follow and cancel batch flashcard generation
+//! - View and select existing decks, and choose each deck's scheduler
+//! - Study the due cards of every deck at once, or build a custom session
+//! - See how today's answers went
+//! - Fix leeches (cards failed over and over): edit, regenerate or reset them
 //! - View and continue saved texts
-//! - Navigate to settings
+//! - Navigate to conversation practice and settings
 
 use crate::constants;
+use crate::models::flashcard::{FlashCard, SchedulerKind};
 use crate::models::{DeckInfo, TextInfo};
+use crate::services::card_generator::GenerationProgress;
+use crate::services::study_day::DayStats;
 use crate::ui::{button_style, section_style, text_input_style};
-use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use iced::widget::{button, column, container, progress_bar, row, scrollable, text, text_input};
 use iced::{Alignment, Element, Fill, Length, Task};
+use std::collections::HashMap;
 
 #[derive(Default)]
 pub struct HomeView {
     input_text: String,
     decks: Vec<DeckInfo>,
     saved_texts: Vec<TextInfo>,
+    generation: Option<GenerationProgress>,
+    status: Option<String>,
+    today: DayStats,
+    leeches: Vec<FlashCard>,
+    // Wrong answer picked most often for each leech, by card id
+    leech_mistakes: HashMap<String, String>,
+    // Leech whose answer is being edited, with the edited text
+    editing_leech: Option<(String, String)>,
+    regenerating_leech: Option<String>,
 }
 
 impl HomeView {
@@ -24,6 +41,79 @@ impl HomeView {
     fn has_valid_input(&self) -> bool {
         !self.input_text.trim().is_empty()
     }
+
+    /// Check if the mode buttons can be used
+    fn can_submit(&self) -> bool {
+        self.has_valid_input() && self.generation.is_none()
+    }
+
+    /// Text currently in the input field
+    pub fn input_text(&self) -> &str {
+        &self.input_text
+    }
+
+    /// Replace the list of decks
+    pub fn set_decks(&mut self, decks: Vec<DeckInfo>) {
+        self.decks = decks;
+    }
+
+    /// Listed decks
+    pub fn decks(&self) -> &[DeckInfo] {
+        &self.decks
+    }
+
+    /// Ids of all listed decks
+    pub fn deck_ids(&self) -> Vec<String> {
+        self.decks.iter().map(|deck| deck.id.clone()).collect()
+    }
+
+    /// Replace the statistics of today's answers
+    pub fn set_day_stats(&mut self, today: DayStats) {
+        self.today = today;
+    }
+
+    /// Replace the list of leeches, with the wrong answer each is usually mistaken for
+    pub fn set_leeches(&mut self, leeches: Vec<FlashCard>, mistakes: HashMap<String, String>) {
+        self.leeches = leeches;
+        self.leech_mistakes = mistakes;
+    }
+
+    /// Leech with the given id
+    pub fn leech(&self, id: &str) -> Option<&FlashCard> {
+        self.leeches.iter().find(|card| card.id == id)
+    }
+
+    /// Id and edited answer of the leech being edited
+    pub fn leech_edit(&self) -> Option<(&str, &str)> {
+        self.editing_leech
+            .as_ref()
+            .map(|(id, answer)| (id.as_str(), answer.as_str()))
+    }
+
+    /// Close the leech editor
+    pub fn finish_leech_edit(&mut self) {
+        self.editing_leech = None;
+    }
+
+    /// Mark a leech as waiting for its regenerated card, or none
+    pub fn set_regenerating_leech(&mut self, id: Option<String>) {
+        self.regenerating_leech = id;
+    }
+
+    /// Replace the list of saved texts
+    pub fn set_saved_texts(&mut self, saved_texts: Vec<TextInfo>) {
+        self.saved_texts = saved_texts;
+    }
+
+    /// Show or hide the card generation progress
+    pub fn set_generation_progress(&mut self, progress: Option<GenerationProgress>) {
+        self.generation = progress;
+    }
+
+    /// Show or clear a status message below the mode buttons
+    pub fn set_status(&mut self, status: Option<String>) {
+        self.status = status;
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -32,8 +122,19 @@ pub enum Message {
     SubmitForPractice,
     SubmitForLearning,
     SelectDeck(String),
+    StudyAllDue,
+    OpenCustomSession,
+    EditLeech(String),
+    LeechAnswerChanged(String),
+    SaveLeech,
+    CancelLeechEdit,
+    RegenerateLeech(String),
+    ResetLeech(String),
+    SetDeckScheduler(String, SchedulerKind),
     SelectText(String),
     NavigateToSettings,
+    NavigateToConversation,
+    CancelGeneration,
 }
 
 impl HomeView {
@@ -61,7 +162,30 @@ impl HomeView {
                 println!("Selected text: {}", id);
                 Task::none()
             }
+            Message::SetDeckScheduler(..) => Task::none(),
+            Message::StudyAllDue | Message::OpenCustomSession => Task::none(),
+            Message::EditLeech(id) => {
+                self.editing_leech = self
+                    .leech(&id)
+                    .map(|card| (id, card.card_type.correct_answer().to_string()));
+                Task::none()
+            }
+            Message::LeechAnswerChanged(answer) => {
+                if let Some((_, edited)) = &mut self.editing_leech {
+                    *edited = answer;
+                }
+                Task::none()
+            }
+            Message::CancelLeechEdit => {
+                self.editing_leech = None;
+                Task::none()
+            }
+            Message::SaveLeech | Message::RegenerateLeech(_) | Message::ResetLeech(_) => {
+                Task::none()
+            }
             Message::NavigateToSettings => Task::none(),
+            Message::NavigateToConversation => Task::none(),
+            Message::CancelGeneration => Task::none(),
         }
     }
 
@@ -95,7 +219,7 @@ impl HomeView {
         .width(Length::Fill)
         .style(button_style);
 
-        let practice_button = if self.has_valid_input() {
+        let practice_button = if self.can_submit() {
             practice_button.on_press(Message::SubmitForPractice)
         } else {
             practice_button
@@ -111,7 +235,7 @@ impl HomeView {
         .width(Length::Fill)
         .style(button_style);
 
-        let learning_button = if self.has_valid_input() {
+        let learning_button = if self.can_submit() {
             learning_button.on_press(Message::SubmitForLearning)
         } else {
             learning_button
@@ -121,8 +245,37 @@ impl HomeView {
             .spacing(10)
             .width(Length::Fill);
 
+        let generation_section = match &self.generation {
+            Some(progress) => self.generation_progress(progress),
+            None => column![].into(),
+        };
+
+        let status = match &self.status {
+            Some(status) => text(status).size(14),
+            None => text(""),
+        };
+
         // Decks section
-        let decks_title = text("Your Decks").size(24).width(Length::Fill);
+        let due: usize = self
+            .decks
+            .iter()
+            .map(|deck| deck.due_cards)
+            .sum();
+        let study_all_button = button(text(format!("Study All Due ({})", due)))
+            .on_press_maybe((due > 0).then_some(Message::StudyAllDue))
+            .padding(8)
+            .style(button_style);
+        let custom_session_button = button(text("Custom Session"))
+            .on_press(Message::OpenCustomSession)
+            .padding(8)
+            .style(button_style);
+        let decks_title = row![
+            text("Your Decks").size(24).width(Length::Fill),
+            custom_session_button,
+            study_all_button
+        ]
+        .align_y(Alignment::Center)
+        .spacing(10);
 
         let decks_list = if self.decks.is_empty() {
             column![text("No decks yet. Create one by practicing some text!").size(14)].spacing(5)
@@ -146,6 +299,11 @@ impl HomeView {
                 })
         };
 
+        let conversation_button = button("Conversation")
+            .on_press(Message::NavigateToConversation)
+            .padding(10)
+            .style(button_style);
+
         // Settings button
         let settings_button = button("Settings")
             .on_press(Message::NavigateToSettings)
@@ -155,7 +313,7 @@ impl HomeView {
         // Main layout
         let content = column![
             // Header
-            row![title, settings_button]
+            row![title, conversation_button, settings_button]
                 .align_y(Alignment::Center)
                 .spacing(10),
             subtitle,
@@ -163,6 +321,10 @@ impl HomeView {
             text("Input Text").size(20),
             input,
             buttons,
+            generation_section,
+            status,
+            self.day_stats(),
+            self.leech_notice(),
             // Content sections
             decks_title,
             decks_list,
@@ -181,6 +343,42 @@ impl HomeView {
             .into()
     }
 
+    fn generation_progress(&self, progress: &GenerationProgress) -> Element<'_, Message> {
+        let label = if progress.failed > 0 {
+            format!(
+                "Generating flashcards... {}/{} ({} failed)",
+                progress.processed(),
+                progress.total,
+                progress.failed
+            )
+        } else {
+            format!(
+                "Generating flashcards... {}/{}",
+                progress.processed(),
+                progress.total
+            )
+        };
+
+        let cancel_button = button(text("Cancel"))
+            .on_press(Message::CancelGeneration)
+            .padding(8)
+            .style(button_style);
+
+        container(
+            column![
+                row![text(label).size(14).width(Length::Fill), cancel_button]
+                    .align_y(Alignment::Center)
+                    .spacing(10),
+                progress_bar(0.0..=progress.total as f32, progress.processed() as f32),
+            ]
+            .spacing(10)
+            .padding(15),
+        )
+        .width(Length::Fill)
+        .style(section_style)
+        .into()
+    }
+
     fn deck_card<'a>(&self, deck: &'a DeckInfo) -> Element<'a, Message> {
         let name = text(&deck.name).size(18);
 
@@ -195,9 +393,19 @@ impl HomeView {
             .padding(8)
             .style(button_style);
 
+        let other_scheduler = match deck.scheduler {
+            SchedulerKind::Sm2 => SchedulerKind::Fsrs,
+            SchedulerKind::Fsrs => SchedulerKind::Sm2,
+        };
+        let scheduler_button = button(text(format!("Scheduler: {}", deck.scheduler.as_str())))
+            .on_press(Message::SetDeckScheduler(deck.id.clone(), other_scheduler))
+            .padding(8)
+            .style(button_style);
+
         container(
             row![
                 column![name, stats].spacing(5).width(Length::Fill),
+                scheduler_button,
                 open_button
             ]
             .align_y(Alignment::Center)
@@ -209,6 +417,122 @@ impl HomeView {
         .into()
     }
 
+    /// Line summing up the answers given today
+    fn day_stats(&self) -> Element<'_, Message> {
+        let today = &self.today;
+        if today.answers == 0 {
+            return column![].into();
+        }
+
+        let mut summary = format!(
+            "Today: {} answer(s), {}% correct",
+            today.answers,
+            today.correct * 100 / today.answers
+        );
+        if let Some(average) = today.average_response {
+            summary.push_str(&format!(
+                ", {:.1}s per answer",
+                average.num_milliseconds() as f32 / 1000.0
+            ));
+        }
+        text(summary).size(14).into()
+    }
+
+    /// Notice listing the leeches, with ways to fix each one
+    fn leech_notice(&self) -> Element<'_, Message> {
+        if self.leeches.is_empty() {
+            return column![].into();
+        }
+
+        let intro = text(format!(
+            "{} card(s) keep being forgotten. Rewrite, regenerate or reset them.",
+            self.leeches.len()
+        ))
+        .size(14);
+        let leeches = self
+            .leeches
+            .iter()
+            .fold(column![intro].spacing(10), |col, card| {
+                col.push(self.leech_row(card))
+            });
+
+        container(
+            column![text("Leeches").size(20), leeches]
+                .spacing(10)
+                .padding(15),
+        )
+        .width(Length::Fill)
+        .style(section_style)
+        .into()
+    }
+
+    fn leech_row<'a>(&'a self, card: &'a FlashCard) -> Element<'a, Message> {
+        if let Some((id, answer)) = &self.editing_leech
+            && *id == card.id
+        {
+            return row![
+                text(card.card_type.front()).size(16),
+                text_input("Answer", answer)
+                    .on_input(Message::LeechAnswerChanged)
+                    .on_submit(Message::SaveLeech)
+                    .padding(8)
+                    .style(text_input_style),
+                button(text("Save"))
+                    .on_press_maybe((!answer.trim().is_empty()).then_some(Message::SaveLeech))
+                    .padding(8)
+                    .style(button_style),
+                button(text("Cancel"))
+                    .on_press(Message::CancelLeechEdit)
+                    .padding(8)
+                    .style(button_style),
+            ]
+            .align_y(Alignment::Center)
+            .spacing(10)
+            .into();
+        }
+
+        let srs = &card.srs_data;
+        let mut details = format!(
+            "{} — forgotten {} times{}",
+            card.card_type.correct_answer(),
+            srs.lapses,
+            if srs.suspended { ", suspended" } else { "" }
+        );
+        if let Some(mistake) = self.leech_mistakes.get(&card.id) {
+            details.push_str(&format!(", often mistaken for {}", mistake));
+        }
+        let regenerating = self.regenerating_leech.as_deref() == Some(card.id.as_str());
+        let can_regenerate = self.regenerating_leech.is_none();
+
+        row![
+            column![
+                text(card.card_type.front()).size(16),
+                text(details).size(12)
+            ]
+            .spacing(3)
+            .width(Length::Fill),
+            button(text("Edit"))
+                .on_press(Message::EditLeech(card.id.clone()))
+                .padding(8)
+                .style(button_style),
+            button(text(if regenerating {
+                "Regenerating..."
+            } else {
+                "Regenerate"
+            }))
+            .on_press_maybe(can_regenerate.then(|| Message::RegenerateLeech(card.id.clone())))
+            .padding(8)
+            .style(button_style),
+            button(text("Reset"))
+                .on_press(Message::ResetLeech(card.id.clone()))
+                .padding(8)
+                .style(button_style),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(10)
+        .into()
+    }
+
     fn text_card<'a>(&self, text_info: &'a TextInfo) -> Element<'a, Message> {
         let title = text(&text_info.title).size(16);
 
@@ -6,14 +6,51 @@
 //! - Context-aware grammar and vocabulary help
 //! - On-demand LLM processing with loading states
 //! - Add words to flashcards functionality
+//! - Follow-up questions with a conversation kept per text
+//! - Warnings when an explanation disagrees with local reading or dictionary data
+//! - Correcting an explanation by hand, or regenerating it with feedback
+//! - Sentence translations with hover-linked Japanese and English phrases
+//! - Graded reader rewrites studied side by side with their source text
 
 use crate::constants::ui;
-use crate::models::{ExampleSentence, WordExplanation, WordSegment};
+use crate::models::deck::LearningText;
+use crate::models::word::{sentence_ranges, sentences};
+use crate::models::{
+    ExampleSentence, JLPTLevel, SentenceTranslation, WordExplanation, WordSegment,
+};
+use crate::services::verification::{self, Mismatch};
 use crate::ui::{button_style, section_style, text_input_style};
 use iced::widget::{
-    button, column, container, row, scrollable, text, text_input, Space,
+    button, column, container, mouse_area, row, scrollable, text, text_input, Space,
 };
 use iced::{Alignment, Color, Element, Fill, Length, Task};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Highlight for segments and English phrases linked to the hovered one
+const ALIGNMENT_HIGHLIGHT: Color = Color::from_rgb(0.85, 0.65, 0.2);
+
+/// The other text of a graded reader pair, shown beside the one being studied
+#[derive(Debug, Clone)]
+pub struct Companion {
+    pub text_id: String,
+    pub sentences: Vec<String>,
+    pub level: JLPTLevel,
+    /// Whether the companion is the rewrite, and the studied text its source
+    pub is_rewrite: bool,
+    /// For each sentence of the rewrite, the source sentence it retells
+    pub sentence_map: Vec<Option<usize>>,
+}
+
+/// Part of an explanation being edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftField {
+    Meaning,
+    Reading,
+    GrammarNotes,
+    ExampleJapanese(usize),
+    ExampleTranslation(usize),
+}
 
 /// Loading state for LLM explanation
 #[derive(Debug, Clone, PartialEq)]
@@ -25,6 +62,9 @@ enum LoadingState {
 }
 
 pub struct LearningView {
+    // Saved text being studied
+    text_id: Option<String>,
+
     // Current text being studied
     original_text: String,
     
@@ -36,77 +76,84 @@ pub struct LearningView {
     
     // Loading state for LLM
     loading_state: LoadingState,
+
+    // Differences from local data per explained word index
+    mismatches: HashMap<usize, Vec<Mismatch>>,
+
+    // Correction of the selected word's explanation being edited
+    draft: Option<WordExplanation>,
+
+    // Feedback to regenerate the selected word's explanation with
+    feedback_input: String,
+
+    // Feedback of the pending explanation request, when it must bypass stored explanations
+    regeneration: Option<String>,
     
     // User question input
     question_input: String,
     
     // LLM responses to user questions
     qa_history: Vec<(String, String)>, // (question, answer)
+
+    // Waiting for the answer to the last question
+    awaiting_answer: bool,
+
+    // Error from the last question, if it failed
+    question_error: Option<String>,
+
+    // Segment range of the sentence being translated or shown
+    translation_range: Option<Range<usize>>,
+
+    // Translation of that sentence, once received
+    translation: Option<SentenceTranslation>,
+
+    // Waiting for the translation
+    translation_loading: bool,
+
+    // Error from the last translation request
+    translation_error: Option<String>,
+
+    // Alignment under the mouse, on either side
+    hovered_alignment: Option<usize>,
+
+    // Level to rewrite the text at
+    rewrite_level: JLPTLevel,
+
+    // Waiting for a graded reader rewrite
+    rewriting: bool,
+
+    // Error from the last rewrite request
+    rewrite_error: Option<String>,
+
+    // Linked source text or rewrite
+    companion: Option<Companion>,
 }
 
 impl Default for LearningView {
     fn default() -> Self {
-        // Create sample parsed text for demonstration
-        let sample_text = "今日は日本語を勉強します。";
-        let sample_segments = vec![
-            WordSegment {
-                surface: "今日".to_string(),
-                reading: "きょう".to_string(),
-                base_form: "今日".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-            WordSegment {
-                surface: "は".to_string(),
-                reading: "は".to_string(),
-                base_form: "は".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-            WordSegment {
-                surface: "日本語".to_string(),
-                reading: "にほんご".to_string(),
-                base_form: "日本語".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-            WordSegment {
-                surface: "を".to_string(),
-                reading: "を".to_string(),
-                base_form: "を".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-            WordSegment {
-                surface: "勉強".to_string(),
-                reading: "べんきょう".to_string(),
-                base_form: "勉強".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-            WordSegment {
-                surface: "します".to_string(),
-                reading: "します".to_string(),
-                base_form: "する".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-            WordSegment {
-                surface: "。".to_string(),
-                reading: "。".to_string(),
-                base_form: "。".to_string(),
-                explanation: None,
-                is_selected: false,
-            },
-        ];
-
         Self {
-            original_text: sample_text.to_string(),
-            word_segments: sample_segments,
+            text_id: None,
+            original_text: String::new(),
+            word_segments: Vec::new(),
             selected_word_index: None,
             loading_state: LoadingState::Idle,
+            mismatches: HashMap::new(),
+            draft: None,
+            feedback_input: String::new(),
+            regeneration: None,
             question_input: String::new(),
             qa_history: Vec::new(),
+            awaiting_answer: false,
+            question_error: None,
+            translation_range: None,
+            translation: None,
+            translation_loading: false,
+            translation_error: None,
+            hovered_alignment: None,
+            rewrite_level: JLPTLevel::N5,
+            rewriting: false,
+            rewrite_error: None,
+            companion: None,
         }
     }
 }
@@ -116,18 +163,226 @@ pub enum Message {
     BackToHome,
     SelectWord(usize),
     RequestExplanation,
-    ExplanationReceived(WordExplanation),
-    ExplanationError(String),
+    ExplanationReceived(usize, WordExplanation, Vec<Mismatch>),
+    ExplanationError(usize, String),
+    AcceptLocalData,
+    EditExplanation,
+    DraftChanged(DraftField, String),
+    DraftLevelSelected(JLPTLevel),
+    AddDraftExample,
+    RemoveDraftExample(usize),
+    SaveExplanation,
+    CancelEdit,
+    FeedbackChanged(String),
+    RegenerateExplanation,
     AddToVocabularyFlashcards,
     AddToGrammarFlashcards,
     QuestionInputChanged(String),
     AskQuestion,
     QuestionAnswered(String),
+    QuestionFailed(String),
+    RequestTranslation,
+    TranslationReceived(usize, SentenceTranslation),
+    TranslationFailed(usize, String),
+    HoverSegment(Option<usize>),
+    HoverPhrase(Option<usize>),
     NextWord,
     PreviousWord,
+    RewriteLevelSelected(JLPTLevel),
+    RequestRewrite,
+    RewriteSaved(Companion),
+    RewriteFailed(String),
+    /// Open the companion text at a sentence, or at the one matching the current sentence
+    JumpToCompanion(Option<usize>),
 }
 
 impl LearningView {
+    /// Study a saved text, restoring its earlier questions and answers
+    pub fn load_text(&mut self, text: LearningText, qa_history: Vec<(String, String)>) {
+        self.text_id = Some(text.id);
+        self.original_text = text.original_text;
+        self.word_segments = text.tokenized_segments;
+        self.selected_word_index = None;
+        self.loading_state = LoadingState::Idle;
+        self.mismatches.clear();
+        self.draft = None;
+        self.feedback_input.clear();
+        self.regeneration = None;
+        self.question_input.clear();
+        self.qa_history = qa_history;
+        self.awaiting_answer = false;
+        self.question_error = None;
+        self.clear_translation();
+        self.rewriting = false;
+        self.rewrite_error = None;
+        self.companion = None;
+    }
+
+    /// Show a linked source text or rewrite beside the text
+    pub fn set_companion(&mut self, companion: Option<Companion>) {
+        self.companion = companion;
+    }
+
+    fn clear_translation(&mut self) {
+        self.translation_range = None;
+        self.translation = None;
+        self.translation_loading = false;
+        self.translation_error = None;
+        self.hovered_alignment = None;
+    }
+
+    /// Id of the saved text being studied
+    pub fn text_id(&self) -> Option<&str> {
+        self.text_id.as_deref()
+    }
+
+    /// Full text being studied
+    pub fn original_text(&self) -> &str {
+        &self.original_text
+    }
+
+    /// Answered questions, oldest first (excludes a question still waiting for its answer)
+    pub fn answered_questions(&self) -> &[(String, String)] {
+        let answered = if self.awaiting_answer {
+            self.qa_history.len().saturating_sub(1)
+        } else {
+            self.qa_history.len()
+        };
+        &self.qa_history[..answered]
+    }
+
+    /// Question waiting for its answer
+    pub fn pending_question(&self) -> Option<&str> {
+        self.awaiting_answer
+            .then(|| self.qa_history.last())
+            .flatten()
+            .map(|(question, _)| question.as_str())
+    }
+
+    /// Selected word whose explanation should be requested
+    pub fn pending_explanation(&self) -> Option<(usize, &WordSegment)> {
+        if self.loading_state != LoadingState::Loading {
+            return None;
+        }
+        let index = self.selected_word_index?;
+        self.word_segments.get(index).map(|segment| (index, segment))
+    }
+
+    /// Feedback for the pending explanation, when it must be generated afresh
+    ///
+    /// Regenerated explanations skip the cache and replace the user's
+    /// correction. The feedback may be empty.
+    pub fn regeneration_feedback(&self) -> Option<&str> {
+        self.pending_explanation()?;
+        self.regeneration.as_deref()
+    }
+
+    /// Selected word and its explanation, once the user has corrected it
+    pub fn edited_explanation(&self) -> Option<(&str, &WordExplanation)> {
+        let segment = self.word_segments.get(self.selected_word_index?)?;
+        let explanation = segment.explanation.as_ref().filter(|e| e.user_edited)?;
+        Some((&segment.base_form, explanation))
+    }
+
+    /// Sentence waiting for its translation, with the index of its first segment
+    pub fn pending_translation(&self) -> Option<(usize, &[WordSegment])> {
+        if !self.translation_loading {
+            return None;
+        }
+        let range = self.translation_range.clone()?;
+        Some((range.start, &self.word_segments[range]))
+    }
+
+    /// Sentences of the text and the level to rewrite them at, while a rewrite is requested
+    pub fn pending_rewrite(&self) -> Option<(Vec<String>, JLPTLevel)> {
+        self.rewriting
+            .then(|| (sentences(&self.word_segments), self.rewrite_level))
+    }
+
+    /// Companion text to open, and the sentence to open it at
+    ///
+    /// Without a `sentence`, the first companion sentence matching the current
+    /// sentence is used.
+    pub fn companion_jump(&self, sentence: Option<usize>) -> Option<(String, Option<usize>)> {
+        let companion = self.companion.as_ref()?;
+        let sentence = sentence.or_else(|| self.corresponding_sentences().first().copied());
+        Some((companion.text_id.clone(), sentence))
+    }
+
+    /// Index of the first segment of a sentence
+    pub fn sentence_start(&self, sentence: usize) -> Option<usize> {
+        sentence_ranges(&self.word_segments)
+            .get(sentence)
+            .map(|range| range.start)
+    }
+
+    /// Index of the sentence containing the selected word, or of the first sentence
+    fn current_sentence_index(&self) -> Option<usize> {
+        let index = self.selected_word_index.unwrap_or(0);
+        sentence_ranges(&self.word_segments)
+            .iter()
+            .position(|range| range.contains(&index))
+    }
+
+    /// Companion sentences matching the current sentence
+    fn corresponding_sentences(&self) -> Vec<usize> {
+        let (Some(companion), Some(current)) = (&self.companion, self.current_sentence_index()) else {
+            return Vec::new();
+        };
+
+        if companion.is_rewrite {
+            companion
+                .sentence_map
+                .iter()
+                .enumerate()
+                .filter(|(_, source)| **source == Some(current))
+                .map(|(index, _)| index)
+                .collect()
+        } else {
+            companion
+                .sentence_map
+                .get(current)
+                .copied()
+                .flatten()
+                .into_iter()
+                .collect()
+        }
+    }
+
+    /// Segment range of the sentence containing the selected word, or of the first sentence
+    fn current_sentence(&self) -> Option<Range<usize>> {
+        let index = self.selected_word_index.unwrap_or(0);
+        if index >= self.word_segments.len() {
+            return None;
+        }
+
+        let start = self.word_segments[..index]
+            .iter()
+            .rposition(WordSegment::ends_sentence)
+            .map_or(0, |end| end + 1);
+        let end = self.word_segments[index..]
+            .iter()
+            .position(WordSegment::ends_sentence)
+            .map_or(self.word_segments.len(), |offset| index + offset + 1);
+        Some(start..end)
+    }
+
+    /// Alignment of the shown translation that covers a segment of the text
+    fn alignment_for_segment(&self, index: usize) -> Option<usize> {
+        let range = self.translation_range.as_ref()?;
+        if !range.contains(&index) {
+            return None;
+        }
+        self.translation
+            .as_ref()?
+            .alignment_for_segment(index - range.start)
+    }
+
+    /// Check if a segment belongs to the hovered alignment
+    fn is_segment_highlighted(&self, index: usize) -> bool {
+        self.hovered_alignment.is_some() && self.alignment_for_segment(index) == self.hovered_alignment
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::BackToHome => Task::none(),
@@ -142,16 +397,13 @@ impl LearningView {
                 if let Some(segment) = self.word_segments.get_mut(index) {
                     segment.is_selected = true;
                     self.selected_word_index = Some(index);
+                    self.draft = None;
+                    self.feedback_input.clear();
+                    self.regeneration = None;
                     
-                    // If no explanation exists, request one
+                    // If no explanation exists, the parent requests one
                     if segment.explanation.is_none() {
                         self.loading_state = LoadingState::Loading;
-                        // TODO: In real implementation, spawn async task to call LLM
-                        // For now, simulate with sample data
-                        let surface = segment.surface.clone();
-                        return Task::done(Message::ExplanationReceived(
-                            self.generate_sample_explanation(&surface),
-                        ));
                     } else {
                         self.loading_state = LoadingState::Loaded;
                     }
@@ -160,33 +412,147 @@ impl LearningView {
             }
             
             Message::RequestExplanation => {
-                if let Some(index) = self.selected_word_index {
+                // The parent sends the request once the selected word is loading
+                if self.selected_word_index.is_some() {
                     self.loading_state = LoadingState::Loading;
-                    if let Some(segment) = self.word_segments.get(index) {
-                        // TODO: Real LLM call here
-                        return Task::done(Message::ExplanationReceived(
-                            self.generate_sample_explanation(&segment.surface),
-                        ));
-                    }
+                    self.draft = None;
+                    self.regeneration = Some(String::new());
                 }
                 Task::none()
             }
             
-            Message::ExplanationReceived(explanation) => {
-                if let Some(index) = self.selected_word_index {
-                    if let Some(segment) = self.word_segments.get_mut(index) {
-                        segment.explanation = Some(explanation);
+            Message::ExplanationReceived(index, explanation, mismatches) => {
+                if let Some(segment) = self.word_segments.get_mut(index) {
+                    segment.explanation = Some(explanation);
+                    self.mismatches.insert(index, mismatches);
+                    // The user may have moved on to another word meanwhile
+                    if self.selected_word_index == Some(index) {
                         self.loading_state = LoadingState::Loaded;
+                        self.feedback_input.clear();
+                        self.regeneration = None;
                     }
                 }
                 Task::none()
             }
             
-            Message::ExplanationError(error) => {
-                self.loading_state = LoadingState::Error(error);
+            Message::ExplanationError(index, error) => {
+                if self.selected_word_index == Some(index) {
+                    self.loading_state = LoadingState::Error(error);
+                    self.regeneration = None;
+                }
                 Task::none()
             }
             
+            Message::AcceptLocalData => {
+                if let Some(index) = self.selected_word_index
+                    && let Some(mismatches) = self.mismatches.remove(&index)
+                    && let Some(explanation) = self
+                        .word_segments
+                        .get_mut(index)
+                        .and_then(|segment| segment.explanation.as_mut())
+                {
+                    verification::accept_local_explanation(explanation, &mismatches);
+                }
+                Task::none()
+            }
+
+            Message::EditExplanation => {
+                self.draft = self
+                    .selected_word_index
+                    .and_then(|index| self.word_segments.get(index))
+                    .and_then(|segment| segment.explanation.clone());
+                Task::none()
+            }
+
+            Message::DraftChanged(field, value) => {
+                if let Some(draft) = &mut self.draft {
+                    match field {
+                        DraftField::Meaning => draft.meaning = value,
+                        DraftField::Reading => draft.reading = Some(value),
+                        DraftField::GrammarNotes => draft.grammar_notes = Some(value),
+                        DraftField::ExampleJapanese(example) => {
+                            if let Some(example) = draft.examples.get_mut(example) {
+                                example.japanese = value;
+                            }
+                        }
+                        DraftField::ExampleTranslation(example) => {
+                            if let Some(example) = draft.examples.get_mut(example) {
+                                example.translation = value;
+                            }
+                        }
+                    }
+                }
+                Task::none()
+            }
+
+            Message::DraftLevelSelected(level) => {
+                if let Some(draft) = &mut self.draft {
+                    draft.jlpt_level = level.as_str().to_string();
+                }
+                Task::none()
+            }
+
+            Message::AddDraftExample => {
+                if let Some(draft) = &mut self.draft {
+                    draft.examples.push(ExampleSentence {
+                        japanese: String::new(),
+                        translation: String::new(),
+                        language: draft.language,
+                    });
+                }
+                Task::none()
+            }
+
+            Message::RemoveDraftExample(example) => {
+                if let Some(draft) = &mut self.draft
+                    && example < draft.examples.len()
+                {
+                    draft.examples.remove(example);
+                }
+                Task::none()
+            }
+
+            Message::SaveExplanation => {
+                // The parent stores the correction once it is applied
+                if let Some(index) = self.selected_word_index
+                    && let Some(draft) = self.draft.take()
+                    && let Some(base_form) = self
+                        .word_segments
+                        .get(index)
+                        .map(|segment| segment.base_form.clone())
+                {
+                    let explanation = finish_draft(draft);
+                    // The correction applies to every occurrence of the word
+                    for (other, segment) in self.word_segments.iter_mut().enumerate() {
+                        if segment.base_form == base_form {
+                            segment.explanation = Some(explanation.clone());
+                            self.mismatches.remove(&other);
+                        }
+                    }
+                }
+                Task::none()
+            }
+
+            Message::CancelEdit => {
+                self.draft = None;
+                Task::none()
+            }
+
+            Message::FeedbackChanged(feedback) => {
+                self.feedback_input = feedback;
+                Task::none()
+            }
+
+            Message::RegenerateExplanation => {
+                // The parent sends the request once the selected word is loading
+                if self.selected_word_index.is_some() {
+                    self.loading_state = LoadingState::Loading;
+                    self.draft = None;
+                    self.regeneration = Some(self.feedback_input.trim().to_string());
+                }
+                Task::none()
+            }
+
             Message::AddToVocabularyFlashcards => {
                 if let Some(index) = self.selected_word_index {
                     if let Some(segment) = self.word_segments.get(index) {
@@ -213,26 +579,110 @@ impl LearningView {
             }
             
             Message::AskQuestion => {
-                if !self.question_input.trim().is_empty() {
-                    let question = self.question_input.clone();
+                // The parent sends the question to the LLM once it is pending
+                if !self.question_input.trim().is_empty() && !self.awaiting_answer {
+                    let question = self.question_input.trim().to_string();
                     self.question_input.clear();
-                    // TODO: Real LLM call here
-                    let answer = format!("This is a simulated answer to: '{}'", question);
-                    return Task::done(Message::QuestionAnswered(answer));
+                    self.qa_history.push((question, String::new()));
+                    self.awaiting_answer = true;
+                    self.question_error = None;
                 }
                 Task::none()
             }
             
             Message::QuestionAnswered(answer) => {
-                if let Some((_question, _)) = self.qa_history.last() {
+                if self.awaiting_answer {
                     // Update the last Q&A pair
                     if let Some(last) = self.qa_history.last_mut() {
                         last.1 = answer;
                     }
+                    self.awaiting_answer = false;
                 }
                 Task::none()
             }
+
+            Message::QuestionFailed(error) => {
+                if self.awaiting_answer {
+                    // Give the question back so it can be asked again
+                    if let Some((question, _)) = self.qa_history.pop() {
+                        self.question_input = question;
+                    }
+                    self.awaiting_answer = false;
+                }
+                self.question_error = Some(error);
+                Task::none()
+            }
             
+            Message::RequestTranslation => {
+                // The parent sends the sentence once the translation is loading
+                if let Some(range) = self.current_sentence() {
+                    self.clear_translation();
+                    self.translation_range = Some(range);
+                    self.translation_loading = true;
+                }
+                Task::none()
+            }
+
+            Message::TranslationReceived(start, translation) => {
+                // Ignore replies for a sentence that is no longer shown
+                if self.translation_loading
+                    && self.translation_range.as_ref().map(|range| range.start) == Some(start)
+                {
+                    self.translation = Some(translation);
+                    self.translation_loading = false;
+                }
+                Task::none()
+            }
+
+            Message::TranslationFailed(start, error) => {
+                if self.translation_loading
+                    && self.translation_range.as_ref().map(|range| range.start) == Some(start)
+                {
+                    self.translation_error = Some(error);
+                    self.translation_loading = false;
+                }
+                Task::none()
+            }
+
+            Message::HoverSegment(index) => {
+                self.hovered_alignment = index.and_then(|index| self.alignment_for_segment(index));
+                Task::none()
+            }
+
+            Message::HoverPhrase(alignment) => {
+                self.hovered_alignment = alignment;
+                Task::none()
+            }
+
+            Message::RewriteLevelSelected(level) => {
+                self.rewrite_level = level;
+                Task::none()
+            }
+
+            Message::RequestRewrite => {
+                // The parent sends the text to the LLM once the rewrite is pending
+                if !self.rewriting && !self.word_segments.is_empty() {
+                    self.rewriting = true;
+                    self.rewrite_error = None;
+                }
+                Task::none()
+            }
+
+            Message::RewriteSaved(companion) => {
+                self.rewriting = false;
+                self.companion = Some(companion);
+                Task::none()
+            }
+
+            Message::RewriteFailed(error) => {
+                self.rewriting = false;
+                self.rewrite_error = Some(error);
+                Task::none()
+            }
+
+            // Handled by the parent, which opens the other text
+            Message::JumpToCompanion(_) => Task::none(),
+
             Message::NextWord => {
                 if let Some(current_index) = self.selected_word_index {
                     if current_index < self.word_segments.len() - 1 {
@@ -263,6 +713,10 @@ impl LearningView {
             Space::new().height(20),
             self.word_segments_display(),
             Space::new().height(20),
+            self.translation_panel(),
+            Space::new().height(20),
+            self.graded_reader_panel(),
+            Space::new().height(20),
             self.explanation_panel(),
             Space::new().height(20),
             self.question_section(),
@@ -330,8 +784,9 @@ impl LearningView {
             .map(|(idx, segment)| {
                 let is_selected = segment.is_selected;
                 let has_explanation = segment.explanation.is_some();
+                let is_highlighted = self.is_segment_highlighted(idx);
 
-                button(
+                let word_button = button(
                     column![
                         text(&segment.surface).size(24),
                         text(&segment.reading).size(12),
@@ -343,14 +798,20 @@ impl LearningView {
                 .padding(10)
                 .style(move |theme: &iced::Theme, status| {
                     let mut style = button_style(theme, status);
-                    if is_selected {
+                    if is_highlighted {
+                        style.background = Some(ALIGNMENT_HIGHLIGHT.into());
+                    } else if is_selected {
                         style.background = Some(Color::from_rgb(0.3, 0.5, 0.8).into());
                     } else if has_explanation {
                         style.background = Some(Color::from_rgb(0.4, 0.7, 0.4).into());
                     }
                     style
-                })
-                .into()
+                });
+
+                mouse_area(word_button)
+                    .on_enter(Message::HoverSegment(Some(idx)))
+                    .on_exit(Message::HoverSegment(None))
+                    .into()
             })
             .collect();
 
@@ -383,6 +844,88 @@ impl LearningView {
         .into()
     }
 
+    fn translation_panel(&self) -> Element<'_, Message> {
+        let title = text("Sentence Translation").size(20);
+
+        let translate_button = button(if self.translation_loading {
+            "Translating..."
+        } else {
+            "Translate Sentence"
+        })
+        .on_press_maybe((!self.translation_loading).then_some(Message::RequestTranslation))
+        .padding(10)
+        .style(button_style);
+
+        let sentence: Element<'_, Message> = match &self.translation_range {
+            Some(range) => text(
+                self.word_segments[range.clone()]
+                    .iter()
+                    .map(|segment| segment.surface.as_str())
+                    .collect::<String>(),
+            )
+            .size(16)
+            .into(),
+            None => text("Translates the sentence of the selected word").size(14).into(),
+        };
+
+        let body: Element<'_, Message> = match (&self.translation, &self.translation_error) {
+            (Some(translation), _) => self.aligned_translation(translation),
+            (None, Some(error)) => text(error).size(12).into(),
+            (None, None) => column![].into(),
+        };
+
+        container(
+            column![
+                row![title, Space::new().width(Fill), translate_button]
+                    .align_y(Alignment::Center),
+                sentence,
+                body,
+            ]
+            .spacing(15),
+        )
+        .padding(20)
+        .width(Length::Fill)
+        .style(section_style)
+        .into()
+    }
+
+    /// Translation split into words, with aligned phrases reacting to the mouse
+    fn aligned_translation<'a>(
+        &'a self,
+        translation: &'a SentenceTranslation,
+    ) -> Element<'a, Message> {
+        let mut words: Vec<Element<'a, Message>> = Vec::new();
+        for (piece, alignment) in translation.pieces() {
+            for word in piece.split_whitespace() {
+                let Some(alignment) = alignment else {
+                    words.push(text(word).size(16).into());
+                    continue;
+                };
+                let is_highlighted = self.hovered_alignment == Some(alignment);
+                let phrase = container(text(word).size(16))
+                    .padding([2, 4])
+                    .style(move |theme: &iced::Theme| {
+                        let palette = theme.extended_palette();
+                        let mut style = section_style(theme);
+                        style.background = Some(if is_highlighted {
+                            ALIGNMENT_HIGHLIGHT.into()
+                        } else {
+                            palette.background.weak.color.into()
+                        });
+                        style
+                    });
+                words.push(
+                    mouse_area(phrase)
+                        .on_enter(Message::HoverPhrase(Some(alignment)))
+                        .on_exit(Message::HoverPhrase(None))
+                        .into(),
+                );
+            }
+        }
+
+        row(words).spacing(6).wrap().into()
+    }
+
     fn explanation_panel(&self) -> Element<'_, Message> {
         if let Some(index) = self.selected_word_index {
             if let Some(segment) = self.word_segments.get(index) {
@@ -410,8 +953,10 @@ impl LearningView {
                         .into()
                     }
                     LoadingState::Loaded => {
-                        if let Some(explanation) = &segment.explanation {
-                            self.display_explanation(segment, explanation)
+                        if let Some(draft) = &self.draft {
+                            self.explanation_editor(segment, draft)
+                        } else if let Some(explanation) = &segment.explanation {
+                            self.display_explanation(index, segment, explanation)
                         } else {
                             container(text("No explanation available").size(14))
                                 .padding(20)
@@ -425,6 +970,10 @@ impl LearningView {
                             column![
                                 text("Error loading explanation").size(16),
                                 text(error).size(12),
+                                button("Try Again")
+                                    .on_press(Message::RequestExplanation)
+                                    .padding(8)
+                                    .style(button_style),
                             ]
                             .spacing(5),
                         )
@@ -450,12 +999,19 @@ impl LearningView {
 
     fn display_explanation<'a>(
         &'a self,
+        index: usize,
         segment: &'a WordSegment,
         explanation: &'a WordExplanation,
     ) -> Element<'a, Message> {
+        // Prefer the LLM's reading; the tokenizer may only echo the surface
+        let reading = explanation
+            .reading
+            .as_deref()
+            .filter(|reading| !reading.is_empty())
+            .unwrap_or(&segment.reading);
         let word_display: Element<'_, Message> = column![
             text(&segment.surface).size(32),
-            text(&segment.reading).size(18),
+            text(reading).size(18),
             text(format!("Dictionary form: {}", segment.base_form)).size(14),
         ]
         .spacing(5)
@@ -472,6 +1028,22 @@ impl LearningView {
             })
             .into();
 
+        let badges: Element<'_, Message> = if explanation.user_edited {
+            let edited_badge = container(text("✎ Corrected by you").size(12))
+                .padding([4, 12])
+                .style(|theme: &iced::Theme| {
+                    let mut style = section_style(theme);
+                    style.background = Some(Color::from_rgb(0.3, 0.6, 0.35).into());
+                    style.text_color = Some(Color::WHITE);
+                    style
+                });
+            row![jlpt_badge, edited_badge].spacing(8).into()
+        } else {
+            jlpt_badge
+        };
+
+        let warning = self.verification_warning(index);
+
         let meaning: Element<'_, Message> = column![
             text("Meaning:").size(16),
             text(&explanation.meaning).size(14),
@@ -496,7 +1068,7 @@ impl LearningView {
                 let example_container = container(
                     column![
                         text(&example.japanese).size(14),
-                        text(&example.english).size(12),
+                        text(&example.translation).size(12),
                     ]
                     .spacing(5),
                 )
@@ -513,6 +1085,26 @@ impl LearningView {
         }
         let examples_section: Element<'_, Message> = column(examples_elements).spacing(10).into();
 
+        let correction: Element<'_, Message> = row![
+            text_input("Feedback, e.g. too advanced", &self.feedback_input)
+                .on_input(Message::FeedbackChanged)
+                .on_submit(Message::RegenerateExplanation)
+                .padding(8)
+                .size(14)
+                .style(text_input_style),
+            button("Regenerate")
+                .on_press(Message::RegenerateExplanation)
+                .padding(8)
+                .style(button_style),
+            button("Edit")
+                .on_press(Message::EditExplanation)
+                .padding(8)
+                .style(button_style),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .into();
+
         let action_buttons: Element<'_, Message> = row![
             button("Add to Vocabulary")
                 .on_press(Message::AddToVocabularyFlashcards)
@@ -542,13 +1134,16 @@ impl LearningView {
 
         container(
             column(vec![
-                jlpt_badge,
+                badges,
+                warning,
                 word_display,
                 Space::new().height(15).into(),
                 meaning,
                 grammar_section,
                 examples_section,
-                Space::new().height(20).into(),
+                Space::new().height(10).into(),
+                correction,
+                Space::new().height(10).into(),
                 action_buttons,
                 Space::new().height(10).into(),
                 navigation,
@@ -561,6 +1156,261 @@ impl LearningView {
         .into()
     }
 
+    /// Form correcting the selected word's explanation
+    fn explanation_editor<'a>(
+        &'a self,
+        segment: &'a WordSegment,
+        draft: &'a WordExplanation,
+    ) -> Element<'a, Message> {
+        let field = |label: &'a str, value: &'a str, field: DraftField| -> Element<'a, Message> {
+            column![
+                text(label).size(14),
+                text_input(label, value)
+                    .on_input(move |value| Message::DraftChanged(field, value))
+                    .padding(8)
+                    .size(14)
+                    .style(text_input_style),
+            ]
+            .spacing(5)
+            .into()
+        };
+
+        let levels = JLPTLevel::ALL.iter().fold(
+            row![text("JLPT level:").size(14)]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            |row, &level| {
+                let selected = draft.jlpt_level == level.as_str();
+                row.push(
+                    button(text(level.as_str().to_string()).size(12))
+                        .on_press_maybe((!selected).then_some(Message::DraftLevelSelected(level)))
+                        .padding(6)
+                        .style(move |theme: &iced::Theme, status| {
+                            let mut style = button_style(theme, status);
+                            if selected {
+                                style.background = Some(level.color().into());
+                                style.text_color = Color::WHITE;
+                            }
+                            style
+                        }),
+                )
+            },
+        );
+
+        let examples = draft.examples.iter().enumerate().fold(
+            column![text("Example Sentences:").size(14)].spacing(8),
+            |col, (index, example)| {
+                col.push(
+                    row![
+                        column![
+                            text_input("Japanese", &example.japanese)
+                                .on_input(move |value| {
+                                    Message::DraftChanged(DraftField::ExampleJapanese(index), value)
+                                })
+                                .padding(8)
+                                .size(14)
+                                .style(text_input_style),
+                            text_input("Translation", &example.translation)
+                                .on_input(move |value| {
+                                    Message::DraftChanged(DraftField::ExampleTranslation(index), value)
+                                })
+                                .padding(8)
+                                .size(12)
+                                .style(text_input_style),
+                        ]
+                        .spacing(4)
+                        .width(Length::Fill),
+                        button("Remove")
+                            .on_press(Message::RemoveDraftExample(index))
+                            .padding(6)
+                            .style(button_style),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+                )
+            },
+        );
+
+        container(
+            column![
+                text(format!("Correct the explanation of {}", segment.surface)).size(18),
+                field("Meaning", &draft.meaning, DraftField::Meaning),
+                field(
+                    "Reading",
+                    draft.reading.as_deref().unwrap_or_default(),
+                    DraftField::Reading
+                ),
+                field(
+                    "Grammar Notes",
+                    draft.grammar_notes.as_deref().unwrap_or_default(),
+                    DraftField::GrammarNotes
+                ),
+                levels,
+                examples,
+                button("Add Example")
+                    .on_press(Message::AddDraftExample)
+                    .padding(6)
+                    .style(button_style),
+                row![
+                    button("Save Correction")
+                        .on_press_maybe(
+                            (!draft.meaning.trim().is_empty()).then_some(Message::SaveExplanation)
+                        )
+                        .padding(10)
+                        .style(button_style),
+                    button("Cancel")
+                        .on_press(Message::CancelEdit)
+                        .padding(10)
+                        .style(button_style),
+                ]
+                .spacing(10),
+            ]
+            .spacing(10),
+        )
+        .padding(20)
+        .width(Length::Fill)
+        .style(section_style)
+        .into()
+    }
+
+    /// Badge listing where the explanation disagrees with local data
+    fn verification_warning(&self, index: usize) -> Element<'_, Message> {
+        let Some(mismatches) = self.mismatches.get(&index).filter(|m| !m.is_empty()) else {
+            return column![].into();
+        };
+
+        let details = mismatches.iter().fold(column![].spacing(3), |col, mismatch| {
+            col.push(text(format!("• {}", mismatch.describe())).size(12))
+        });
+
+        container(
+            column![
+                text("⚠ This explanation disagrees with local data").size(14),
+                details,
+                row![
+                    button("Regenerate")
+                        .on_press(Message::RequestExplanation)
+                        .padding(8)
+                        .style(button_style),
+                    button("Accept Local Data")
+                        .on_press(Message::AcceptLocalData)
+                        .padding(8)
+                        .style(button_style),
+                ]
+                .spacing(10),
+            ]
+            .spacing(8),
+        )
+        .padding(12)
+        .width(Length::Fill)
+        .style(|theme: &iced::Theme| {
+            let mut style = section_style(theme);
+            style.background = Some(Color::from_rgb(0.85, 0.6, 0.2).into());
+            style.text_color = Some(Color::WHITE);
+            style
+        })
+        .into()
+    }
+
+    fn graded_reader_panel(&self) -> Element<'_, Message> {
+        let title = text("Graded Reader").size(20);
+
+        let level_buttons = JLPTLevel::ALL.iter().fold(
+            row![text("Rewrite at:").size(14)]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            |row, &level| {
+                let selected = level == self.rewrite_level;
+                row.push(
+                    button(text(level.as_str().to_string()).size(14))
+                        .on_press_maybe((!selected).then_some(Message::RewriteLevelSelected(level)))
+                        .padding(8)
+                        .style(move |theme: &iced::Theme, status| {
+                            let mut style = button_style(theme, status);
+                            if selected {
+                                style.background = Some(level.color().into());
+                                style.text_color = Color::WHITE;
+                            }
+                            style
+                        }),
+                )
+            },
+        );
+
+        let rewrite_label = if self.rewriting { "Rewriting..." } else { "Rewrite" };
+        let rewrite_button = button(rewrite_label)
+            .on_press_maybe((!self.rewriting).then_some(Message::RequestRewrite))
+            .padding(8)
+            .style(button_style);
+
+        let rewrite_error = match &self.rewrite_error {
+            Some(error) => column![text(error).size(12)],
+            None => column![],
+        };
+
+        container(
+            column![
+                title,
+                row![level_buttons, Space::new().width(Fill), rewrite_button]
+                    .align_y(Alignment::Center),
+                rewrite_error,
+                self.companion_sentences(),
+            ]
+            .spacing(15),
+        )
+        .padding(20)
+        .width(Length::Fill)
+        .style(section_style)
+        .into()
+    }
+
+    /// Sentences of the linked text, with those matching the current sentence highlighted
+    fn companion_sentences(&self) -> Element<'_, Message> {
+        let Some(companion) = &self.companion else {
+            return column![].into();
+        };
+
+        let (label, switch_label) = if companion.is_rewrite {
+            (format!("Rewritten at {}", companion.level.as_str()), "Switch to Rewrite ⇄")
+        } else {
+            ("Original text".to_string(), "Switch to Original ⇄")
+        };
+        let switch_button = button(switch_label)
+            .on_press(Message::JumpToCompanion(None))
+            .padding(8)
+            .style(button_style);
+
+        let corresponding = self.corresponding_sentences();
+        let sentence_list = companion.sentences.iter().enumerate().fold(
+            column![].spacing(5),
+            |col, (index, sentence)| {
+                let is_match = corresponding.contains(&index);
+                col.push(
+                    button(text(sentence).size(16))
+                        .on_press(Message::JumpToCompanion(Some(index)))
+                        .padding(6)
+                        .width(Length::Fill)
+                        .style(move |theme: &iced::Theme, status| {
+                            let mut style = button_style(theme, status);
+                            if is_match {
+                                style.background = Some(ALIGNMENT_HIGHLIGHT.into());
+                                style.text_color = Color::WHITE;
+                            }
+                            style
+                        }),
+                )
+            },
+        );
+
+        column![
+            row![text(label).size(16).width(Length::Fill), switch_button]
+                .align_y(Alignment::Center),
+            sentence_list,
+        ]
+        .spacing(10)
+        .into()
+    }
+
     fn question_section(&self) -> Element<'_, Message> {
         let title = text("Ask a Question").size(20);
 
@@ -577,22 +1427,28 @@ impl LearningView {
             .padding(12)
             .style(button_style);
 
-        let ask_button = if !self.question_input.trim().is_empty() {
+        let ask_button = if !self.question_input.trim().is_empty() && !self.awaiting_answer {
             ask_button.on_press(Message::AskQuestion)
         } else {
             ask_button
         };
 
+        let question_error = match &self.question_error {
+            Some(error) => column![text(error).size(12)],
+            None => column![],
+        };
+
         let qa_history = if !self.qa_history.is_empty() {
             let history_list =
                 self.qa_history
                     .iter()
                     .fold(column![].spacing(15), |col, (q, a)| {
+                        let answer = if a.is_empty() { "Thinking..." } else { a.as_str() };
                         col.push(
                             container(
                                 column![
                                     text(format!("Q: {}", q)).size(14),
-                                    text(format!("A: {}", a)).size(12),
+                                    text(format!("A: {}", answer)).size(12),
                                 ]
                                 .spacing(5),
                             )
@@ -617,6 +1473,7 @@ impl LearningView {
             column![
                 title,
                 row![input, ask_button].spacing(10),
+                question_error,
                 qa_history,
             ]
             .spacing(15),
@@ -626,49 +1483,217 @@ impl LearningView {
         .style(section_style)
         .into()
     }
+}
 
-    // Helper function to generate sample explanations (will be replaced with real LLM)
-    fn generate_sample_explanation(&self, word: &str) -> WordExplanation {
-        match word {
-            "今日" => WordExplanation {
-                meaning: "today".to_string(),
-                grammar_notes: None,
-                examples: vec![
-                    ExampleSentence {
-                        japanese: "今日は晴れです。".to_string(),
-                        english: "Today is sunny.".to_string(),
-                    },
-                ],
-                jlpt_level: "N5".to_string(),
-            },
-            "日本語" => WordExplanation {
-                meaning: "Japanese language".to_string(),
-                grammar_notes: Some("Compound of 日本 (Japan) + 語 (language)".to_string()),
-                examples: vec![
-                    ExampleSentence {
-                        japanese: "日本語を話せますか。".to_string(),
-                        english: "Can you speak Japanese?".to_string(),
-                    },
-                ],
-                jlpt_level: "N5".to_string(),
-            },
-            "勉強" => WordExplanation {
-                meaning: "study".to_string(),
-                grammar_notes: Some("Noun that can be used with する to make a verb (勉強する = to study)".to_string()),
-                examples: vec![
-                    ExampleSentence {
-                        japanese: "毎日勉強します。".to_string(),
-                        english: "I study every day.".to_string(),
-                    },
-                ],
-                jlpt_level: "N5".to_string(),
-            },
-            _ => WordExplanation {
-                meaning: format!("Meaning of '{}' (simulated)", word),
-                grammar_notes: Some("This is a simulated explanation. In the real app, this will come from the AI tutor.".to_string()),
-                examples: vec![],
-                jlpt_level: "N5".to_string(),
-            },
-        }
+/// Tidy an edited explanation and mark it as the user's
+fn finish_draft(mut draft: WordExplanation) -> WordExplanation {
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    draft.meaning = draft.meaning.trim().to_string();
+    draft.reading = non_empty(draft.reading);
+    draft.grammar_notes = non_empty(draft.grammar_notes);
+    draft
+        .examples
+        .retain(|example| !example.japanese.trim().is_empty());
+    draft.user_edited = true;
+    draft
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::llm::mock_service;
+    use iced::futures::executor::block_on;
+
+    fn sample_text() -> LearningText {
+        let segments = [("今日", "きょう", "今日"), ("は", "は", "は"), ("勉強", "べんきょう", "勉強")]
+            .into_iter()
+            .map(|(surface, reading, base_form)| WordSegment {
+                surface: surface.to_string(),
+                reading: reading.to_string(),
+                base_form: base_form.to_string(),
+                explanation: None,
+                is_selected: false,
+            })
+            .collect();
+        LearningText::new("今日は勉強".to_string(), segments)
+    }
+
+    #[test]
+    fn selecting_a_word_loads_its_explanation_from_the_llm() {
+        let mut view = LearningView::default();
+        view.load_text(sample_text(), Vec::new());
+
+        let _ = view.update(Message::SelectWord(2));
+        let (index, segment) = view.pending_explanation().expect("explanation requested");
+        assert_eq!(index, 2);
+
+        let explanation = block_on(mock_service().explain_word(
+            &segment.surface,
+            &segment.reading,
+            &segment.base_form,
+            view.original_text(),
+        ))
+        .unwrap();
+        let _ = view.update(Message::ExplanationReceived(index, explanation, Vec::new()));
+
+        assert!(view.pending_explanation().is_none());
+        assert_eq!(view.loading_state, LoadingState::Loaded);
+        let explanation = view.word_segments[2].explanation.as_ref().unwrap();
+        assert_eq!(explanation.meaning, "study");
+        assert_eq!(explanation.jlpt_level, "N5");
+    }
+
+    #[test]
+    fn late_explanation_does_not_replace_the_selected_word_state() {
+        let mut view = LearningView::default();
+        view.load_text(sample_text(), Vec::new());
+
+        let _ = view.update(Message::SelectWord(0));
+        let _ = view.update(Message::SelectWord(2));
+        let explanation = block_on(mock_service().explain_word("今日", "きょう", "今日", "今日は")).unwrap();
+        let _ = view.update(Message::ExplanationReceived(0, explanation, Vec::new()));
+
+        assert!(view.word_segments[0].explanation.is_some());
+        assert_eq!(view.pending_explanation().map(|(index, _)| index), Some(2));
+
+        let _ = view.update(Message::ExplanationError(0, "stale".to_string()));
+        assert_eq!(view.loading_state, LoadingState::Loading);
+    }
+
+    #[test]
+    fn corrected_explanation_applies_to_every_occurrence() {
+        let mut text = sample_text();
+        text.tokenized_segments.push(text.tokenized_segments[2].clone());
+        let mut view = LearningView::default();
+        view.load_text(text, Vec::new());
+
+        let _ = view.update(Message::SelectWord(2));
+        let explanation = block_on(mock_service().explain_word("勉強", "べんきょう", "勉強", "今日は勉強")).unwrap();
+        let _ = view.update(Message::ExplanationReceived(2, explanation, Vec::new()));
+
+        let _ = view.update(Message::EditExplanation);
+        let _ = view.update(Message::DraftChanged(DraftField::Meaning, " studying ".to_string()));
+        let _ = view.update(Message::DraftLevelSelected(JLPTLevel::N4));
+        let _ = view.update(Message::AddDraftExample);
+        let _ = view.update(Message::SaveExplanation);
+
+        let (base_form, explanation) = view.edited_explanation().expect("correction saved");
+        assert_eq!(base_form, "勉強");
+        assert_eq!(explanation.meaning, "studying");
+        assert_eq!(explanation.jlpt_level, "N4");
+        // The empty example is dropped
+        assert_eq!(explanation.examples.len(), 1);
+        assert!(view.word_segments[3].explanation.as_ref().unwrap().user_edited);
+
+        let _ = view.update(Message::FeedbackChanged("too advanced".to_string()));
+        let _ = view.update(Message::RegenerateExplanation);
+        assert_eq!(view.regeneration_feedback(), Some("too advanced"));
+    }
+
+    #[test]
+    fn translation_links_segments_and_phrases_both_ways() {
+        let original = "明日。今日は日本語を勉強します。".to_string();
+        let segments = crate::services::TokenizerService::default()
+            .tokenize(&original)
+            .unwrap();
+        let mut view = LearningView::default();
+        view.load_text(LearningText::new(original, segments), Vec::new());
+
+        // 語 (index 8) is in the second sentence
+        let _ = view.update(Message::SelectWord(8));
+        let _ = view.update(Message::RequestTranslation);
+        let (start, sentence) = view.pending_translation().expect("translation requested");
+        assert_eq!(start, 3);
+
+        let translation = block_on(mock_service().translate_sentence(sentence)).unwrap();
+        assert_eq!(translation.translation, "Today I study Japanese.");
+        let _ = view.update(Message::TranslationReceived(start, translation));
+        assert!(view.pending_translation().is_none());
+
+        let _ = view.update(Message::HoverSegment(Some(8)));
+        let translation = view.translation.as_ref().unwrap();
+        let phrase = translation.alignments[view.hovered_alignment.unwrap()].phrase.clone();
+        assert_eq!(&translation.translation[phrase], "Japanese");
+
+        let _ = view.update(Message::HoverPhrase(Some(0)));
+        assert!(view.is_segment_highlighted(3) && view.is_segment_highlighted(4));
+        assert!(!view.is_segment_highlighted(5) && !view.is_segment_highlighted(0));
+    }
+
+    #[test]
+    fn questions_are_answered_and_kept_in_history() {
+        let mut view = LearningView::default();
+        view.load_text(sample_text(), Vec::new());
+
+        let _ = view.update(Message::QuestionInputChanged("What does は do?".to_string()));
+        let _ = view.update(Message::AskQuestion);
+        let question = view.pending_question().expect("question pending").to_string();
+        assert!(view.answered_questions().is_empty());
+
+        let answer = block_on(mock_service().answer_question(
+            &question,
+            view.original_text(),
+            view.answered_questions(),
+            None,
+        ))
+        .unwrap();
+        let _ = view.update(Message::QuestionAnswered(answer));
+
+        assert!(view.pending_question().is_none());
+        assert_eq!(view.answered_questions().len(), 1);
+        assert_eq!(view.answered_questions()[0].0, "What does は do?");
+        assert!(view.answered_questions()[0].1.starts_with("This is demo mode"));
+    }
+
+    #[test]
+    fn failed_question_is_returned_to_the_input() {
+        let mut view = LearningView::default();
+        view.load_text(sample_text(), Vec::new());
+
+        let _ = view.update(Message::QuestionInputChanged("Why?".to_string()));
+        let _ = view.update(Message::AskQuestion);
+        let _ = view.update(Message::QuestionFailed("offline".to_string()));
+
+        assert!(view.pending_question().is_none());
+        assert!(view.answered_questions().is_empty());
+        assert_eq!(view.question_input, "Why?");
+        assert_eq!(view.question_error.as_deref(), Some("offline"));
+    }
+
+    #[test]
+    fn rewrite_sentences_follow_the_current_sentence() {
+        let original = "明日。\n今日は日本語を勉強します。".to_string();
+        let segments = crate::services::TokenizerService::default()
+            .tokenize(&original)
+            .unwrap();
+        let mut view = LearningView::default();
+        view.load_text(LearningText::new(original, segments), Vec::new());
+
+        let _ = view.update(Message::RewriteLevelSelected(JLPTLevel::N4));
+        let _ = view.update(Message::RequestRewrite);
+        let (sentences, level) = view.pending_rewrite().expect("rewrite requested");
+        assert_eq!(sentences, ["明日。", "今日は日本語を勉強します。"]);
+        assert_eq!(level, JLPTLevel::N4);
+
+        let _ = view.update(Message::RewriteSaved(Companion {
+            text_id: "text-rewrite".to_string(),
+            sentences: vec!["あした。".to_string(), "きょう。".to_string(), "べんきょう。".to_string()],
+            level,
+            is_rewrite: true,
+            sentence_map: vec![Some(0), Some(1), Some(1)],
+        }));
+        assert!(view.pending_rewrite().is_none());
+
+        // 今 (index 4) starts the second sentence, retold by the last two
+        let _ = view.update(Message::SelectWord(4));
+        assert_eq!(view.corresponding_sentences(), [1, 2]);
+        assert_eq!(
+            view.companion_jump(None),
+            Some(("text-rewrite".to_string(), Some(1)))
+        );
     }
 }
@@ -6,6 +6,9 @@
 //! - update() method for state changes
 //! - view() method for rendering UI
 
+pub mod conversation;
+pub mod custom_session;
+pub mod developer;
 pub mod home;
 pub mod learning;
 pub mod practice;
@@ -2,105 +2,130 @@
 //!
 //! This view implements:
 //! - Flashcard display with vocabulary/grammar questions
-//! - SRS (Spaced Repetition System) algorithm
+//! - SRS (Spaced Repetition System) algorithm, rescheduling each card on its first answer
+//! - A session queue that shows cards in learning steps again once their step expires
+//! - Optional Again/Hard/Good/Easy self-rating after the answer is revealed
 //! - Multiple choice quiz interface
 //! - Furigana display and romaji toggle
 //! - Example sentences and JLPT level badges
+//! - The card's predicted recall probability, once FSRS has a memory state for it
+//! - Warnings on cards that disagree with local reading or dictionary data
+//! - Sentence writing exercises graded by the LLM
+//! - Custom sessions built from filters, which may leave scheduling untouched
 
 use crate::components::{jlpt_badge, QuizState};
 use crate::constants::ui;
-use crate::models::{CardType, ExampleSentence, GrammarCard, JLPTLevel, VocabularyCard};
+use crate::models::flashcard::FlashCard;
+use crate::constants::srs;
+use crate::models::{CardType, GrammarCard, VocabularyCard, WritingGrade};
+use crate::services::learner_report::ReviewOutcome;
+use crate::services::fsrs::{self, Rating};
+use crate::services::scheduler;
+use crate::services::verification::Mismatch;
+use crate::views::custom_session::{self, CustomSessionView};
 use crate::ui::{button_style, section_style};
-use iced::widget::{button, column, container, row, scrollable, text, Space};
+use crate::ui::text_input_style;
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Color, Element, Fill, Length, Task};
+use chrono::{DateTime, Duration, Local, Utc};
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 
 // All types now imported from models and components modules
 
+/// How the current card is quizzed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Exercise {
+    /// Pick the meaning among wrong answers
+    #[default]
+    MultipleChoice,
+    /// Write a sentence using the word or pattern, graded by the LLM
+    Writing,
+}
+
+/// An answer to a practice card, graded and ready to log
+#[derive(Debug, Clone)]
+pub struct PracticeAnswer {
+    pub card_id: String,
+    /// SM-2 quality grade, 0-5
+    pub quality: u8,
+    /// Chosen multiple-choice answer or written sentence
+    pub answer: String,
+    /// Time from showing the card to answering it
+    pub response_time: std::time::Duration,
+}
+
+/// A revealed multiple-choice answer waiting for the user's own rating
+#[derive(Debug, Clone)]
+struct PendingRating {
+    /// Graded with the quality used when the user moves on without rating
+    answer: PracticeAnswer,
+    /// Each rating with the delay until the next review it leads to
+    previews: Vec<(Rating, String)>,
+}
+
 pub struct PracticeView {
-    cards: Vec<CardType>,
+    cards: Vec<FlashCard>,
     current_index: usize,
     show_romaji: bool,
     show_examples: bool,
     quiz_state: QuizState,
+    // Answer options of the current card in the order shown: 0 is the correct
+    // answer, then the wrong answers
+    answer_order: Vec<usize>,
+    // When the current question was shown, to grade the answer's speed
+    question_shown_at: Instant,
     score: usize,
     total_answered: usize,
+    outcomes: Vec<ReviewOutcome>,
+    // Queue positions already answered and rescheduled this session
+    reviewed: HashSet<usize>,
+    // Cards in learning steps, shown again once their step expires
+    learning: Vec<FlashCard>,
+    pending_rating: Option<PendingRating>,
+    // Differences from local data per card id
+    mismatches: HashMap<String, Vec<Mismatch>>,
+    regenerating: bool,
+    exercise: Exercise,
+    writing_input: String,
+    // Waiting for the grade of the written sentence
+    grading: bool,
+    grade: Option<WritingGrade>,
+    grade_error: Option<String>,
+    // Time taken to write the sentence being graded
+    writing_time: std::time::Duration,
+    // Answers reschedule cards, unless a custom session said otherwise
+    affects_scheduling: bool,
+    custom_session: Option<CustomSessionView>,
 }
 
 impl Default for PracticeView {
     fn default() -> Self {
-        // Create sample cards for demonstration
-        let sample_cards = vec![
-            CardType::Vocabulary(VocabularyCard {
-                kanji: "食べる".to_string(),
-                hiragana: "たべる".to_string(),
-                romaji: "taberu".to_string(),
-                meaning: "to eat".to_string(),
-                wrong_answers: vec![
-                    "to drink".to_string(),
-                    "to cook".to_string(),
-                    "to buy".to_string(),
-                ],
-                example_sentences: vec![
-                    ExampleSentence {
-                        japanese: "朝ごはんを食べます。".to_string(),
-                        english: "I eat breakfast.".to_string(),
-                    },
-                    ExampleSentence {
-                        japanese: "寿司を食べたいです。".to_string(),
-                        english: "I want to eat sushi.".to_string(),
-                    },
-                ],
-                jlpt_level: JLPTLevel::N5,
-            }),
-            CardType::Grammar(GrammarCard {
-                pattern: "〜てもいい".to_string(),
-                pattern_reading: "てもいい".to_string(),
-                explanation: "Permission: 'it's okay to...'".to_string(),
-                wrong_answers: vec![
-                    "Obligation: 'must do'".to_string(),
-                    "Prohibition: 'must not'".to_string(),
-                    "Suggestion: 'how about'".to_string(),
-                ],
-                example_sentences: vec![
-                    ExampleSentence {
-                        japanese: "ここで写真を撮ってもいいですか。".to_string(),
-                        english: "Is it okay to take pictures here?".to_string(),
-                    },
-                    ExampleSentence {
-                        japanese: "窓を開けてもいいですよ。".to_string(),
-                        english: "It's okay to open the window.".to_string(),
-                    },
-                ],
-                jlpt_level: JLPTLevel::N4,
-            }),
-            CardType::Vocabulary(VocabularyCard {
-                kanji: "勉強".to_string(),
-                hiragana: "べんきょう".to_string(),
-                romaji: "benkyou".to_string(),
-                meaning: "study".to_string(),
-                wrong_answers: vec![
-                    "work".to_string(),
-                    "homework".to_string(),
-                    "school".to_string(),
-                ],
-                example_sentences: vec![
-                    ExampleSentence {
-                        japanese: "毎日日本語を勉強しています。".to_string(),
-                        english: "I study Japanese every day.".to_string(),
-                    },
-                ],
-                jlpt_level: JLPTLevel::N5,
-            }),
-        ];
-
         Self {
-            cards: sample_cards,
+            cards: Vec::new(),
             current_index: 0,
             show_romaji: false,
             show_examples: false,
             quiz_state: QuizState::Question,
+            answer_order: Vec::new(),
+            question_shown_at: Instant::now(),
             score: 0,
             total_answered: 0,
+            outcomes: Vec::new(),
+            reviewed: HashSet::new(),
+            learning: Vec::new(),
+            pending_rating: None,
+            mismatches: HashMap::new(),
+            regenerating: false,
+            exercise: Exercise::default(),
+            writing_input: String::new(),
+            grading: false,
+            grade: None,
+            grade_error: None,
+            writing_time: std::time::Duration::ZERO,
+            affects_scheduling: true,
+            custom_session: None,
         }
     }
 }
@@ -111,11 +136,280 @@ pub enum Message {
     ToggleRomaji,
     ToggleExamples,
     SelectAnswer(usize),
+    /// The user's own rating of a revealed answer
+    RateAnswer(Rating),
     NextCard,
     PreviousCard,
+    RegenerateCard,
+    AcceptLocalCardData,
+    SelectExercise(Exercise),
+    WritingInputChanged(String),
+    SubmitWriting,
+    /// Grade of the sentence written for the card with this id
+    WritingGraded(String, WritingGrade),
+    WritingGradeFailed(String),
+    OpenCustomSession,
+    CustomSession(custom_session::Message),
 }
 
 impl PracticeView {
+    /// Start a new session with the given cards
+    pub fn set_cards(&mut self, cards: Vec<FlashCard>) {
+        self.cards = cards;
+        self.current_index = 0;
+        self.show_romaji = false;
+        self.show_examples = false;
+        self.quiz_state = QuizState::Question;
+        self.question_shown_at = Instant::now();
+        self.score = 0;
+        self.total_answered = 0;
+        self.outcomes.clear();
+        self.reviewed.clear();
+        self.learning.clear();
+        self.pending_rating = None;
+        self.mismatches.clear();
+        self.regenerating = false;
+        self.affects_scheduling = true;
+        self.reset_writing();
+        self.shuffle_answers();
+    }
+
+    /// Shuffle the answer options of the current card
+    fn shuffle_answers(&mut self) {
+        let options = self
+            .current_card()
+            .map_or(0, |card| card.card_type.wrong_answers().len() + 1);
+        self.answer_order = (0..options).collect();
+        self.answer_order.shuffle(&mut rand::rng());
+    }
+
+    /// Button position of the correct answer
+    fn correct_position(&self) -> usize {
+        self.answer_order
+            .iter()
+            .position(|&option| option == 0)
+            .unwrap_or_default()
+    }
+
+    /// Answer shown at a button position
+    fn answer_at<'a>(&self, card: &'a CardType, position: usize) -> Option<&'a str> {
+        match *self.answer_order.get(position)? {
+            0 => Some(card.correct_answer()),
+            option => card.wrong_answers().get(option - 1).map(String::as_str),
+        }
+    }
+
+    /// Let answers in the current session reschedule cards, or not
+    pub fn set_affects_scheduling(&mut self, affects_scheduling: bool) {
+        self.affects_scheduling = affects_scheduling;
+    }
+
+    /// Show the custom session builder
+    pub fn open_custom_session(&mut self, builder: CustomSessionView) {
+        self.custom_session = Some(builder);
+    }
+
+    pub fn close_custom_session(&mut self) {
+        self.custom_session = None;
+    }
+
+    /// Custom session builder, while it is shown
+    pub fn custom_session(&self) -> Option<&CustomSessionView> {
+        self.custom_session.as_ref()
+    }
+
+    pub fn custom_session_mut(&mut self) -> Option<&mut CustomSessionView> {
+        self.custom_session.as_mut()
+    }
+
+    /// Card currently shown
+    pub fn current_card(&self) -> Option<&FlashCard> {
+        self.cards.get(self.current_index)
+    }
+
+    /// Card of the session with the given id
+    pub fn card(&self, card_id: &str) -> Option<&FlashCard> {
+        self.cards.iter().find(|card| card.id == card_id)
+    }
+
+    /// Card and sentence waiting for a grade
+    pub fn pending_writing(&self) -> Option<(&FlashCard, &str)> {
+        if !self.grading {
+            return None;
+        }
+        self.current_card()
+            .map(|card| (card, self.writing_input.trim()))
+    }
+
+    /// Graded multiple-choice answer that should be logged and reschedule its card
+    ///
+    /// Only the first answer at each queue position counts, so going back to a
+    /// card and answering again does not reschedule it twice.
+    pub fn answer_review(&self, selected: usize) -> Option<PracticeAnswer> {
+        if self.quiz_state != QuizState::Question {
+            return None;
+        }
+        let card = self.current_card()?;
+        if !self.awaits_review(&card.id) {
+            return None;
+        }
+        let answer = self.answer_at(&card.card_type, selected)?;
+        let correct = selected == self.correct_position();
+        let response_time = self.question_shown_at.elapsed();
+        Some(PracticeAnswer {
+            card_id: card.id.clone(),
+            quality: scheduler::multiple_choice_quality(correct, response_time),
+            answer: answer.to_string(),
+            response_time,
+        })
+    }
+
+    /// Written sentence of a card, graded `quality`, if it should be logged and reschedule the card
+    pub fn writing_answer(&self, card_id: &str, quality: u8) -> Option<PracticeAnswer> {
+        let (card, sentence) = self.pending_writing()?;
+        if card.id != card_id || !self.awaits_review(card_id) {
+            return None;
+        }
+        Some(PracticeAnswer {
+            card_id: card_id.to_string(),
+            quality,
+            answer: sentence.to_string(),
+            response_time: self.writing_time,
+        })
+    }
+
+    /// Ask the user to rate the revealed answer, showing where each rating leads
+    pub fn await_rating(&mut self, answer: PracticeAnswer, previews: Vec<(Rating, String)>) {
+        self.pending_rating = Some(PendingRating { answer, previews });
+    }
+
+    /// Answer still waiting for a rating, graded with its default quality
+    ///
+    /// The answer counts as rated afterwards, whether the caller schedules it
+    /// with the user's rating or with the default.
+    pub fn take_pending_rating(&mut self) -> Option<PracticeAnswer> {
+        self.pending_rating.take().map(|pending| pending.answer)
+    }
+
+    /// Check if answers in this session reschedule cards
+    pub fn affects_scheduling(&self) -> bool {
+        self.affects_scheduling
+    }
+
+    /// Check if the card is shown and not yet answered at this queue position
+    fn awaits_review(&self, card_id: &str) -> bool {
+        self.current_card().is_some_and(|card| card.id == card_id)
+            && !self.reviewed.contains(&self.current_index)
+    }
+
+    /// Queue a rescheduled card again if it is in learning steps and not suspended
+    pub fn requeue_if_learning(&mut self, card: &FlashCard) {
+        self.learning.retain(|waiting| waiting.id != card.id);
+        if card.srs_data.learning.is_some() && !card.srs_data.suspended {
+            self.learning.push(card.clone());
+        }
+    }
+
+    /// Due time of the card in learning that comes back first
+    fn next_learning_due(&self) -> Option<DateTime<Utc>> {
+        self.learning.iter().map(|card| card.srs_data.next_review).min()
+    }
+
+    /// Check if a card in learning can be shown after the current one
+    ///
+    /// At the end of the queue, cards due within the learn-ahead limit are
+    /// shown early rather than leaving the session idle.
+    fn learning_card_ready(&self, now: DateTime<Utc>) -> bool {
+        let at_end = self.current_index + 1 >= self.cards.len();
+        let limit = if at_end {
+            now + Duration::minutes(srs::LEARN_AHEAD_MINUTES)
+        } else {
+            now
+        };
+        self.next_learning_due().is_some_and(|due| due <= limit)
+    }
+
+    /// Move the first due card in learning into the queue after the current card
+    fn insert_due_learning_card(&mut self, now: DateTime<Utc>) {
+        if !self.learning_card_ready(now) {
+            return;
+        }
+        let Some(position) = (0..self.learning.len())
+            .min_by_key(|&i| self.learning[i].srs_data.next_review)
+        else {
+            return;
+        };
+
+        let card = self.learning.remove(position);
+        let inserted_at = self.current_index + 1;
+        self.cards.insert(inserted_at, card);
+        self.reviewed = self
+            .reviewed
+            .drain()
+            .map(|reviewed| if reviewed >= inserted_at { reviewed + 1 } else { reviewed })
+            .collect();
+    }
+
+    /// Record where a card disagrees with local data
+    pub fn set_mismatches(&mut self, card_id: &str, mismatches: Vec<Mismatch>) {
+        if mismatches.is_empty() {
+            self.mismatches.remove(card_id);
+        } else {
+            self.mismatches.insert(card_id.to_string(), mismatches);
+        }
+    }
+
+    /// Differences from local data of a card
+    pub fn mismatches(&self, card_id: &str) -> &[Mismatch] {
+        self.mismatches.get(card_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Swap in an updated version of a card everywhere it appears in the session
+    pub fn replace_card(&mut self, card: FlashCard) {
+        for existing in self
+            .cards
+            .iter_mut()
+            .chain(self.learning.iter_mut())
+            .filter(|c| c.id == card.id)
+        {
+            *existing = card.clone();
+        }
+        // A regenerated card may come with a different number of options
+        let options = card.card_type.wrong_answers().len() + 1;
+        if self
+            .current_card()
+            .is_some_and(|current| current.id == card.id)
+            && options != self.answer_order.len()
+        {
+            self.shuffle_answers();
+        }
+    }
+
+    pub fn set_regenerating(&mut self, regenerating: bool) {
+        self.regenerating = regenerating;
+    }
+
+    /// Check if the current card has been answered in either exercise
+    fn is_answered(&self) -> bool {
+        self.quiz_state != QuizState::Question || self.grade.is_some()
+    }
+
+    fn can_submit_writing(&self) -> bool {
+        !self.writing_input.trim().is_empty() && !self.grading && self.grade.is_none()
+    }
+
+    fn reset_writing(&mut self) {
+        self.writing_input.clear();
+        self.grading = false;
+        self.grade = None;
+        self.grade_error = None;
+    }
+
+    /// Hand over the answers given since the last call, for the learner report
+    pub fn take_session_outcomes(&mut self) -> Vec<ReviewOutcome> {
+        std::mem::take(&mut self.outcomes)
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::BackToHome => Task::none(),
@@ -129,54 +423,133 @@ impl PracticeView {
             }
             Message::SelectAnswer(selected) => {
                 if self.quiz_state == QuizState::Question {
-                    // Answer 0 is always the correct answer
-                    if selected == 0 {
+                    let correct = self.correct_position();
+                    if selected == correct {
                         self.quiz_state = QuizState::AnswerCorrect;
                         self.score += 1;
                     } else {
-                        self.quiz_state = QuizState::AnswerIncorrect {
-                            selected,
-                            correct: 0,
-                        };
+                        self.quiz_state = QuizState::AnswerIncorrect { selected, correct };
                     }
                     self.total_answered += 1;
+                    self.outcomes.push(ReviewOutcome::new(
+                        &self.cards[self.current_index].card_type,
+                        selected == correct,
+                    ));
+                    self.reviewed.insert(self.current_index);
                 }
                 Task::none()
             }
             Message::NextCard => {
-                if self.current_index < self.cards.len() - 1 {
+                if !self.grading {
+                    self.insert_due_learning_card(Utc::now());
+                }
+                if self.current_index < self.cards.len() - 1 && !self.grading {
                     self.current_index += 1;
                     self.quiz_state = QuizState::Question;
+                    self.question_shown_at = Instant::now();
                     self.show_examples = false;
+                    self.reset_writing();
+                    self.shuffle_answers();
                 }
                 Task::none()
             }
             Message::PreviousCard => {
-                if self.current_index > 0 {
+                if self.current_index > 0 && !self.grading {
                     self.current_index -= 1;
                     self.quiz_state = QuizState::Question;
+                    self.question_shown_at = Instant::now();
                     self.show_examples = false;
+                    self.reset_writing();
+                    self.shuffle_answers();
                 }
                 Task::none()
             }
+            Message::SelectExercise(exercise) => {
+                if !self.grading {
+                    self.exercise = exercise;
+                }
+                Task::none()
+            }
+            Message::WritingInputChanged(input) => {
+                if !self.grading {
+                    self.writing_input = input;
+                }
+                Task::none()
+            }
+            Message::SubmitWriting => {
+                // The parent sends the sentence to the LLM once it is pending
+                if self.can_submit_writing() {
+                    self.grading = true;
+                    self.grade_error = None;
+                    self.writing_time = self.question_shown_at.elapsed();
+                }
+                Task::none()
+            }
+            Message::WritingGraded(card_id, grade) => {
+                let is_current = self.current_card().is_some_and(|card| card.id == card_id);
+                if self.grading && is_current {
+                    let passed = grade.quality() >= srs::PASSING_QUALITY;
+                    if passed {
+                        self.score += 1;
+                    }
+                    self.total_answered += 1;
+                    self.outcomes.push(ReviewOutcome::new(
+                        &self.cards[self.current_index].card_type,
+                        passed,
+                    ));
+                    self.reviewed.insert(self.current_index);
+                    self.grade = Some(grade);
+                }
+                self.grading = false;
+                Task::none()
+            }
+            Message::WritingGradeFailed(error) => {
+                self.grading = false;
+                self.grade_error = Some(error);
+                Task::none()
+            }
+            // Handled by the parent, which owns the LLM and database
+            Message::RegenerateCard
+            | Message::AcceptLocalCardData
+            | Message::RateAnswer(_)
+            | Message::OpenCustomSession => Task::none(),
+            Message::CustomSession(msg) => match &mut self.custom_session {
+                Some(builder) => builder.update(msg).map(Message::CustomSession),
+                None => Task::none(),
+            },
         }
     }
 
     pub fn view(&self) -> Element<'_, Message> {
+        if let Some(builder) = &self.custom_session {
+            let content = container(builder.view().map(Message::CustomSession))
+                .padding(20)
+                .width(Length::Fill)
+                .max_width(ui::MAX_CONTENT_WIDTH);
+            return scrollable(container(content).width(Length::Fill).center_x(Fill))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
         if self.cards.is_empty() {
             return self.empty_state();
         }
 
-        let current_card = &self.cards[self.current_index];
+        let current_card = &self.cards[self.current_index].card_type;
 
         let content = column![
             self.header(),
             Space::new().height(20),
             self.progress_bar(),
             Space::new().height(20),
+            self.verification_warning(),
+            self.exercise_selector(),
             self.card_display(current_card),
             Space::new().height(20),
-            self.quiz_section(current_card),
+            match self.exercise {
+                Exercise::MultipleChoice => self.quiz_section(current_card),
+                Exercise::Writing => self.writing_section(current_card),
+            },
             Space::new().height(20),
             self.navigation_controls(),
         ]
@@ -198,10 +571,17 @@ impl PracticeView {
             text("No cards to practice yet.").size(16),
             text("Go back to Home and create some flashcards from Japanese text!").size(14),
             Space::new().height(20),
-            button("Back to Home")
-                .on_press(Message::BackToHome)
-                .padding(12)
-                .style(button_style),
+            row![
+                button("Custom Session")
+                    .on_press(Message::OpenCustomSession)
+                    .padding(12)
+                    .style(button_style),
+                button("Back to Home")
+                    .on_press(Message::BackToHome)
+                    .padding(12)
+                    .style(button_style),
+            ]
+            .spacing(10),
         ]
         .spacing(20)
         .padding(20)
@@ -230,6 +610,11 @@ impl PracticeView {
         ))
         .size(16);
 
+        let custom_button = button("Custom Session")
+            .on_press(Message::OpenCustomSession)
+            .padding(10)
+            .style(button_style);
+
         let back_button = button("← Back to Home")
             .on_press(Message::BackToHome)
             .padding(10)
@@ -237,6 +622,7 @@ impl PracticeView {
 
         row![
             column![title, stats].spacing(5).width(Length::Fill),
+            custom_button,
             back_button
         ]
         .align_y(Alignment::Center)
@@ -245,10 +631,35 @@ impl PracticeView {
     }
 
     fn progress_bar(&self) -> Element<'_, Message> {
+        // Once answered the card is rescheduled, so only show it before
+        let recall = self
+            .current_card()
+            .filter(|_| !self.is_answered())
+            .and_then(|card| card.srs_data.fsrs.as_ref())
+            .map(|state| {
+                format!(
+                    " · Predicted recall {:.0}%",
+                    fsrs::retrievability(state, chrono::Utc::now()) * 100.0
+                )
+            })
+            .unwrap_or_default();
+        let learning = if self.learning.is_empty() {
+            String::new()
+        } else {
+            format!(" · {} in learning", self.learning.len())
+        };
+        let unscheduled = if self.affects_scheduling {
+            ""
+        } else {
+            " · Not rescheduled"
+        };
         let progress_text = text(format!(
-            "Card {} of {}",
+            "Card {} of {}{}{}{}",
             self.current_index + 1,
-            self.cards.len()
+            self.cards.len(),
+            learning,
+            unscheduled,
+            recall
         ))
         .size(14);
 
@@ -259,6 +670,56 @@ impl PracticeView {
             .into()
     }
 
+    /// Badge listing where the current card disagrees with local data
+    fn verification_warning(&self) -> Element<'_, Message> {
+        let mismatches = self
+            .current_card()
+            .map_or(&[][..], |card| self.mismatches(&card.id));
+        if mismatches.is_empty() {
+            return column![].into();
+        }
+
+        let details = mismatches.iter().fold(column![].spacing(3), |col, mismatch| {
+            col.push(text(format!("• {}", mismatch.describe())).size(12))
+        });
+
+        let regenerate_label = if self.regenerating {
+            "Regenerating..."
+        } else {
+            "Regenerate"
+        };
+
+        container(
+            column![
+                text("⚠ This card disagrees with local data").size(14),
+                details,
+                row![
+                    button(regenerate_label)
+                        .on_press_maybe((!self.regenerating).then_some(Message::RegenerateCard))
+                        .padding(8)
+                        .style(button_style),
+                    button("Accept Local Data")
+                        .on_press_maybe(
+                            (!self.regenerating).then_some(Message::AcceptLocalCardData)
+                        )
+                        .padding(8)
+                        .style(button_style),
+                ]
+                .spacing(10),
+            ]
+            .spacing(8),
+        )
+        .padding(12)
+        .width(Length::Fill)
+        .style(|theme: &iced::Theme| {
+            let mut style = section_style(theme);
+            style.background = Some(Color::from_rgb(0.85, 0.6, 0.2).into());
+            style.text_color = Some(Color::WHITE);
+            style
+        })
+        .into()
+    }
+
     fn card_display<'a>(&'a self, card: &'a CardType) -> Element<'a, Message> {
         match card {
             CardType::Vocabulary(vocab) => self.vocabulary_card(vocab),
@@ -332,22 +793,14 @@ impl PracticeView {
     fn quiz_section<'a>(&'a self, card: &'a CardType) -> Element<'a, Message> {
         let question: Element<'a, Message> = text("What does this mean?").size(18).into();
 
-        let (correct_answer, wrong_answers) = match card {
-            CardType::Vocabulary(vocab) => (&vocab.meaning, &vocab.wrong_answers),
-            CardType::Grammar(grammar) => (&grammar.explanation, &grammar.wrong_answers),
-        };
-
-        // Shuffle answers (in real implementation, this would be done when card is shown)
-        let mut all_answers = vec![correct_answer.clone()];
-        all_answers.extend(wrong_answers.iter().cloned());
-
-        let answer_button_elements: Vec<Element<'a, Message>> = all_answers
-            .into_iter()
-            .enumerate()
+        // Options are shuffled each time a card is shown
+        let correct_position = self.correct_position();
+        let answer_button_elements: Vec<Element<'a, Message>> = (0..self.answer_order.len())
+            .filter_map(|idx| Some((idx, self.answer_at(card, idx)?)))
             .map(|(idx, answer)| {
                 let button_style_fn = match &self.quiz_state {
                     QuizState::Question => button_style,
-                    QuizState::AnswerCorrect if idx == 0 => {
+                    QuizState::AnswerCorrect if idx == correct_position => {
                         |theme: &iced::Theme, status| {
                             let mut style = button_style(theme, status);
                             style.background = Some(Color::from_rgb(0.2, 0.8, 0.2).into());
@@ -397,57 +850,219 @@ impl PracticeView {
         let answer_buttons: Element<'a, Message> = column(answer_button_elements).spacing(10).into();
 
         let examples_section = if self.quiz_state != QuizState::Question {
-            let examples = match card {
-                CardType::Vocabulary(vocab) => &vocab.example_sentences,
-                CardType::Grammar(grammar) => &grammar.example_sentences,
-            };
-
-            if self.show_examples {
-                let examples_list = examples.iter().fold(
-                    column![].spacing(15),
-                    |col, example| {
-                        col.push(column![
-                            text(&example.japanese).size(16),
-                            text(&example.english).size(14),
-                        ].spacing(5))
-                    },
-                );
-
-                column![
-                    Space::new().height(20),
-                    button("Hide Examples")
-                        .on_press(Message::ToggleExamples)
-                        .padding(10)
-                        .style(button_style),
-                    container(examples_list)
-                        .padding(15)
-                        .width(Length::Fill)
-                        .style(section_style),
-                ]
-            } else {
-                column![
-                    Space::new().height(20),
-                    button("Show Example Sentences")
-                        .on_press(Message::ToggleExamples)
-                        .padding(10)
-                        .style(button_style),
-                ]
-            }
+            self.examples_section(card)
         } else {
-            column![]
+            column![].into()
         };
 
-        column![question, answer_buttons, examples_section]
+        column![question, answer_buttons, self.rating_buttons(), examples_section]
             .spacing(15)
             .into()
     }
 
+    /// Again/Hard/Good/Easy buttons, each with the delay until the next review
+    fn rating_buttons(&self) -> Element<'_, Message> {
+        let Some(pending) = &self.pending_rating else {
+            return column![].into();
+        };
+
+        let buttons = pending
+            .previews
+            .iter()
+            .fold(row![].spacing(10), |row, (rating, delay)| {
+                row.push(
+                    button(
+                        column![text(rating.name()).size(16), text(delay).size(12)]
+                            .align_x(Alignment::Center)
+                            .width(Length::Fill),
+                    )
+                    .on_press(Message::RateAnswer(*rating))
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(button_style),
+                )
+            });
+        let default = Rating::from_quality(pending.answer.quality);
+
+        column![
+            text("How well did you know it?").size(16),
+            buttons,
+            text(format!("Or move on to rate it {}.", default.name())).size(12),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    /// Example sentences of a card behind a show/hide toggle
+    fn examples_section<'a>(&'a self, card: &'a CardType) -> Element<'a, Message> {
+        let examples = match card {
+            CardType::Vocabulary(vocab) => &vocab.example_sentences,
+            CardType::Grammar(grammar) => &grammar.example_sentences,
+        };
+
+        let section = if self.show_examples {
+            let examples_list = examples.iter().fold(
+                column![].spacing(15),
+                |col, example| {
+                    col.push(column![
+                        text(&example.japanese).size(16),
+                        text(&example.translation).size(14),
+                    ].spacing(5))
+                },
+            );
+
+            column![
+                Space::new().height(20),
+                button("Hide Examples")
+                    .on_press(Message::ToggleExamples)
+                    .padding(10)
+                    .style(button_style),
+                container(examples_list)
+                    .padding(15)
+                    .width(Length::Fill)
+                    .style(section_style),
+            ]
+        } else {
+            column![
+                Space::new().height(20),
+                button("Show Example Sentences")
+                    .on_press(Message::ToggleExamples)
+                    .padding(10)
+                    .style(button_style),
+            ]
+        };
+
+        section.into()
+    }
+
+    fn exercise_selector(&self) -> Element<'_, Message> {
+        let option = |label: &'static str, exercise: Exercise| {
+            let selected = self.exercise == exercise;
+            button(text(label).size(14))
+                .on_press_maybe(
+                    (!selected && !self.grading).then_some(Message::SelectExercise(exercise)),
+                )
+                .padding(8)
+                .style(move |theme: &iced::Theme, status| {
+                    let mut style = button_style(theme, status);
+                    if selected {
+                        style.background = Some(theme.extended_palette().primary.base.color.into());
+                    }
+                    style
+                })
+        };
+
+        row![
+            option("Multiple Choice", Exercise::MultipleChoice),
+            option("Write a Sentence", Exercise::Writing),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn writing_section<'a>(&'a self, card: &'a CardType) -> Element<'a, Message> {
+        let prompt = match card {
+            CardType::Vocabulary(vocab) => format!(
+                "Write a Japanese sentence using {} ({})",
+                vocab.kanji, vocab.meaning
+            ),
+            CardType::Grammar(grammar) => format!(
+                "Write a Japanese sentence using {} ({})",
+                grammar.pattern, grammar.explanation
+            ),
+        };
+
+        let input = text_input("文を書いてください... (Write your sentence)", &self.writing_input)
+            .on_input(Message::WritingInputChanged)
+            .on_submit(Message::SubmitWriting)
+            .padding(12)
+            .size(16)
+            .style(text_input_style);
+
+        let submit_label = if self.grading { "Grading..." } else { "Submit" };
+        let submit_button = button(submit_label)
+            .on_press_maybe(self.can_submit_writing().then_some(Message::SubmitWriting))
+            .padding(12)
+            .style(button_style);
+
+        let grade_error = match &self.grade_error {
+            Some(error) => column![text(error).size(12)],
+            None => column![],
+        };
+
+        let result: Element<'a, Message> = match &self.grade {
+            Some(grade) => column![self.grade_display(grade), self.examples_section(card)]
+                .spacing(10)
+                .into(),
+            None => column![].into(),
+        };
+
+        column![
+            text(prompt).size(18),
+            row![input, submit_button].spacing(10),
+            grade_error,
+            result,
+        ]
+        .spacing(15)
+        .into()
+    }
+
+    fn grade_display<'a>(&self, grade: &'a WritingGrade) -> Element<'a, Message> {
+        let passed = grade.quality() >= srs::PASSING_QUALITY;
+        let headline = format!(
+            "Score: {}/{}{}",
+            grade.score.min(srs::MAX_QUALITY),
+            srs::MAX_QUALITY,
+            if grade.target_used {
+                ""
+            } else {
+                " — the target was not used correctly"
+            }
+        );
+
+        let corrections = grade
+            .corrections
+            .iter()
+            .fold(column![].spacing(5), |col, correction| {
+                col.push(
+                    text(format!(
+                        "{} → {}: {}",
+                        correction.original, correction.corrected, correction.explanation
+                    ))
+                    .size(14),
+                )
+            });
+
+        container(
+            column![
+                text(headline).size(18),
+                text(&grade.feedback).size(14),
+                corrections,
+            ]
+            .spacing(8),
+        )
+        .padding(15)
+        .width(Length::Fill)
+        .style(move |theme: &iced::Theme| {
+            let mut style = section_style(theme);
+            let color = if passed {
+                Color::from_rgb(0.2, 0.8, 0.2)
+            } else {
+                Color::from_rgb(0.8, 0.2, 0.2)
+            };
+            style.border.color = color;
+            style.border.width = 2.0;
+            style
+        })
+        .into()
+    }
+
     fn navigation_controls(&self) -> Element<'_, Message> {
         let prev_button = button("← Previous")
             .padding(12)
             .style(button_style);
 
-        let prev_button = if self.current_index > 0 {
+        let prev_button = if self.current_index > 0 && !self.grading {
             prev_button.on_press(Message::PreviousCard)
         } else {
             prev_button
@@ -457,17 +1072,75 @@ impl PracticeView {
             .padding(12)
             .style(button_style);
 
-        let next_button = if self.current_index < self.cards.len() - 1
-            && self.quiz_state != QuizState::Question
-        {
+        let now = Utc::now();
+        let has_next = self.current_index < self.cards.len() - 1 || self.learning_card_ready(now);
+        let next_button = if has_next && self.is_answered() && !self.grading {
             next_button.on_press(Message::NextCard)
         } else {
             next_button
         };
 
-        row![prev_button, Space::new().width(Fill), next_button]
+        // At the end of the queue, say when the next card in learning comes back
+        let waiting = match self.next_learning_due() {
+            Some(due) if !has_next && self.is_answered() => text(format!(
+                "Next learning card due at {}",
+                due.with_timezone(&Local).format("%H:%M")
+            ))
+            .size(14),
+            _ => text(""),
+        };
+
+        row![prev_button, Space::new().width(Fill), waiting, next_button]
             .spacing(10)
             .width(Length::Fill)
+            .align_y(Alignment::Center)
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::flashcard::SRSData;
+    use crate::models::{JLPTLevel, Language};
+
+    #[test]
+    fn answers_are_graded_where_the_correct_one_was_shuffled_to() {
+        let now = Utc::now();
+        let card = FlashCard {
+            id: "card".to_string(),
+            deck_id: "deck".to_string(),
+            card_type: CardType::Vocabulary(VocabularyCard {
+                kanji: "猫".to_string(),
+                hiragana: "ねこ".to_string(),
+                romaji: "neko".to_string(),
+                meaning: "cat".to_string(),
+                wrong_answers: vec!["dog".to_string(), "bird".to_string(), "fish".to_string()],
+                example_sentences: Vec::new(),
+                jlpt_level: JLPTLevel::N5,
+                language: Language::English,
+            }),
+            srs_data: SRSData::new_card(now),
+            created_at: now,
+            updated_at: now,
+        };
+        let mut view = PracticeView::default();
+        view.set_cards(vec![card]);
+
+        let mut order = view.answer_order.clone();
+        order.sort();
+        assert_eq!(order, [0, 1, 2, 3]);
+
+        // Correct answer shown last, wrong answers before it
+        view.answer_order = vec![2, 1, 3, 0];
+        let right = view.answer_review(3).unwrap();
+        assert_eq!(right.answer, "cat");
+        assert!(right.quality >= srs::PASSING_QUALITY);
+        let wrong = view.answer_review(0).unwrap();
+        assert_eq!(wrong.answer, "bird");
+        assert!(wrong.quality < srs::PASSING_QUALITY);
+
+        let _ = view.update(Message::SelectAnswer(3));
+        assert_eq!(view.quiz_state, QuizState::AnswerCorrect);
+    }
+}
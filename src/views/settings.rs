@@ -4,31 +4,92 @@
 //! - Theme (dark/light mode)
 //! - Font size
 //! - User profile for personalized AI responses
-//! - Gemini API key for LLM integration
-//! - SRS parameters (daily limits, new cards)
+//! - Language of meanings, explanations and translations
+//! - AI-written learner report and its revision history
+//! - Gemini API key for LLM integration, or offline demo mode
+//! - LLM request limits and daily token budget
+//! - SRS parameters (daily limits, day rollover, learning steps) and FSRS parameter fitting
+//! - Practice queue order, new card spacing and sibling burying
+//! - Rating multiple-choice answers yourself (Again/Hard/Good/Easy)
+//! - Leech threshold and whether leeches are suspended
+//! - Interval fuzz and review load balancing
 
 use crate::constants::{srs, ui};
-use crate::ui::{button_style, section_style, slider_style, text_input_style};
-use iced::widget::{button, column, container, row, scrollable, slider, text, text_input};
+use crate::models::Language;
+use crate::models::deck::{LLMUsage, LearnerReport, ReportSource};
+use crate::services::llm::governor::GovernorConfig;
+use crate::services::load_balancer::LoadBalancing;
+use crate::services::scheduler::{self, LearningSteps, LeechPolicy};
+use crate::services::study_day::{self, DailyLimits, DayRollover};
+use crate::services::study_queue::{QueueOptions, QueueOrder};
+use crate::ui::{button_style, section_style, slider_style, text_editor_style, text_input_style};
+use iced::widget::{
+    button, column, container, row, scrollable, slider, text, text_editor, text_input, toggler,
+};
 use iced::{Element, Length, Task, alignment};
 
 #[derive(Debug, Clone)]
 pub struct SettingsView {
     font_size: u16,
     user_profile: String,
+    language: Language,
     api_key: String,
+    demo_mode: bool,
+    requests_per_minute: String,
+    daily_token_budget: String,
+    llm_usage: Option<LLMUsage>,
+    learner_report: text_editor::Content,
+    report_history: Vec<LearnerReport>,
+    report_status: Option<String>,
+    generating_report: bool,
     daily_review_limit: String,
     new_cards_per_day: String,
+    day_starts_at: String,
+    utc_offset: String,
+    learning_steps: String,
+    relearning_steps: String,
+    queue_order: QueueOrder,
+    reviews_per_new_card: String,
+    bury_siblings: bool,
+    self_rating: bool,
+    leech_threshold: String,
+    suspend_leeches: bool,
+    load_balancing: LoadBalancing,
+    fsrs_fitted: bool,
+    fsrs_status: Option<String>,
 }
 
 impl Default for SettingsView {
     fn default() -> Self {
+        let governor = GovernorConfig::default();
         Self {
             font_size: ui::DEFAULT_FONT_SIZE,
             user_profile: String::new(),
+            language: Language::default(),
             api_key: String::new(),
+            demo_mode: false,
+            requests_per_minute: governor.requests_per_minute.to_string(),
+            daily_token_budget: governor.daily_token_budget.to_string(),
+            llm_usage: None,
+            learner_report: text_editor::Content::new(),
+            report_history: Vec::new(),
+            report_status: None,
+            generating_report: false,
             daily_review_limit: srs::DEFAULT_DAILY_REVIEW_LIMIT.to_string(),
             new_cards_per_day: srs::DEFAULT_NEW_CARDS_PER_DAY.to_string(),
+            day_starts_at: srs::DEFAULT_DAY_STARTS_AT.to_string(),
+            utc_offset: String::new(),
+            learning_steps: srs::DEFAULT_LEARNING_STEPS.to_string(),
+            relearning_steps: srs::DEFAULT_RELEARNING_STEPS.to_string(),
+            queue_order: QueueOrder::default(),
+            reviews_per_new_card: srs::DEFAULT_REVIEWS_PER_NEW_CARD.to_string(),
+            bury_siblings: true,
+            self_rating: false,
+            leech_threshold: srs::DEFAULT_LEECH_THRESHOLD.to_string(),
+            suspend_leeches: false,
+            load_balancing: LoadBalancing::default(),
+            fsrs_fitted: false,
+            fsrs_status: None,
         }
     }
 }
@@ -38,15 +99,224 @@ pub enum Message {
     BackToHome,
     FontSizeChanged(u16),
     UserProfileChanged(String),
+    LanguageSelected(Language),
     ApiKeyChanged(String),
+    DemoModeToggled(bool),
+    RequestsPerMinuteChanged(String),
+    DailyTokenBudgetChanged(String),
+    LearnerReportEdited(text_editor::Action),
+    SaveLearnerReport,
+    RegenerateLearnerReport,
+    SelectReportRevision(String),
     DailyReviewLimitChanged(String),
     NewCardsPerDayChanged(String),
+    DayStartsAtChanged(String),
+    UtcOffsetChanged(String),
+    LearningStepsChanged(String),
+    RelearningStepsChanged(String),
+    QueueOrderSelected(QueueOrder),
+    ReviewsPerNewCardChanged(String),
+    BurySiblingsToggled(bool),
+    SelfRatingToggled(bool),
+    LeechThresholdChanged(String),
+    SuspendLeechesToggled(bool),
+    FuzzIntervalsToggled(bool),
+    BalanceLoadToggled(bool),
+    OptimizeFsrs,
+    ResetFsrs,
+    OpenDeveloperLog,
 }
 
 impl SettingsView {
+    /// Gemini API key entered by the user
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Free-form description of the user's level and goals
+    pub fn user_profile(&self) -> &str {
+        &self.user_profile
+    }
+
+    /// Language meanings, explanations and translations are written in
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Restore the stored explanation language
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    /// Check if LLM requests are answered from local fixtures
+    pub fn demo_mode(&self) -> bool {
+        self.demo_mode
+    }
+
+    /// Restore the stored demo mode flag
+    pub fn set_demo_mode(&mut self, demo_mode: bool) {
+        self.demo_mode = demo_mode;
+    }
+
+    /// LLM limits entered by the user, if both fields are valid numbers
+    pub fn governor_config(&self) -> Option<GovernorConfig> {
+        let requests_per_minute = self.requests_per_minute.trim().parse().ok()?;
+        let daily_token_budget = self.daily_token_budget.trim().parse().ok()?;
+
+        (requests_per_minute > 0).then_some(GovernorConfig {
+            requests_per_minute,
+            daily_token_budget,
+        })
+    }
+
+    /// Fill the LLM limit fields from stored values
+    pub fn set_governor_config(&mut self, config: GovernorConfig) {
+        self.requests_per_minute = config.requests_per_minute.to_string();
+        self.daily_token_budget = config.daily_token_budget.to_string();
+    }
+
+    /// Daily limits entered by the user, if both fields are whole numbers
+    pub fn daily_limits(&self) -> Option<DailyLimits> {
+        Some(DailyLimits {
+            reviews: self.daily_review_limit.trim().parse().ok()?,
+            new_cards: self.new_cards_per_day.trim().parse().ok()?,
+        })
+    }
+
+    /// Fill the daily limit fields from stored values
+    pub fn set_daily_limits(&mut self, limits: DailyLimits) {
+        self.daily_review_limit = limits.reviews.to_string();
+        self.new_cards_per_day = limits.new_cards.to_string();
+    }
+
+    /// Start of the study day entered by the user, if the hour and offset are valid
+    pub fn day_rollover(&self) -> Option<DayRollover> {
+        let starts_at = self
+            .day_starts_at
+            .trim()
+            .parse()
+            .ok()
+            .filter(|hour| *hour < 24)?;
+        Some(DayRollover {
+            starts_at,
+            utc_offset: study_day::parse_utc_offset(&self.utc_offset)?,
+        })
+    }
+
+    /// Fill the day rollover fields from stored values
+    pub fn set_day_rollover(&mut self, rollover: DayRollover) {
+        self.day_starts_at = rollover.starts_at.to_string();
+        self.utc_offset = study_day::format_utc_offset(rollover.utc_offset);
+    }
+
+    /// Practice queue options entered by the user, if the new card spacing is a whole number
+    pub fn queue_options(&self) -> Option<QueueOptions> {
+        Some(QueueOptions {
+            order: self.queue_order,
+            reviews_per_new_card: self.reviews_per_new_card.trim().parse().ok()?,
+            bury_siblings: self.bury_siblings,
+        })
+    }
+
+    /// Fill the practice queue fields from stored values
+    pub fn set_queue_options(&mut self, options: QueueOptions) {
+        self.queue_order = options.order;
+        self.reviews_per_new_card = options.reviews_per_new_card.to_string();
+        self.bury_siblings = options.bury_siblings;
+    }
+
+    /// Restore the stored self-rating flag
+    pub fn set_self_rating(&mut self, self_rating: bool) {
+        self.self_rating = self_rating;
+    }
+
+    /// Leech policy entered by the user, if the threshold is a whole number
+    pub fn leech_policy(&self) -> Option<LeechPolicy> {
+        Some(LeechPolicy {
+            threshold: self.leech_threshold.trim().parse().ok()?,
+            suspend: self.suspend_leeches,
+        })
+    }
+
+    /// Fill the leech fields from stored values
+    pub fn set_leech_policy(&mut self, policy: LeechPolicy) {
+        self.leech_threshold = policy.threshold.to_string();
+        self.suspend_leeches = policy.suspend;
+    }
+
+    /// Interval fuzz options chosen by the user
+    pub fn load_balancing(&self) -> LoadBalancing {
+        self.load_balancing
+    }
+
+    /// Restore the stored interval fuzz options
+    pub fn set_load_balancing(&mut self, options: LoadBalancing) {
+        self.load_balancing = options;
+    }
+
+    /// Learning and relearning steps entered by the user, if both fields are valid
+    pub fn learning_steps(&self) -> Option<LearningSteps> {
+        Some(LearningSteps {
+            learning: scheduler::parse_steps(&self.learning_steps)?,
+            relearning: scheduler::parse_steps(&self.relearning_steps)?,
+        })
+    }
+
+    /// Learning steps as entered by the user
+    pub fn learning_steps_text(&self) -> &str {
+        &self.learning_steps
+    }
+
+    /// Relearning steps as entered by the user
+    pub fn relearning_steps_text(&self) -> &str {
+        &self.relearning_steps
+    }
+
+    /// Fill the step fields from stored values
+    pub fn set_learning_steps(&mut self, steps: &LearningSteps) {
+        self.learning_steps = scheduler::format_steps(&steps.learning);
+        self.relearning_steps = scheduler::format_steps(&steps.relearning);
+    }
+
+    /// Show today's LLM usage
+    pub fn set_llm_usage(&mut self, usage: LLMUsage) {
+        self.llm_usage = Some(usage);
+    }
+
+    /// Learner report text as currently shown in the editor
+    pub fn learner_report(&self) -> String {
+        self.learner_report.text()
+    }
+
+    /// Show the stored report revisions, newest first, and load the latest into the editor
+    pub fn set_report_history(&mut self, history: Vec<LearnerReport>) {
+        let latest = history
+            .first()
+            .map(|report| report.content.as_str())
+            .unwrap_or_default();
+        self.learner_report = text_editor::Content::with_text(latest);
+        self.report_history = history;
+    }
+
+    /// Show the result of saving or regenerating the report
+    pub fn set_report_status(&mut self, status: Option<String>) {
+        self.report_status = status;
+    }
+
+    /// Mark a report regeneration as running or finished
+    /// Record whether FSRS decks use fitted parameters, with a status message
+    pub fn set_fsrs_parameters(&mut self, fitted: bool, status: Option<String>) {
+        self.fsrs_fitted = fitted;
+        self.fsrs_status = status;
+    }
+
+    pub fn set_generating_report(&mut self, generating: bool) {
+        self.generating_report = generating;
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::BackToHome => Task::none(),
+            Message::BackToHome | Message::OpenDeveloperLog => Task::none(),
             Message::FontSizeChanged(size) => {
                 self.font_size = size.clamp(ui::MIN_FONT_SIZE, ui::MAX_FONT_SIZE);
                 Task::none()
@@ -55,10 +325,45 @@ impl SettingsView {
                 self.user_profile = value;
                 Task::none()
             }
+            Message::LanguageSelected(language) => {
+                self.language = language;
+                Task::none()
+            }
             Message::ApiKeyChanged(value) => {
                 self.api_key = value;
                 Task::none()
             }
+            Message::DemoModeToggled(enabled) => {
+                self.demo_mode = enabled;
+                Task::none()
+            }
+            Message::RequestsPerMinuteChanged(value) => {
+                self.requests_per_minute = value;
+                Task::none()
+            }
+            Message::DailyTokenBudgetChanged(value) => {
+                self.daily_token_budget = value;
+                Task::none()
+            }
+            Message::LearnerReportEdited(action) => {
+                self.learner_report.perform(action);
+                Task::none()
+            }
+            // Saving, regenerating and fitting are handled by the app
+            Message::SaveLearnerReport
+            | Message::RegenerateLearnerReport
+            | Message::OptimizeFsrs
+            | Message::ResetFsrs => Task::none(),
+            Message::SelectReportRevision(id) => {
+                if let Some(report) = self.report_history.iter().find(|report| report.id == id) {
+                    self.learner_report = text_editor::Content::with_text(&report.content);
+                    self.report_status = Some(format!(
+                        "Loaded the revision from {}. Save to make it current.",
+                        report.created_at.format("%Y-%m-%d %H:%M")
+                    ));
+                }
+                Task::none()
+            }
             Message::DailyReviewLimitChanged(value) => {
                 self.daily_review_limit = value;
                 Task::none()
@@ -67,6 +372,54 @@ impl SettingsView {
                 self.new_cards_per_day = value;
                 Task::none()
             }
+            Message::DayStartsAtChanged(value) => {
+                self.day_starts_at = value;
+                Task::none()
+            }
+            Message::UtcOffsetChanged(value) => {
+                self.utc_offset = value;
+                Task::none()
+            }
+            Message::LearningStepsChanged(value) => {
+                self.learning_steps = value;
+                Task::none()
+            }
+            Message::RelearningStepsChanged(value) => {
+                self.relearning_steps = value;
+                Task::none()
+            }
+            Message::QueueOrderSelected(order) => {
+                self.queue_order = order;
+                Task::none()
+            }
+            Message::ReviewsPerNewCardChanged(value) => {
+                self.reviews_per_new_card = value;
+                Task::none()
+            }
+            Message::BurySiblingsToggled(enabled) => {
+                self.bury_siblings = enabled;
+                Task::none()
+            }
+            Message::SelfRatingToggled(enabled) => {
+                self.self_rating = enabled;
+                Task::none()
+            }
+            Message::LeechThresholdChanged(value) => {
+                self.leech_threshold = value;
+                Task::none()
+            }
+            Message::SuspendLeechesToggled(enabled) => {
+                self.suspend_leeches = enabled;
+                Task::none()
+            }
+            Message::FuzzIntervalsToggled(enabled) => {
+                self.load_balancing.fuzz = enabled;
+                Task::none()
+            }
+            Message::BalanceLoadToggled(enabled) => {
+                self.load_balancing.balance = enabled;
+                Task::none()
+            }
         }
     }
 
@@ -105,6 +458,43 @@ impl SettingsView {
                     .size(16)
                     .width(Length::Fill)
                     .style(text_input_style),
+                text("Explain words and translate sentences in:").size(14),
+                self.language_selector(),
+            ]
+            .spacing(12),
+        )
+        .padding(20)
+        .style(section_style);
+
+        let report_section = container(
+            column![
+                text("Learner Report").size(24),
+                text("Written by the AI after each practice session and included in every explanation. Edit it to correct anything it got wrong.").size(14),
+                text_editor(&self.learner_report)
+                    .placeholder("No report yet. Finish a practice session or press Regenerate.")
+                    .on_action(Message::LearnerReportEdited)
+                    .padding(12)
+                    .height(Length::Fixed(240.0))
+                    .style(text_editor_style),
+                row![
+                    button("Save Edits")
+                        .on_press(Message::SaveLearnerReport)
+                        .padding(10)
+                        .style(button_style),
+                    button(if self.generating_report {
+                        "Generating..."
+                    } else {
+                        "Regenerate"
+                    })
+                    .on_press_maybe(
+                        (!self.generating_report).then_some(Message::RegenerateLearnerReport)
+                    )
+                    .padding(10)
+                    .style(button_style),
+                ]
+                .spacing(12),
+                text(self.report_status.as_deref().unwrap_or_default()).size(14),
+                self.report_revisions(),
             ]
             .spacing(12),
         )
@@ -122,6 +512,36 @@ impl SettingsView {
                     .secure(true)
                     .width(Length::Fill)
                     .style(text_input_style),
+                toggler(self.demo_mode)
+                    .label(
+                        "Demo mode: answer from local fixture files, no API key or network needed"
+                    )
+                    .on_toggle(Message::DemoModeToggled),
+                row![
+                    text("Requests per minute"),
+                    text_input("10", &self.requests_per_minute)
+                        .on_input(Message::RequestsPerMinuteChanged)
+                        .padding(10)
+                        .width(Length::Fixed(100.0))
+                        .style(text_input_style),
+                ]
+                .spacing(12)
+                .align_y(alignment::Vertical::Center),
+                row![
+                    text("Daily token budget (0 = unlimited)"),
+                    text_input("500000", &self.daily_token_budget)
+                        .on_input(Message::DailyTokenBudgetChanged)
+                        .padding(10)
+                        .width(Length::Fixed(140.0))
+                        .style(text_input_style),
+                ]
+                .spacing(12)
+                .align_y(alignment::Vertical::Center),
+                self.llm_limits_status(),
+                button("Developer Log")
+                    .on_press(Message::OpenDeveloperLog)
+                    .padding(10)
+                    .style(button_style),
             ]
             .spacing(12),
         )
@@ -151,6 +571,12 @@ impl SettingsView {
                 ]
                 .spacing(12)
                 .align_y(alignment::Vertical::Center),
+                self.day_rollover_settings(),
+                self.queue_settings(),
+                self.learning_steps_settings(),
+                self.leech_settings(),
+                self.load_balancing_settings(),
+                self.fsrs_settings(),
             ]
             .spacing(16),
         )
@@ -162,6 +588,7 @@ impl SettingsView {
             text("Customize the app to match your study preferences.").size(16),
             appearance_section,
             profile_section,
+            report_section,
             llm_section,
             srs_section,
             button("Back to Home")
@@ -184,4 +611,273 @@ impl SettingsView {
         .height(Length::Fill)
         .into()
     }
+
+    fn day_rollover_settings(&self) -> Element<'_, Message> {
+        let status = if self.daily_limits().is_none() {
+            "Daily limits must be whole numbers."
+        } else if self.day_rollover().is_none() {
+            "The day starts at an hour from 0 to 23, in a timezone like +9 or -05:30."
+        } else {
+            "Limits reset when the next day starts, so late sessions count toward the evening."
+        };
+
+        column![
+            row![
+                text("Next day starts at (hour)"),
+                text_input("4", &self.day_starts_at)
+                    .on_input(Message::DayStartsAtChanged)
+                    .padding(10)
+                    .width(Length::Fixed(100.0))
+                    .style(text_input_style),
+            ]
+            .spacing(12)
+            .align_y(alignment::Vertical::Center),
+            row![
+                text("Timezone (UTC offset)"),
+                text_input("System timezone", &self.utc_offset)
+                    .on_input(Message::UtcOffsetChanged)
+                    .padding(10)
+                    .width(Length::Fixed(200.0))
+                    .style(text_input_style),
+            ]
+            .spacing(12)
+            .align_y(alignment::Vertical::Center),
+            text(status).size(14),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn queue_settings(&self) -> Element<'_, Message> {
+        let orders = QueueOrder::ALL
+            .iter()
+            .fold(row![text("Review order")].spacing(8), |row, &order| {
+                let selected = order == self.queue_order;
+                row.push(
+                    button(text(order.name()).size(14))
+                        .on_press_maybe((!selected).then_some(Message::QueueOrderSelected(order)))
+                        .padding(8)
+                        .style(move |theme: &iced::Theme, status| {
+                            let mut style = button_style(theme, status);
+                            if selected {
+                                style.background = Some(theme.palette().primary.into());
+                                style.text_color = iced::Color::WHITE;
+                            }
+                            style
+                        }),
+                )
+            })
+            .align_y(alignment::Vertical::Center);
+
+        let mut settings = column![
+            orders,
+            row![
+                text("Reviews between new cards (0 = new cards first)"),
+                text_input("4", &self.reviews_per_new_card)
+                    .on_input(Message::ReviewsPerNewCardChanged)
+                    .padding(10)
+                    .width(Length::Fixed(100.0))
+                    .style(text_input_style),
+            ]
+            .spacing(12)
+            .align_y(alignment::Vertical::Center),
+            toggler(self.bury_siblings)
+                .label("Bury siblings (cards with the same word) until the next day")
+                .on_toggle(Message::BurySiblingsToggled),
+            toggler(self.self_rating)
+                .label("Rate multiple-choice answers yourself (Again/Hard/Good/Easy)")
+                .on_toggle(Message::SelfRatingToggled),
+        ]
+        .spacing(10);
+        if self.queue_options().is_none() {
+            settings = settings.push(
+                text("The number of reviews between new cards must be a whole number.").size(14),
+            );
+        }
+        settings.into()
+    }
+
+    fn leech_settings(&self) -> Element<'_, Message> {
+        let status = if self.leech_policy().is_some() {
+            "Cards forgotten this many times are listed on the Home screen as leeches."
+        } else {
+            "The leech threshold must be a whole number."
+        };
+
+        column![
+            row![
+                text("Leech threshold (lapses, 0 = never)"),
+                text_input("8", &self.leech_threshold)
+                    .on_input(Message::LeechThresholdChanged)
+                    .padding(10)
+                    .width(Length::Fixed(100.0))
+                    .style(text_input_style),
+            ]
+            .spacing(12)
+            .align_y(alignment::Vertical::Center),
+            toggler(self.suspend_leeches)
+                .label("Suspend leeches until they are fixed")
+                .on_toggle(Message::SuspendLeechesToggled),
+            text(status).size(14),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn load_balancing_settings(&self) -> Element<'_, Message> {
+        let mut balance = toggler(self.load_balancing.balance)
+            .label("Balance reviews: move fuzzed due dates to days with fewer reviews");
+        if self.load_balancing.fuzz {
+            balance = balance.on_toggle(Message::BalanceLoadToggled);
+        }
+
+        column![
+            toggler(self.load_balancing.fuzz)
+                .label("Fuzz intervals so cards added together do not all come due together")
+                .on_toggle(Message::FuzzIntervalsToggled),
+            balance,
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn learning_steps_settings(&self) -> Element<'_, Message> {
+        let status = if self.learning_steps().is_some() {
+            "New cards come back after each learning step, forgotten cards after each relearning step, before they get day intervals."
+        } else {
+            "Steps are durations like 1m 10m 1h, separated by spaces; a bare number counts as minutes."
+        };
+
+        column![
+            row![
+                text("Learning steps"),
+                text_input(srs::DEFAULT_LEARNING_STEPS, &self.learning_steps)
+                    .on_input(Message::LearningStepsChanged)
+                    .padding(10)
+                    .width(Length::Fixed(200.0))
+                    .style(text_input_style),
+            ]
+            .spacing(12)
+            .align_y(alignment::Vertical::Center),
+            row![
+                text("Relearning steps"),
+                text_input(srs::DEFAULT_RELEARNING_STEPS, &self.relearning_steps)
+                    .on_input(Message::RelearningStepsChanged)
+                    .padding(10)
+                    .width(Length::Fixed(200.0))
+                    .style(text_input_style),
+            ]
+            .spacing(12)
+            .align_y(alignment::Vertical::Center),
+            text(status).size(14),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn fsrs_settings(&self) -> Element<'_, Message> {
+        let parameters = if self.fsrs_fitted {
+            "FSRS decks use parameters fitted to your reviews."
+        } else {
+            "FSRS decks use the default parameters. Fit them to your reviews once you have enough."
+        };
+
+        let mut settings = column![
+            text(parameters).size(14),
+            row![
+                button("Optimize FSRS")
+                    .on_press(Message::OptimizeFsrs)
+                    .padding(10)
+                    .style(button_style),
+                button("Reset to Defaults")
+                    .on_press_maybe(self.fsrs_fitted.then_some(Message::ResetFsrs))
+                    .padding(10)
+                    .style(button_style),
+            ]
+            .spacing(10),
+        ]
+        .spacing(10);
+        if let Some(status) = &self.fsrs_status {
+            settings = settings.push(text(status).size(14));
+        }
+        settings.into()
+    }
+
+    fn language_selector(&self) -> Element<'_, Message> {
+        let buttons = Language::ALL
+            .iter()
+            .fold(row![].spacing(8), |row, &language| {
+                let selected = language == self.language;
+                row.push(
+                    button(text(language.name()).size(14))
+                        .on_press_maybe((!selected).then_some(Message::LanguageSelected(language)))
+                        .padding(8)
+                        .style(move |theme: &iced::Theme, status| {
+                            let mut style = button_style(theme, status);
+                            if selected {
+                                style.background = Some(theme.palette().primary.into());
+                                style.text_color = iced::Color::WHITE;
+                            }
+                            style
+                        }),
+                )
+            });
+        buttons.wrap().into()
+    }
+
+    fn report_revisions(&self) -> Element<'_, Message> {
+        if self.report_history.is_empty() {
+            return column![].into();
+        }
+
+        let revisions = self.report_history.iter().fold(
+            column![text("History").size(16)].spacing(6),
+            |col, report| {
+                let source = match report.source {
+                    ReportSource::Generated => "generated",
+                    ReportSource::Edited => "edited",
+                };
+                col.push(
+                    button(
+                        text(format!(
+                            "{} · {}",
+                            report.created_at.format("%Y-%m-%d %H:%M"),
+                            source
+                        ))
+                        .size(14),
+                    )
+                    .on_press(Message::SelectReportRevision(report.id.clone()))
+                    .padding(8)
+                    .style(button_style),
+                )
+            },
+        );
+
+        revisions.into()
+    }
+
+    fn llm_limits_status(&self) -> Element<'_, Message> {
+        let Some(config) = self.governor_config() else {
+            return text("Limits must be whole numbers, with at least one request per minute.")
+                .size(14)
+                .into();
+        };
+
+        let usage = self
+            .llm_usage
+            .as_ref()
+            .map_or((0, 0), |usage| (usage.total_tokens, usage.requests));
+        let budget = if config.daily_token_budget == 0 {
+            "no budget".to_string()
+        } else {
+            format!("budget {}", config.daily_token_budget)
+        };
+
+        text(format!(
+            "Today: {} tokens in {} requests ({})",
+            usage.0, usage.1, budget
+        ))
+        .size(14)
+        .into()
+    }
 }